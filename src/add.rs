@@ -0,0 +1,366 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use cargo::util::PartialVersion;
+use tokio::fs;
+
+use crate::{
+    flex_eprintln,
+    manifest::{IsobinManifest, IsobinManifestError, ManifestDependency},
+    manifest_editor::IsobinManifestEditor,
+    paths::isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+    providers::{
+        cargo::{CargoInstallDependency, CargoInstallDependencyDetailBuilder},
+        ProviderKind,
+    },
+    utils::{fs_ext::relative_path, io_ext},
+    InstallMode, Result, SpecifiedTarget,
+};
+
+#[derive(Default)]
+pub struct AddService {}
+
+impl AddService {
+    pub async fn add(&self, add_service_option: AddServiceOption) -> Result<()> {
+        let add_service_option = add_service_option.fix().await?;
+        let (specified_target, version) = Self::parse_target(add_service_option.target())?;
+        let name = specified_target.name().to_string();
+        let isobin_manifest =
+            IsobinManifest::load_from_file(add_service_option.isobin_manifest_path()).await?;
+        if isobin_manifest.exists_name(&name) && !add_service_option.force {
+            Err(AddServiceError::new_already_exists(
+                ProviderKind::Cargo,
+                name.clone(),
+            ))?;
+        }
+
+        let dependency = add_service_option.make_cargo_dependency(version);
+        dependency.validate().map_err(|err| {
+            IsobinManifestError::new_validate(ProviderKind::Cargo, name.clone(), err)
+        })?;
+        let mut isobin_manifest_editor =
+            IsobinManifestEditor::load_from_file(add_service_option.isobin_manifest_path()).await?;
+        isobin_manifest_editor.upsert_dependency(None, &ProviderKind::Cargo, &name, &dependency)?;
+        isobin_manifest_editor.save().await?;
+        flex_eprintln!(
+            add_service_option.quiet,
+            "Added {}/{} to {}",
+            ProviderKind::Cargo,
+            name,
+            add_service_option.isobin_manifest_path().display()
+        );
+
+        crate::install(
+            crate::InstallServiceOptionBuilder::default()
+                .isobin_manifest_path(add_service_option.isobin_manifest_path().into())
+                .mode(InstallMode::SpecificInstallTargetsOnly {
+                    specified_install_targets: vec![SpecifiedTarget::new(
+                        Some(ProviderKind::Cargo),
+                        name,
+                    )],
+                })
+                .quiet(add_service_option.quiet)
+                .build(),
+        )
+        .await
+    }
+
+    fn parse_target(target: &str) -> Result<(SpecifiedTarget, Option<PartialVersion>)> {
+        let (target, version) = match target.split_once('@') {
+            Some((target, version)) => (target, Some(PartialVersion::from_str(version)?)),
+            None => (target, None),
+        };
+        let specified_target = SpecifiedTarget::from_str(target)?;
+        match specified_target.provider_kind() {
+            None | Some(ProviderKind::Cargo) => Ok((specified_target, version)),
+            Some(provider) => {
+                Err(AddServiceError::new_unsupported_provider(provider.clone()).into())
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum AddServiceError {
+    #[error("{provider}/{name} already exists in isobin manifest. Use --force to overwrite it")]
+    AlreadyExists {
+        provider: ProviderKind,
+        name: String,
+    },
+
+    #[error("Adding {provider} dependencies is not supported. Edit the isobin manifest instead")]
+    UnsupportedProvider { provider: ProviderKind },
+
+    #[error("Not found the path {path}")]
+    NotFoundPath { path: String },
+}
+
+#[derive(Getters)]
+pub struct AddServiceOptionBase<P> {
+    quiet: bool,
+    force: bool,
+    target: String,
+    bins: Vec<String>,
+    registry: Option<String>,
+    index: Option<String>,
+    path: Option<PathBuf>,
+    git: Option<String>,
+    branch: Option<String>,
+    tag: Option<String>,
+    rev: Option<String>,
+    features: Vec<String>,
+    no_default_features: bool,
+    all_features: bool,
+    isobin_manifest_path: P,
+}
+
+pub type AddServiceOption = AddServiceOptionBase<Option<PathBuf>>;
+type FixedAddServiceOption = AddServiceOptionBase<PathBuf>;
+
+impl AddServiceOption {
+    async fn fix(self) -> Result<FixedAddServiceOption> {
+        let isobin_manifest_path =
+            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+        let path = match &self.path {
+            Some(path) => Some(
+                Self::fix_path(
+                    path,
+                    &std::env::current_dir()?,
+                    isobin_manifest_dir(&isobin_manifest_path)?,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+        Ok(FixedAddServiceOption {
+            quiet: self.quiet,
+            force: self.force,
+            target: self.target,
+            bins: self.bins,
+            registry: self.registry,
+            index: self.index,
+            path,
+            git: self.git,
+            branch: self.branch,
+            tag: self.tag,
+            rev: self.rev,
+            features: self.features,
+            no_default_features: self.no_default_features,
+            all_features: self.all_features,
+            isobin_manifest_path,
+        })
+    }
+
+    /// Returns `path`, which is given relative to `current_dir`, as relative to the manifest dir
+    /// which the paths in the manifest are resolved against.
+    async fn fix_path(
+        path: &Path,
+        current_dir: &Path,
+        isobin_manifest_dir: &Path,
+    ) -> Result<PathBuf> {
+        let path = current_dir.join(path);
+        let path = fs::canonicalize(&path)
+            .await
+            .map_err(|_| AddServiceError::new_not_found_path(io_ext::path_to_string(&path)))?;
+        let path = relative_path(isobin_manifest_dir, &path);
+        Ok(if path.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            path
+        })
+    }
+}
+
+impl FixedAddServiceOption {
+    fn make_cargo_dependency(&self, version: Option<PartialVersion>) -> CargoInstallDependency {
+        fn non_empty(values: &[String]) -> Option<Vec<String>> {
+            if values.is_empty() {
+                None
+            } else {
+                Some(values.to_vec())
+            }
+        }
+        CargoInstallDependency::from_detail(
+            CargoInstallDependencyDetailBuilder::default()
+                .bins(non_empty(&self.bins))
+                .version(version)
+                .registry(self.registry.clone())
+                .index(self.index.clone())
+                .path(self.path.clone())
+                .git(self.git.clone())
+                .branch(self.branch.clone())
+                .tag(self.tag.clone())
+                .rev(self.rev.clone())
+                .features(non_empty(&self.features))
+                .no_default_features(self.no_default_features.then_some(true))
+                .all_features(self.all_features.then_some(true))
+                .build(),
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct AddServiceOptionBuilder {
+    quiet: bool,
+    force: bool,
+    target: String,
+    bins: Vec<String>,
+    registry: Option<String>,
+    index: Option<String>,
+    path: Option<PathBuf>,
+    git: Option<String>,
+    branch: Option<String>,
+    tag: Option<String>,
+    rev: Option<String>,
+    features: Vec<String>,
+    no_default_features: bool,
+    all_features: bool,
+    isobin_manifest_path: Option<PathBuf>,
+}
+
+impl AddServiceOptionBuilder {
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+    pub fn target(mut self, target: String) -> Self {
+        self.target = target;
+        self
+    }
+    pub fn bins(mut self, bins: Vec<String>) -> Self {
+        self.bins = bins;
+        self
+    }
+    pub fn registry(mut self, registry: Option<String>) -> Self {
+        self.registry = registry;
+        self
+    }
+    pub fn index(mut self, index: Option<String>) -> Self {
+        self.index = index;
+        self
+    }
+    pub fn path(mut self, path: Option<PathBuf>) -> Self {
+        self.path = path;
+        self
+    }
+    pub fn git(mut self, git: Option<String>) -> Self {
+        self.git = git;
+        self
+    }
+    pub fn branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+    pub fn tag(mut self, tag: Option<String>) -> Self {
+        self.tag = tag;
+        self
+    }
+    pub fn rev(mut self, rev: Option<String>) -> Self {
+        self.rev = rev;
+        self
+    }
+    pub fn features(mut self, features: Vec<String>) -> Self {
+        self.features = features;
+        self
+    }
+    pub fn no_default_features(mut self, no_default_features: bool) -> Self {
+        self.no_default_features = no_default_features;
+        self
+    }
+    pub fn all_features(mut self, all_features: bool) -> Self {
+        self.all_features = all_features;
+        self
+    }
+    pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
+        self.isobin_manifest_path = Some(isobin_manifest_path);
+        self
+    }
+    pub fn build(self) -> AddServiceOption {
+        AddServiceOption {
+            quiet: self.quiet,
+            force: self.force,
+            target: self.target,
+            bins: self.bins,
+            registry: self.registry,
+            index: self.index,
+            path: self.path,
+            git: self.git,
+            branch: self.branch,
+            tag: self.tag,
+            rev: self.rev,
+            features: self.features,
+            no_default_features: self.no_default_features,
+            all_features: self.all_features,
+            isobin_manifest_path: self.isobin_manifest_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::current_source_dir;
+    use rstest::*;
+
+    #[rstest]
+    #[case("cargo-nextest", SpecifiedTarget::new(None, "cargo-nextest".into()), None)]
+    #[case(
+        "cargo-nextest@0.9",
+        SpecifiedTarget::new(None, "cargo-nextest".into()),
+        Some(PartialVersion::from_str("0.9").unwrap())
+    )]
+    #[case(
+        "cargo/cargo-nextest@0.9.61",
+        SpecifiedTarget::new(Some(ProviderKind::Cargo), "cargo-nextest".into()),
+        Some(PartialVersion::from_str("0.9.61").unwrap())
+    )]
+    fn parse_target_works(
+        #[case] target: &str,
+        #[case] expected_target: SpecifiedTarget,
+        #[case] expected_version: Option<PartialVersion>,
+    ) {
+        let (actual_target, actual_version) = AddService::parse_target(target).unwrap();
+        pretty_assertions::assert_eq!(expected_target, actual_target);
+        pretty_assertions::assert_eq!(expected_version, actual_version);
+    }
+
+    #[rstest]
+    #[case("../shared", "include/nested", "include/shared")]
+    #[case("shared", "include", "include/shared")]
+    #[case(".", ".", ".")]
+    #[case("..", "include", ".")]
+    #[tokio::test]
+    async fn fix_path_works(#[case] path: &str, #[case] current_dir: &str, #[case] expected: &str) {
+        let isobin_manifest_dir = current_source_dir!().join("testdata/isobin_manifests");
+        let actual = AddServiceOption::fix_path(
+            Path::new(path),
+            &isobin_manifest_dir.join(current_dir),
+            &isobin_manifest_dir,
+        )
+        .await
+        .unwrap();
+        pretty_assertions::assert_eq!(PathBuf::from(expected), actual);
+    }
+
+    #[tokio::test]
+    async fn fix_path_not_found_works() {
+        let isobin_manifest_dir = current_source_dir!().join("testdata/isobin_manifests");
+        let err = AddServiceOption::fix_path(
+            Path::new("not_found"),
+            &isobin_manifest_dir,
+            &isobin_manifest_dir,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AddServiceError>(),
+            Some(AddServiceError::NotFoundPath { .. })
+        ));
+    }
+}
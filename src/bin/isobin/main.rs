@@ -1,4 +1,4 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use isobin::{print_error, *};
 use std::{path::PathBuf, process::exit};
 
@@ -26,6 +26,11 @@ pub struct Application;
 impl Application {
     pub async fn exec(&self, args: Arguments) -> Result<()> {
         let subcommand = args.subcommand;
+        let subcommand = if args.global {
+            subcommand.into_global().await?
+        } else {
+            subcommand
+        };
         match subcommand {
             SubCommands::Path { base_options } => {
                 self.path(base_options.manifest_path, base_options.quiet)
@@ -34,10 +39,83 @@ impl Application {
             SubCommands::Sync {
                 base_options,
                 force,
+                locked,
+                group_options,
+            } => {
+                self.sync(
+                    base_options.manifest_path,
+                    base_options.quiet,
+                    force,
+                    locked,
+                    group_options,
+                )
+                .await
+            }
+            SubCommands::Install {
+                base_options,
+                force,
+                locked,
+                group_options,
+                targets,
+            } => {
+                self.install(
+                    base_options.manifest_path,
+                    base_options.quiet,
+                    force,
+                    locked,
+                    group_options,
+                    targets,
+                )
+                .await
+            }
+            SubCommands::Add {
+                base_options,
+                force,
+                target,
+                cargo_options,
             } => {
-                self.sync(base_options.manifest_path, base_options.quiet, force)
+                self.add(
+                    base_options.manifest_path,
+                    base_options.quiet,
+                    force,
+                    target,
+                    cargo_options,
+                )
+                .await
+            }
+            SubCommands::Remove {
+                base_options,
+                targets,
+            } => {
+                self.remove(base_options.manifest_path, base_options.quiet, targets)
                     .await
             }
+            SubCommands::List {
+                base_options,
+                format,
+            } => self.list(base_options.manifest_path, format).await,
+            SubCommands::Outdated {
+                base_options,
+                format,
+            } => {
+                self.outdated(base_options.manifest_path, base_options.quiet, format)
+                    .await
+            }
+            SubCommands::Upgrade {
+                base_options,
+                latest,
+                dry_run,
+                targets,
+            } => {
+                self.upgrade(
+                    base_options.manifest_path,
+                    base_options.quiet,
+                    latest,
+                    dry_run,
+                    targets,
+                )
+                .await
+            }
             SubCommands::Clean { base_options } => {
                 self.clean(base_options.manifest_path, base_options.quiet)
                     .await
@@ -79,10 +157,15 @@ impl Application {
         isobin_manifest_path: Option<PathBuf>,
         quiet: bool,
         force: bool,
+        locked: bool,
+        group_options: GroupOptions,
     ) -> Result<()> {
         let sync_service_option_builder = SyncServiceOptionBuilder::default()
             .quiet(quiet)
-            .force(force);
+            .force(force)
+            .locked(locked)
+            .groups(group_options.groups)
+            .without_groups(group_options.without_groups);
         let sync_service_option_builder = if let Some(isobin_manifest_path) = isobin_manifest_path {
             sync_service_option_builder.isobin_manifest_path(isobin_manifest_path)
         } else {
@@ -90,6 +173,199 @@ impl Application {
         };
         sync(sync_service_option_builder.build()).await
     }
+    async fn install(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        quiet: bool,
+        force: bool,
+        locked: bool,
+        group_options: GroupOptions,
+        targets: Vec<SpecifiedTarget>,
+    ) -> Result<()> {
+        let install_service_option_builder = InstallServiceOptionBuilder::default()
+            .quiet(quiet)
+            .force(force)
+            .locked(locked)
+            .groups(group_options.groups)
+            .without_groups(group_options.without_groups);
+        let install_service_option_builder =
+            if let Some(isobin_manifest_path) = isobin_manifest_path {
+                install_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+            } else {
+                install_service_option_builder
+            };
+        let install_service_option_builder =
+            install_service_option_builder.mode(InstallMode::SpecificInstallTargetsOnly {
+                specified_install_targets: targets,
+            });
+        install(install_service_option_builder.build()).await
+    }
+    async fn add(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        quiet: bool,
+        force: bool,
+        target: String,
+        cargo_options: CargoAddOptions,
+    ) -> Result<()> {
+        let add_service_option_builder = AddServiceOptionBuilder::default()
+            .quiet(quiet)
+            .force(force)
+            .target(target)
+            .bins(cargo_options.bins)
+            .registry(cargo_options.registry)
+            .index(cargo_options.index)
+            .path(cargo_options.path)
+            .git(cargo_options.git)
+            .branch(cargo_options.branch)
+            .tag(cargo_options.tag)
+            .rev(cargo_options.rev)
+            .features(cargo_options.features)
+            .no_default_features(cargo_options.no_default_features)
+            .all_features(cargo_options.all_features);
+        let add_service_option_builder = if let Some(isobin_manifest_path) = isobin_manifest_path {
+            add_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+        } else {
+            add_service_option_builder
+        };
+        add(add_service_option_builder.build()).await
+    }
+    async fn remove(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        quiet: bool,
+        targets: Vec<SpecifiedTarget>,
+    ) -> Result<()> {
+        let remove_service_option_builder = RemoveServiceOptionBuilder::default()
+            .quiet(quiet)
+            .targets(targets);
+        let remove_service_option_builder = if let Some(isobin_manifest_path) = isobin_manifest_path
+        {
+            remove_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+        } else {
+            remove_service_option_builder
+        };
+        remove(remove_service_option_builder.build()).await
+    }
+    async fn list(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        format: OutputFormat,
+    ) -> Result<()> {
+        let list_service_option_builder = ListServiceOptionBuilder::default();
+        let list_service_option_builder = if let Some(isobin_manifest_path) = isobin_manifest_path {
+            list_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+        } else {
+            list_service_option_builder
+        };
+        let list_items = list(list_service_option_builder.build()).await?;
+        match format {
+            OutputFormat::Text => {
+                for list_item in list_items.iter() {
+                    println!(
+                        "{}/{} {} ({})",
+                        list_item.provider(),
+                        list_item.name(),
+                        list_item.summary(),
+                        list_item.status()
+                    );
+                    if !list_item.bins().is_empty() {
+                        println!("    {}", list_item.bins().join(", "));
+                    }
+                }
+            }
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&list_items)?);
+            }
+        }
+        Ok(())
+    }
+    async fn outdated(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        quiet: bool,
+        format: OutputFormat,
+    ) -> Result<()> {
+        let outdated_service_option_builder = OutdatedServiceOptionBuilder::default().quiet(quiet);
+        let outdated_service_option_builder =
+            if let Some(isobin_manifest_path) = isobin_manifest_path {
+                outdated_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+            } else {
+                outdated_service_option_builder
+            };
+        let outdated_items = outdated(outdated_service_option_builder.build()).await?;
+        match format {
+            OutputFormat::Text => {
+                let rows = outdated_items
+                    .iter()
+                    .map(|outdated_item| {
+                        [
+                            format!("{}/{}", outdated_item.provider(), outdated_item.name()),
+                            outdated_item.requirement().clone(),
+                            outdated_item.installed().clone().unwrap_or("-".into()),
+                            outdated_item.compatible().clone().unwrap_or("-".into()),
+                            outdated_item.latest().clone().unwrap_or("-".into()),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let header =
+                    ["Name", "Requirement", "Installed", "Compatible", "Latest"].map(String::from);
+                let widths = (0..header.len())
+                    .map(|i| {
+                        std::iter::once(&header)
+                            .chain(rows.iter())
+                            .map(|row| row[i].len())
+                            .max()
+                            .unwrap_or(0)
+                    })
+                    .collect::<Vec<_>>();
+                for row in std::iter::once(&header).chain(rows.iter()) {
+                    let line = row
+                        .iter()
+                        .zip(widths.iter())
+                        .map(|(column, width)| format!("{column:width$}"))
+                        .collect::<Vec<_>>()
+                        .join("  ");
+                    println!("{}", line.trim_end());
+                }
+            }
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&outdated_items)?);
+            }
+        }
+        Ok(())
+    }
+    async fn upgrade(
+        &self,
+        isobin_manifest_path: Option<PathBuf>,
+        quiet: bool,
+        latest: bool,
+        dry_run: bool,
+        targets: Vec<SpecifiedTarget>,
+    ) -> Result<()> {
+        let upgrade_service_option_builder = UpgradeServiceOptionBuilder::default()
+            .quiet(quiet)
+            .latest(latest)
+            .dry_run(dry_run)
+            .targets(targets);
+        let upgrade_service_option_builder =
+            if let Some(isobin_manifest_path) = isobin_manifest_path {
+                upgrade_service_option_builder.isobin_manifest_path(isobin_manifest_path)
+            } else {
+                upgrade_service_option_builder
+            };
+        let upgrade_items = upgrade(upgrade_service_option_builder.build()).await?;
+        for upgrade_item in upgrade_items.iter() {
+            println!(
+                "{}/{} {} -> {}",
+                upgrade_item.provider(),
+                upgrade_item.name(),
+                upgrade_item.from(),
+                upgrade_item.to()
+            );
+        }
+        Ok(())
+    }
     async fn clean(&self, isobin_manifest_path: Option<PathBuf>, quiet: bool) -> Result<()> {
         let clean_service_option_builder = CleanServiceOptionBuilder::default().quiet(quiet);
         let clean_service_option_builder = if let Some(isobin_manifest_path) = isobin_manifest_path
@@ -134,11 +410,15 @@ impl Application {
 #[derive(Parser)]
 #[command(author, version, about)]
 pub struct Arguments {
+    /// Uses the per-user manifest in the config dir instead of the project one
+    #[arg(long, global = true, default_value_t = false)]
+    global: bool,
     #[command(subcommand)]
     subcommand: SubCommands,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum SubCommands {
     Path {
         #[command(flatten)]
@@ -149,6 +429,70 @@ pub enum SubCommands {
         base_options: BaseOptions,
         #[arg(short, long, default_value_t = false)]
         force: bool,
+        /// Require isobin.lock to be up to date and install exactly the locked versions
+        #[arg(long, default_value_t = false)]
+        locked: bool,
+        #[command(flatten)]
+        group_options: GroupOptions,
+    },
+    Install {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+        /// Require isobin.lock to be up to date and install exactly the locked versions
+        #[arg(long, default_value_t = false)]
+        locked: bool,
+        #[command(flatten)]
+        group_options: GroupOptions,
+        /// Targets to install, optionally qualified by provider as `provider/name`
+        #[arg(required = true)]
+        targets: Vec<SpecifiedTarget>,
+    },
+    Add {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        /// Overwrite the dependency if it already exists in the manifest
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+        /// Target to add as `[provider/]name[@version]`
+        target: String,
+        #[command(flatten)]
+        cargo_options: CargoAddOptions,
+    },
+    Remove {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        /// Targets to remove, optionally qualified by provider as `provider/name`
+        #[arg(required = true)]
+        targets: Vec<SpecifiedTarget>,
+    },
+    /// Lists the tools in the manifest and the installed tools
+    List {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Shows the installed, newest compatible and latest versions of the registry dependencies
+    Outdated {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Bumps the version requirements in the manifest and reinstalls the changed targets
+    Upgrade {
+        #[command(flatten)]
+        base_options: BaseOptions,
+        /// Upgrade to the latest release even if it is not compatible with the current requirement
+        #[arg(long, default_value_t = false)]
+        latest: bool,
+        /// Print the planned changes without editing the manifest
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Targets to upgrade, optionally qualified by provider as `provider/name`. All targets by default
+        targets: Vec<SpecifiedTarget>,
     },
     Clean {
         #[command(flatten)]
@@ -166,6 +510,41 @@ pub enum SubCommands {
     },
 }
 
+impl SubCommands {
+    /// Points the manifest path at the global manifest.
+    async fn into_global(mut self) -> Result<Self> {
+        if self.base_options_mut().manifest_path.is_some() {
+            anyhow::bail!("--global can not be used with --manifest-path");
+        }
+        // Only the commands which write tools into the manifest create it on the first use.
+        let global_isobin_manifest_path = match self {
+            Self::Init { .. } => global_isobin_manifest_path(),
+            Self::Add { .. } | Self::Install { .. } | Self::Sync { .. } => {
+                prepare_global_isobin_manifest_path().await?
+            }
+            _ => existing_global_isobin_manifest_path()?,
+        };
+        self.base_options_mut().manifest_path = Some(global_isobin_manifest_path);
+        Ok(self)
+    }
+
+    fn base_options_mut(&mut self) -> &mut BaseOptions {
+        match self {
+            Self::Path { base_options }
+            | Self::Sync { base_options, .. }
+            | Self::Install { base_options, .. }
+            | Self::Add { base_options, .. }
+            | Self::Remove { base_options, .. }
+            | Self::List { base_options, .. }
+            | Self::Outdated { base_options, .. }
+            | Self::Upgrade { base_options, .. }
+            | Self::Clean { base_options }
+            | Self::Run { base_options, .. }
+            | Self::Init { base_options } => base_options,
+        }
+    }
+}
+
 #[derive(Args)]
 pub struct BaseOptions {
     /// Sets a custom manifest file
@@ -174,3 +553,46 @@ pub struct BaseOptions {
     #[arg(long, short, default_value_t = false)]
     quiet: bool,
 }
+
+#[derive(Args)]
+pub struct GroupOptions {
+    /// Installs only the dependencies whose `groups` include one of the groups
+    #[arg(long = "group", value_name = "GROUP", value_delimiter = ',')]
+    groups: Vec<String>,
+    /// Leaves out the dependencies in any of the groups
+    #[arg(long = "without-group", value_name = "GROUP", value_delimiter = ',')]
+    without_groups: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct CargoAddOptions {
+    #[arg(long = "bin", value_name = "NAME")]
+    bins: Vec<String>,
+    #[arg(long)]
+    registry: Option<String>,
+    #[arg(long)]
+    index: Option<String>,
+    /// Path to the local crate, relative to the manifest
+    #[arg(long, value_hint = clap::ValueHint::DirPath)]
+    path: Option<PathBuf>,
+    #[arg(long)]
+    git: Option<String>,
+    #[arg(long)]
+    branch: Option<String>,
+    #[arg(long)]
+    tag: Option<String>,
+    #[arg(long)]
+    rev: Option<String>,
+    #[arg(long, value_delimiter = ',')]
+    features: Vec<String>,
+    #[arg(long, default_value_t = false)]
+    no_default_features: bool,
+    #[arg(long, default_value_t = false)]
+    all_features: bool,
+}
@@ -1,7 +1,9 @@
 mod multi_progress;
 mod print_error;
+mod print_warning;
 mod progress;
 
 pub use multi_progress::*;
 pub use print_error::*;
+pub use print_warning::*;
 pub use progress::*;
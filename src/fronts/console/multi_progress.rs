@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use indicatif::{MultiProgress as IndicatifMultiProgress, ProgressBar as IndicatifProgressBar};
 
 use crate::providers::TargetDependency;
@@ -10,11 +12,13 @@ pub struct MultiProgress {
 }
 
 impl crate::fronts::MultiProgress for MultiProgress {
-    type Progress = Progress;
-    fn make_progress(&self, install_target: &impl TargetDependency) -> Self::Progress {
-        Progress::new(
+    fn make_progress(
+        &self,
+        install_target: &dyn TargetDependency,
+    ) -> Arc<dyn crate::fronts::Progress> {
+        Arc::new(Progress::new(
             self.multi_progress.add(IndicatifProgressBar::hidden()),
             install_target,
-        )
+        ))
     }
 }
@@ -1,6 +1,6 @@
 use colored::Colorize;
 
-use crate::{install::InstallServiceError, Error, IsobinManifestError, ProviderKind};
+use crate::{install::InstallServiceError, Error, IsobinManifestError};
 pub fn print_error(err: &Error) {
     match err.downcast_ref::<InstallServiceError>() {
         Some(InstallServiceError::MultiInstall(errs)) => {
@@ -19,7 +19,7 @@ pub fn print_error(err: &Error) {
                 provider.to_string().red(),
                 name.red()
             );
-            print_provider_error(provider, error_message);
+            eprintln!("{error_message}");
         }
         _ => match err.downcast_ref::<IsobinManifestError>() {
             Some(IsobinManifestError::MultiValidate(errs)) => {
@@ -45,9 +45,3 @@ pub fn print_error(err: &Error) {
         },
     }
 }
-
-fn print_provider_error(provider: &ProviderKind, message: &str) {
-    match provider {
-        ProviderKind::Cargo => eprintln!("{message}"),
-    }
-}
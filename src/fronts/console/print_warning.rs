@@ -0,0 +1,12 @@
+use colored::Colorize;
+
+use crate::IsobinManifestWarning;
+
+pub fn print_warning(warning: &IsobinManifestWarning) {
+    eprintln!(
+        "Warning in {}/{}.",
+        warning.provider().to_string().yellow(),
+        warning.name().yellow()
+    );
+    eprintln!("{}", warning.message().yellow());
+}
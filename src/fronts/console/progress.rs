@@ -17,7 +17,7 @@ pub struct Progress {
 }
 
 impl Progress {
-    pub fn new(progress_bar: ProgressBar, install_target: &impl TargetDependency) -> Self {
+    pub fn new(progress_bar: ProgressBar, install_target: &dyn TargetDependency) -> Self {
         Self {
             progress_bar,
             provider_kind: install_target.provider_kind(),
@@ -1,15 +1,16 @@
 pub mod console;
 pub mod quiet;
 
+use std::sync::Arc;
+
 use crate::providers::TargetDependency;
 use crate::Result;
-pub use console::print_error;
+pub use console::{print_error, print_warning};
 
-pub trait MultiProgress: Clone + 'static + Send + Sync + Default {
-    type Progress: Progress;
-    fn make_progress(&self, install_target: &impl TargetDependency) -> Self::Progress;
+pub trait MultiProgress: 'static + Send + Sync {
+    fn make_progress(&self, install_target: &dyn TargetDependency) -> Arc<dyn Progress>;
 }
-pub trait Progress: Clone + 'static + Send + Sync {
+pub trait Progress: 'static + Send + Sync {
     fn prepare_install(&self) -> Result<()>;
     fn already_installed(&self) -> Result<()>;
     fn start_uninstall(&self) -> Result<()>;
@@ -38,8 +38,10 @@ impl crate::fronts::Progress for Progress {
 pub struct MultiProgress;
 
 impl crate::fronts::MultiProgress for MultiProgress {
-    type Progress = Progress;
-    fn make_progress(&self, _: &impl crate::providers::TargetDependency) -> Self::Progress {
-        Self::Progress {}
+    fn make_progress(
+        &self,
+        _: &dyn crate::providers::TargetDependency,
+    ) -> std::sync::Arc<dyn crate::fronts::Progress> {
+        std::sync::Arc::new(Progress)
     }
 }
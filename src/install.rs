@@ -6,13 +6,12 @@ use tokio::sync::Mutex;
 use crate::bin_map::BinMap;
 use crate::fronts::MultiProgress;
 use crate::fronts::Progress;
+use crate::lock::IsobinLock;
 use crate::paths::isobin_manifest::isobin_manifest_dir;
 use crate::paths::isobin_manifest::isobin_manifest_path_canonicalize;
 use crate::paths::workspace::Workspace;
 use crate::paths::workspace::WorkspaceProvider;
-use crate::providers::cargo::CargoInstallerFactory;
-use crate::providers::cargo::CargoManifest;
-use crate::providers::cargo::CargoTargetDependency;
+use crate::providers::Provider;
 use crate::providers::ProviderKind;
 use crate::providers::TargetBinDependency;
 use crate::providers::TargetDependency;
@@ -34,7 +33,7 @@ pub enum InstallMode {
     },
 }
 
-#[derive(Default)]
+#[derive(Default, new)]
 pub struct InstallService {
     workspace_provider: WorkspaceProvider,
 }
@@ -44,6 +43,11 @@ impl InstallService {
         let install_service_option = install_service_option.fix().await?;
         let isobin_manifest =
             IsobinManifest::load_from_file(install_service_option.isobin_manifest_path()).await?;
+        if !install_service_option.quiet {
+            for warning in isobin_manifest.warnings().iter() {
+                fronts::print_warning(warning);
+            }
+        }
         let isobin_manifest_dir =
             isobin_manifest_dir(install_service_option.isobin_manifest_path())?;
         let workspace = self
@@ -62,14 +66,32 @@ impl InstallService {
         let isobin_manifest_cache = if install_service_option.force {
             IsobinManifest::default()
         } else {
-            IsobinManifestCache::lenient_load_cache_from_dir(tmp_workspace.base_dir()).await
+            IsobinManifestCache::lenient_load_cache_from_dir(tmp_workspace.base_dir())
+                .await
+                .fix(isobin_manifest_dir)
         };
+        let isobin_lock = IsobinLock::load_from_dir(isobin_manifest_dir).await?;
+        let unexpanded_isobin_manifest = IsobinManifest::load_unexpanded_from_file(
+            install_service_option.isobin_manifest_path(),
+        )
+        .await?;
+        if install_service_option.locked {
+            isobin_lock.check_locked(
+                &isobin_manifest,
+                &unexpanded_isobin_manifest,
+                isobin_manifest_dir,
+            )?;
+        }
         let specified_isobin_manifest = match install_service_option.mode() {
-            InstallMode::All => isobin_manifest,
+            InstallMode::All => isobin_manifest.clone(),
             InstallMode::SpecificInstallTargetsOnly {
                 specified_install_targets: specific_install_targets,
             } => isobin_manifest.filter_target(specific_install_targets)?,
-        };
+        }
+        .filter_groups(
+            install_service_option.groups(),
+            install_service_option.without_groups(),
+        )?;
         let install_target_isobin_manifest = IsobinManifest::get_need_install_dependency_manifest(
             &specified_isobin_manifest,
             &isobin_manifest_cache,
@@ -83,12 +105,24 @@ impl InstallService {
             &workspace,
             &tmp_workspace,
             &save_isobin_manifest,
-            &specified_isobin_manifest,
-            &install_target_isobin_manifest,
+            &isobin_lock.pin(&specified_isobin_manifest, &unexpanded_isobin_manifest),
+            &isobin_lock.pin(&install_target_isobin_manifest, &unexpanded_isobin_manifest),
             &IsobinManifest::default(),
             install_service_option.quiet,
         )
-        .await
+        .await?;
+        if !install_service_option.locked {
+            isobin_lock
+                .update_and_save(
+                    &isobin_manifest,
+                    &unexpanded_isobin_manifest,
+                    &specified_isobin_manifest,
+                    &workspace,
+                    isobin_manifest_dir,
+                )
+                .await?;
+        }
+        Ok(())
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -108,54 +142,30 @@ impl InstallService {
         {
             Err(anyhow!("The install target does not exists"))?;
         }
-        if quiet {
-            self.run_install_::<fronts::quiet::MultiProgress>(
-                workspace,
-                tmp_workspace,
-                save_isobin_manifest,
-                specified_isobin_manifest,
-                install_target_isobin_manifest,
-                uninstall_target_isobin_manifest,
-            )
-            .await
+        let multi_progress: Arc<dyn MultiProgress> = if quiet {
+            Arc::new(fronts::quiet::MultiProgress)
         } else {
-            self.run_install_::<fronts::console::MultiProgress>(
-                workspace,
-                tmp_workspace,
-                save_isobin_manifest,
-                specified_isobin_manifest,
-                install_target_isobin_manifest,
-                uninstall_target_isobin_manifest,
-            )
-            .await
+            Arc::new(fronts::console::MultiProgress::default())
+        };
+        let install_runner_provider = InstallRunnerProvider::new(multi_progress, quiet);
+        let mut runners = vec![];
+        for provider in specified_isobin_manifest.providers().iter() {
+            runners.push(
+                provider
+                    .make_runner(
+                        &install_runner_provider,
+                        tmp_workspace,
+                        specified_isobin_manifest.section_of(provider).as_ref(),
+                        install_target_isobin_manifest.section_of(provider).as_ref(),
+                        uninstall_target_isobin_manifest
+                            .section_of(provider)
+                            .as_ref(),
+                    )
+                    .await?,
+            );
         }
-    }
-    async fn run_install_<MP: MultiProgress>(
-        &self,
-        workspace: &Workspace,
-        tmp_workspace: &Workspace,
-        save_isobin_manifest: &IsobinManifest,
-        specified_isobin_manifest: &IsobinManifest,
-        install_target_isobin_manifest: &IsobinManifest,
-        uninstall_target_isobin_manifest: &IsobinManifest,
-    ) -> Result<()> {
-        let cargo_installer_factory = CargoInstallerFactory::new(tmp_workspace.clone());
-        let install_runner_provider = InstallRunnerProvider::<MP>::default();
-        let cargo_runner = install_runner_provider
-            .make_cargo_runner(
-                &cargo_installer_factory,
-                specified_isobin_manifest.cargo(),
-                install_target_isobin_manifest.cargo(),
-                uninstall_target_isobin_manifest.cargo(),
-            )
-            .await?;
-        self.run_each_install(
-            workspace,
-            tmp_workspace,
-            save_isobin_manifest,
-            vec![cargo_runner],
-        )
-        .await
+        self.run_each_install(workspace, tmp_workspace, save_isobin_manifest, runners)
+            .await
     }
 
     async fn run_each_install(
@@ -257,55 +267,54 @@ impl InstallService {
 }
 
 #[derive(Getters, new, Clone)]
-struct InstallTargetContext<IF: TargetDependency + Clone, P: Progress> {
+struct InstallTargetContext<IF: TargetDependency + Clone> {
     target: IF,
-    progress: P,
+    progress: Arc<dyn Progress>,
 }
 
-#[derive(Default)]
-pub struct InstallRunnerProvider<MP: MultiProgress> {
-    multi_progress: MP,
+#[derive(new)]
+pub struct InstallRunnerProvider {
+    multi_progress: Arc<dyn MultiProgress>,
+    quiet: bool,
 }
 
-impl<MP: MultiProgress> InstallRunnerProvider<MP> {
-    pub async fn make_cargo_runner(
+impl InstallRunnerProvider {
+    pub async fn make_provider_runner<P: Provider>(
         &self,
-        cargo_installer: &CargoInstallerFactory,
-        specified_cargo_manifest: &CargoManifest,
-        install_target_cargo_manifest: &CargoManifest,
-        uninstall_target_cargo_manifest: &CargoManifest,
+        provider: &P,
+        workspace: &Workspace,
+        specified_manifest: &P::Manifest,
+        install_target_manifest: &P::Manifest,
+        uninstall_target_manifest: &P::Manifest,
     ) -> Result<Arc<Mutex<dyn InstallRunner>>> {
-        let mut install_targets = specified_cargo_manifest
+        let mut install_targets = specified_manifest
             .dependencies()
             .iter()
             .map(|(name, install_dependency)| {
-                let mode = if install_target_cargo_manifest
-                    .dependencies()
-                    .get(name)
-                    .is_some()
-                {
+                let mode = if install_target_manifest.dependencies().contains_key(name) {
                     TargetMode::Install
                 } else {
                     TargetMode::AlreadyInstalled
                 };
-                CargoTargetDependency::new(name.into(), install_dependency.clone(), mode)
+                provider.make_target(specified_manifest, name, install_dependency, mode)
             })
             .collect::<Vec<_>>();
-        install_targets.extend_from_slice(
-            &uninstall_target_cargo_manifest
-                .dependencies()
-                .iter()
-                .map(|(name, uninstall_dependency)| {
-                    CargoTargetDependency::new(
-                        name.into(),
-                        uninstall_dependency.clone(),
-                        TargetMode::Uninstall,
-                    )
-                })
-                .collect::<Vec<_>>(),
-        );
+        install_targets.extend(uninstall_target_manifest.dependencies().iter().map(
+            |(name, uninstall_dependency)| {
+                provider.make_target(
+                    uninstall_target_manifest,
+                    name,
+                    uninstall_dependency,
+                    TargetMode::Uninstall,
+                )
+            },
+        ));
 
-        self.make_runner(cargo_installer, install_targets).await
+        self.make_runner(
+            &provider.make_installer_factory(workspace.clone(), self.quiet),
+            install_targets,
+        )
+        .await
     }
 
     async fn make_runner<IF: providers::InstallerFactory>(
@@ -334,7 +343,6 @@ impl<MP: MultiProgress> InstallRunnerProvider<MP> {
 
 #[async_trait]
 pub trait InstallRunner: 'static + Sync + Send {
-    fn provider_kind(&self) -> providers::ProviderKind;
     async fn run_installs(&self) -> Result<()>;
     async fn bin_paths(&self) -> Result<Vec<TargetBinDependency>>;
     async fn install_bin_path(&self) -> Result<()>;
@@ -343,22 +351,20 @@ pub trait InstallRunner: 'static + Sync + Send {
 
 #[derive(new)]
 struct InstallRunnerImpl<
-    IT: providers::TargetDependency,
+    IT: providers::TargetDependency + Clone,
     CI: providers::CoreInstaller<InstallTarget = IT>,
     BI: providers::BinPathInstaller<InstallTarget = IT>,
-    P: Progress,
 > {
     core_installer: CI,
     bin_path_installer: BI,
-    contexts: Vec<InstallTargetContext<IT, P>>,
+    contexts: Vec<InstallTargetContext<IT>>,
 }
 
 impl<
-        IT: providers::TargetDependency,
+        IT: providers::TargetDependency + Clone,
         CI: providers::CoreInstaller<InstallTarget = IT>,
         BI: providers::BinPathInstaller<InstallTarget = IT>,
-        P: Progress,
-    > InstallRunnerImpl<IT, CI, BI, P>
+    > InstallRunnerImpl<IT, CI, BI>
 {
     async fn run_sequential_installs(&self) -> Result<()> {
         for context in self.contexts.iter() {
@@ -386,7 +392,7 @@ impl<
     async fn install(
         core_installer: CI,
         bin_path_installer: BI,
-        install_context: InstallTargetContext<IT, P>,
+        install_context: InstallTargetContext<IT>,
     ) -> Result<()> {
         let progress = install_context.progress();
         let target = install_context.target();
@@ -428,11 +434,10 @@ impl<
 
 #[async_trait]
 impl<
-        IT: providers::TargetDependency,
+        IT: providers::TargetDependency + Clone,
         CI: providers::CoreInstaller<InstallTarget = IT>,
         BI: providers::BinPathInstaller<InstallTarget = IT>,
-        P: Progress,
-    > InstallRunner for InstallRunnerImpl<IT, CI, BI, P>
+    > InstallRunner for InstallRunnerImpl<IT, CI, BI>
 {
     fn done_contexts(&self) -> Result<()> {
         for context in self.contexts.iter() {
@@ -448,10 +453,6 @@ impl<
         }
         Ok(())
     }
-    fn provider_kind(&self) -> providers::ProviderKind {
-        self.core_installer.provider_kind()
-    }
-
     async fn run_installs(&self) -> Result<()> {
         match self.core_installer.multi_install_mode() {
             providers::MultiInstallMode::Parallel => self.run_parallel_installs().await,
@@ -489,7 +490,10 @@ impl<
 pub struct InstallServiceOptionBase<P> {
     quiet: bool,
     force: bool,
+    locked: bool,
     mode: InstallMode,
+    groups: Vec<String>,
+    without_groups: Vec<String>,
     isobin_manifest_path: P,
 }
 
@@ -503,6 +507,9 @@ impl InstallServiceOption {
         Ok(FixedInstallServiceOption {
             quiet: self.quiet,
             force: self.force,
+            locked: self.locked,
+            groups: self.groups,
+            without_groups: self.without_groups,
             mode: self.mode,
             isobin_manifest_path,
         })
@@ -513,6 +520,9 @@ impl InstallServiceOption {
 pub struct InstallServiceOptionBuilder {
     quiet: bool,
     force: bool,
+    locked: bool,
+    groups: Vec<String>,
+    without_groups: Vec<String>,
     mode: Option<InstallMode>,
     isobin_manifest_path: Option<PathBuf>,
 }
@@ -531,6 +541,18 @@ impl InstallServiceOptionBuilder {
         self.force = force;
         self
     }
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+    pub fn groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = groups;
+        self
+    }
+    pub fn without_groups(mut self, without_groups: Vec<String>) -> Self {
+        self.without_groups = without_groups;
+        self
+    }
     pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
         self.isobin_manifest_path = Some(isobin_manifest_path);
         self
@@ -540,6 +562,9 @@ impl InstallServiceOptionBuilder {
         InstallServiceOption {
             quiet: self.quiet,
             force: self.force,
+            locked: self.locked,
+            groups: self.groups,
+            without_groups: self.without_groups,
             mode: self.mode.unwrap_or(InstallMode::All),
             isobin_manifest_path: self.isobin_manifest_path,
         }
@@ -4,41 +4,72 @@ extern crate derive_new;
 #[macro_use]
 extern crate derive_getters;
 
+mod add;
 mod bin_map;
 mod clearn;
 mod errors;
 mod fronts;
 mod init;
 mod install;
+mod list;
+mod lock;
 mod macros;
 mod manifest;
+mod manifest_editor;
+mod outdated;
 mod path;
 mod paths;
 mod providers;
+mod remove;
 mod result;
 mod run;
 mod specified_target;
 mod sync;
+mod upgrade;
 mod utils;
+use add::AddService;
+pub use add::{AddServiceError, AddServiceOption, AddServiceOptionBuilder};
 pub use errors::*;
 pub use fronts::print_error;
 use install::InstallService;
 pub use install::{InstallMode, InstallServiceOption, InstallServiceOptionBuilder};
+use list::ListService;
+pub use list::{ListItem, ListItemStatus, ListServiceOption, ListServiceOptionBuilder};
+pub use lock::IsobinLockError;
+use outdated::OutdatedService;
+pub use outdated::{OutdatedItem, OutdatedServiceOption, OutdatedServiceOptionBuilder};
 use path::PathService;
 pub use path::{PathServiceOption, PathServiceOptionBuilder};
+use remove::RemoveService;
+pub use remove::{RemoveServiceOption, RemoveServiceOptionBuilder};
 pub use result::*;
 use run::*;
 pub use run::{RunServiceError, RunServiceOption, RunServiceOptionBuilder};
 use sync::SyncService;
 pub use sync::{SyncServiceOption, SyncServiceOptionBuilder};
+use upgrade::UpgradeService;
+pub use upgrade::{UpgradeItem, UpgradeServiceOption, UpgradeServiceOptionBuilder};
 
 use async_trait::async_trait;
+pub use bin_map::BinDependency;
 use clearn::*;
 pub use clearn::{CleanServiceOption, CleanServiceOptionBuilder};
 use init::*;
 pub use init::{InitServiceOption, InitServiceOptionBuilder};
 use manifest::*;
-pub use providers::ProviderKind;
+pub use manifest::{
+    IsobinManifestError, IsobinManifestWarning, Manifest, ManifestDependency, ProviderManifest,
+};
+pub use paths::isobin_manifest::{
+    existing_global_isobin_manifest_path, global_isobin_manifest_path,
+    prepare_global_isobin_manifest_path,
+};
+pub use paths::workspace::Workspace;
+pub use providers::{
+    register_provider, BinPathInstaller, CoreInstaller, InstallerFactory, MultiInstallMode,
+    Provider, ProviderDependency, ProviderKind, ProviderRegistryError, ProviderTarget,
+    TargetBinDependency, TargetDependency, TargetMode,
+};
 #[cfg(test)]
 use rstest::*;
 pub use specified_target::*;
@@ -57,6 +88,16 @@ pub async fn path(path_service_option: PathServiceOption) -> Result<std::path::P
     path_service.path(path_service_option).await
 }
 
+pub async fn list(list_service_option: ListServiceOption) -> Result<Vec<ListItem>> {
+    let list_service = ListService::default();
+    list_service.list(list_service_option).await
+}
+
+pub async fn outdated(outdated_service_option: OutdatedServiceOption) -> Result<Vec<OutdatedItem>> {
+    let outdated_service = OutdatedService::default();
+    outdated_service.outdated(outdated_service_option).await
+}
+
 pub async fn sync(sync_service_option: SyncServiceOption) -> Result<()> {
     let sync_service = SyncService::default();
     let quiet = *sync_service_option.quiet();
@@ -65,6 +106,20 @@ pub async fn sync(sync_service_option: SyncServiceOption) -> Result<()> {
     flex_eprintln!(quiet, "Completed sync.");
     Ok(())
 }
+pub async fn remove(remove_service_option: RemoveServiceOption) -> Result<()> {
+    let remove_service = RemoveService::default();
+    let quiet = *remove_service_option.quiet();
+    flex_eprintln!(quiet, "Start remove.");
+    remove_service.remove(remove_service_option).await?;
+    flex_eprintln!(quiet, "Completed remove.");
+    Ok(())
+}
+
+pub async fn upgrade(upgrade_service_option: UpgradeServiceOption) -> Result<Vec<UpgradeItem>> {
+    let upgrade_service = UpgradeService::default();
+    upgrade_service.upgrade(upgrade_service_option).await
+}
+
 pub async fn clear(clean_service_option: CleanServiceOption) -> Result<()> {
     let clean_service = CleanService::default();
     let quiet = *clean_service_option.quiet();
@@ -83,3 +138,8 @@ pub async fn init(init_service_option: InitServiceOption) -> Result<()> {
     let init_service = InitService::default();
     init_service.init(init_service_option).await
 }
+
+pub async fn add(add_service_option: AddServiceOption) -> Result<()> {
+    let add_service = AddService::default();
+    add_service.add(add_service_option).await
+}
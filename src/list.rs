@@ -0,0 +1,291 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde_derive::Serialize;
+use strum_macros::Display;
+
+use crate::{
+    bin_map::BinMap,
+    manifest::{IsobinManifest, IsobinManifestCache, Manifest},
+    paths::{
+        isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+        workspace::{Workspace, WorkspaceProvider},
+    },
+    providers::{Provider, ProviderDependency, ProviderKind, TargetDependency},
+    Result,
+};
+
+#[derive(Default)]
+pub struct ListService {
+    workspace_provider: WorkspaceProvider,
+}
+
+impl ListService {
+    pub async fn list(&self, list_service_option: ListServiceOption) -> Result<Vec<ListItem>> {
+        let list_service_option = list_service_option.fix().await?;
+        let isobin_manifest =
+            IsobinManifest::load_from_file(list_service_option.isobin_manifest_path()).await?;
+        let isobin_manifest_dir = isobin_manifest_dir(list_service_option.isobin_manifest_path())?;
+        let workspace = self
+            .workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(isobin_manifest_dir)
+            .await?;
+        let isobin_manifest_cache =
+            IsobinManifestCache::lenient_load_cache_from_dir(workspace.base_dir())
+                .await
+                .fix(isobin_manifest_dir);
+        let bin_map = BinMap::lenient_load_from_dir(workspace.base_dir()).await?;
+
+        let mut bins = HashMap::<(String, String), Vec<String>>::new();
+        for (bin_file_name, bin_dependency) in bin_map.bin_dependencies().iter() {
+            bins.entry((
+                bin_dependency.provider_kind().to_string(),
+                bin_dependency.name().to_string(),
+            ))
+            .or_default()
+            .push(bin_file_name.to_string());
+        }
+
+        let mut list_items = vec![];
+        for provider in isobin_manifest.providers().iter() {
+            list_items.extend(
+                provider
+                    .make_list_items(
+                        isobin_manifest.section_of(provider).as_ref(),
+                        isobin_manifest_cache.section_of(provider).as_ref(),
+                        &workspace,
+                        &mut bins,
+                    )
+                    .await?,
+            );
+        }
+        list_items.sort_by(|a, b| {
+            (a.provider.to_string(), &a.name).cmp(&(b.provider.to_string(), &b.name))
+        });
+        Ok(list_items)
+    }
+
+    pub async fn make_provider_list_items<P: Provider>(
+        provider: &P,
+        manifest: &P::Manifest,
+        cache_manifest: &P::Manifest,
+        workspace: &Workspace,
+        bins: &mut HashMap<(String, String), Vec<String>>,
+    ) -> Result<Vec<ListItem>> {
+        let mut items = vec![];
+        for (name, dependency) in manifest.dependencies().iter() {
+            // An installed dependency is summarized as it was installed, so the drift from the
+            // manifest is shown instead of hidden.
+            let (target_manifest, dependency, status) =
+                match cache_manifest.dependencies().get(name) {
+                    Some(cache_dependency) => {
+                        let status = if provider
+                            .ditect_difference(manifest, cache_manifest, name, workspace)
+                            .await?
+                        {
+                            ListItemStatus::Changed
+                        } else {
+                            ListItemStatus::Installed
+                        };
+                        (cache_manifest, cache_dependency, status)
+                    }
+                    None => (manifest, dependency, ListItemStatus::NotInstalled),
+                };
+            items.push(
+                Self::make_list_item(
+                    provider,
+                    target_manifest,
+                    name,
+                    dependency,
+                    status,
+                    workspace,
+                    bins,
+                )
+                .await?,
+            );
+        }
+        for (name, dependency) in cache_manifest.dependencies().iter() {
+            if !manifest.dependencies().contains_key(name) {
+                items.push(
+                    Self::make_list_item(
+                        provider,
+                        cache_manifest,
+                        name,
+                        dependency,
+                        ListItemStatus::NotInManifest,
+                        workspace,
+                        bins,
+                    )
+                    .await?,
+                );
+            }
+        }
+        Ok(items)
+    }
+
+    async fn make_list_item<P: Provider>(
+        provider: &P,
+        manifest: &P::Manifest,
+        name: &str,
+        dependency: &ProviderDependency<P>,
+        status: ListItemStatus,
+        workspace: &Workspace,
+        bins: &mut HashMap<(String, String), Vec<String>>,
+    ) -> Result<ListItem> {
+        let target = provider
+            .make_list_target(manifest, name, dependency, workspace)
+            .await?;
+        let provider_kind = target.provider_kind();
+        let mut item_bins = bins
+            .remove(&(provider_kind.to_string(), name.to_string()))
+            .unwrap_or_default();
+        item_bins.sort();
+        Ok(ListItem::new(
+            provider_kind,
+            name.to_string(),
+            target.summary(),
+            item_bins,
+            status,
+        ))
+    }
+}
+
+#[derive(Serialize, Getters, new, Debug, PartialEq)]
+pub struct ListItem {
+    provider: ProviderKind,
+    name: String,
+    summary: String,
+    bins: Vec<String>,
+    status: ListItemStatus,
+}
+
+#[derive(Serialize, Display, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ListItemStatus {
+    #[strum(serialize = "installed")]
+    Installed,
+    /// Installed, but the manifest entry changed since.
+    #[strum(serialize = "changed")]
+    Changed,
+    #[strum(serialize = "not installed")]
+    NotInstalled,
+    #[strum(serialize = "not in manifest")]
+    NotInManifest,
+}
+
+#[derive(Getters)]
+pub struct ListServiceOptionBase<P> {
+    isobin_manifest_path: P,
+}
+
+pub type ListServiceOption = ListServiceOptionBase<Option<PathBuf>>;
+type FixedListServiceOption = ListServiceOptionBase<PathBuf>;
+
+impl ListServiceOption {
+    async fn fix(self) -> Result<FixedListServiceOption> {
+        let isobin_manifest_path =
+            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+        Ok(FixedListServiceOption {
+            isobin_manifest_path,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct ListServiceOptionBuilder {
+    isobin_manifest_path: Option<PathBuf>,
+}
+
+impl ListServiceOptionBuilder {
+    pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
+        self.isobin_manifest_path = Some(isobin_manifest_path);
+        self
+    }
+    pub fn build(self) -> ListServiceOption {
+        ListServiceOption {
+            isobin_manifest_path: self.isobin_manifest_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::cargo::{CargoInstallDependency, CargoManifest, CargoProvider};
+    use rstest::*;
+    use std::str::FromStr;
+
+    fn cargo_dependency(version: &str) -> CargoInstallDependency {
+        CargoInstallDependency::Simple(cargo::util::PartialVersion::from_str(version).unwrap())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn make_provider_list_items_works() {
+        let dependencies = [
+            ("cargo-make".to_string(), cargo_dependency("0.37.4")),
+            ("ripgrep".to_string(), cargo_dependency("13")),
+            ("just".to_string(), cargo_dependency("1.16")),
+        ]
+        .into_iter()
+        .collect();
+        let cache_dependencies = [
+            ("cargo-make".to_string(), cargo_dependency("0.37.4")),
+            ("just".to_string(), cargo_dependency("1.15")),
+            ("comrak".to_string(), cargo_dependency("0.19")),
+        ]
+        .into_iter()
+        .collect();
+        let mut bins = [(
+            ("cargo".to_string(), "cargo-make".to_string()),
+            vec!["makers".to_string(), "cargo-make".to_string()],
+        )]
+        .into_iter()
+        .collect();
+        let workspace = Workspace::new(
+            "list".into(),
+            std::env::temp_dir().join("isobin_list_not_found"),
+            std::env::temp_dir().join("isobin_list_not_found_cache"),
+        );
+        let mut actual = ListService::make_provider_list_items(
+            &CargoProvider,
+            &CargoManifest::new(None, None, dependencies),
+            &CargoManifest::new(None, None, cache_dependencies),
+            &workspace,
+            &mut bins,
+        )
+        .await
+        .unwrap();
+        actual.sort_by(|a, b| a.name.cmp(&b.name));
+        let expected = vec![
+            ListItem::new(
+                ProviderKind::Cargo,
+                "cargo-make".into(),
+                "0.37.4".into(),
+                vec!["cargo-make".into(), "makers".into()],
+                ListItemStatus::Installed,
+            ),
+            ListItem::new(
+                ProviderKind::Cargo,
+                "comrak".into(),
+                "0.19".into(),
+                vec![],
+                ListItemStatus::NotInManifest,
+            ),
+            ListItem::new(
+                ProviderKind::Cargo,
+                "just".into(),
+                "1.15".into(),
+                vec![],
+                ListItemStatus::Changed,
+            ),
+            ListItem::new(
+                ProviderKind::Cargo,
+                "ripgrep".into(),
+                "13".into(),
+                vec![],
+                ListItemStatus::NotInstalled,
+            ),
+        ];
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::{Path, PathBuf};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::IsobinManifest,
+    paths::workspace::Workspace,
+    providers::cargo::CargoLock,
+    utils::{io_ext, serde_ext::Toml},
+    Result,
+};
+
+/// The exact versions and sources the dependencies of an isobin manifest were resolved to.
+/// It is saved next to the isobin manifest so that every checkout installs the same binaries.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Getters)]
+pub struct IsobinLock {
+    version: u32,
+    #[serde(default)]
+    cargo: CargoLock,
+}
+
+impl Default for IsobinLock {
+    fn default() -> Self {
+        Self {
+            version: Self::LOCK_VERSION,
+            cargo: CargoLock::default(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum IsobinLockError {
+    #[error(
+        "The lock file needs to be updated but --locked was passed\npath:{path}\ntargets:{names:?}"
+    )]
+    Outdated { path: String, names: Vec<String> },
+}
+
+impl IsobinLock {
+    const LOCK_FILE_NAME: &'static str = "isobin.lock";
+    const LOCK_VERSION: u32 = 1;
+
+    fn make_lock_path(isobin_manifest_dir: impl AsRef<Path>) -> PathBuf {
+        isobin_manifest_dir.as_ref().join(Self::LOCK_FILE_NAME)
+    }
+
+    pub async fn load_from_dir(isobin_manifest_dir: impl AsRef<Path>) -> Result<Self> {
+        Toml::parse_or_default_if_not_found(Self::make_lock_path(isobin_manifest_dir)).await
+    }
+
+    pub async fn save_to_dir(&self, isobin_manifest_dir: impl AsRef<Path>) -> Result<()> {
+        Toml::save_to_file(self, Self::make_lock_path(isobin_manifest_dir)).await
+    }
+
+    /// Fails when the lock does not match the dependencies of `isobin_manifest`.
+    /// `unexpanded_isobin_manifest` is the same manifest loaded without expanding the environment
+    /// variables, whose requirements the lock records.
+    pub fn check_locked(
+        &self,
+        isobin_manifest: &IsobinManifest,
+        unexpanded_isobin_manifest: &IsobinManifest,
+        isobin_manifest_dir: impl AsRef<Path>,
+    ) -> Result<()> {
+        let names = self
+            .cargo
+            .stale_names(isobin_manifest.cargo(), unexpanded_isobin_manifest.cargo());
+        if names.is_empty() {
+            Ok(())
+        } else {
+            Err(IsobinLockError::new_outdated(
+                io_ext::path_to_string(Self::make_lock_path(isobin_manifest_dir)),
+                names,
+            )
+            .into())
+        }
+    }
+
+    /// Replaces each locked dependency with the one that installs exactly the locked package.
+    pub fn pin(
+        &self,
+        isobin_manifest: &IsobinManifest,
+        unexpanded_isobin_manifest: &IsobinManifest,
+    ) -> IsobinManifest {
+        isobin_manifest.clone().with_section(
+            self.cargo
+                .pin(isobin_manifest.cargo(), unexpanded_isobin_manifest.cargo()),
+        )
+    }
+
+    pub async fn update(
+        &self,
+        isobin_manifest: &IsobinManifest,
+        unexpanded_isobin_manifest: &IsobinManifest,
+        resolved_isobin_manifest: &IsobinManifest,
+        workspace: &Workspace,
+    ) -> Result<Self> {
+        Ok(Self {
+            version: Self::LOCK_VERSION,
+            cargo: self
+                .cargo
+                .update(
+                    isobin_manifest.cargo(),
+                    unexpanded_isobin_manifest.cargo(),
+                    resolved_isobin_manifest.cargo(),
+                    workspace,
+                )
+                .await?,
+        })
+    }
+
+    /// Updates the lock after an installation and saves it only when it changed.
+    pub async fn update_and_save(
+        &self,
+        isobin_manifest: &IsobinManifest,
+        unexpanded_isobin_manifest: &IsobinManifest,
+        resolved_isobin_manifest: &IsobinManifest,
+        workspace: &Workspace,
+        isobin_manifest_dir: impl AsRef<Path>,
+    ) -> Result<()> {
+        let new_isobin_lock = self
+            .update(
+                isobin_manifest,
+                unexpanded_isobin_manifest,
+                resolved_isobin_manifest,
+                workspace,
+            )
+            .await?;
+        if &new_isobin_lock != self || !Self::make_lock_path(&isobin_manifest_dir).exists() {
+            new_isobin_lock.save_to_dir(isobin_manifest_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{current_source_dir, providers::cargo::CargoInstallDependency};
+
+    #[tokio::test]
+    async fn unexpanded_requirement_locked_works() {
+        let dir = current_source_dir!().join("testdata/isobin_manifests");
+        let path = dir.join("env_vars_load.toml");
+        let isobin_manifest = IsobinManifest::load_from_file(&path).await.unwrap();
+        let unexpanded_isobin_manifest = IsobinManifest::load_unexpanded_from_file(&path)
+            .await
+            .unwrap();
+        // The requirements are recorded as they are written in the manifest.
+        let isobin_lock: IsobinLock = toml::from_str(
+            r#"
+version = 1
+
+[cargo.dependencies.mirror-tool]
+version = "0.1.0"
+source = "git+https://github.com/example/mirror-tool.git?branch=main#0123abcd"
+requirement = { git = "${ISOBIN_TEST_UNDEFINED_MIRROR:-https://github.com/example}/mirror-tool.git", branch = "main" }
+
+[cargo.dependencies.local-tool]
+version = "0.1.0"
+source = "path+${ISOBIN_TEST_UNDEFINED_ROOT:-tools}/local-tool"
+requirement = { path = "${ISOBIN_TEST_UNDEFINED_ROOT:-tools}/local-tool" }
+"#,
+        )
+        .unwrap();
+        isobin_lock
+            .check_locked(&isobin_manifest, &unexpanded_isobin_manifest, &dir)
+            .unwrap();
+
+        let pinned_isobin_manifest = isobin_lock.pin(&isobin_manifest, &unexpanded_isobin_manifest);
+        let dependencies = pinned_isobin_manifest.cargo().dependencies();
+        let Some(CargoInstallDependency::Detailed(mirror_tool)) = dependencies.get("mirror-tool")
+        else {
+            panic!("mirror-tool should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(
+            &Some("https://github.com/example/mirror-tool.git".to_string()),
+            mirror_tool.git()
+        );
+        pretty_assertions::assert_eq!(&Some("0123abcd".to_string()), mirror_tool.rev());
+        let Some(CargoInstallDependency::Detailed(local_tool)) = dependencies.get("local-tool")
+        else {
+            panic!("local-tool should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(
+            &Some(dir.join("tools/local-tool")),
+            local_tool.absolute_path()
+        );
+
+        // A lock which recorded the expanded values does not match the manifest.
+        let expanded_isobin_lock: IsobinLock = toml::from_str(
+            r#"
+version = 1
+
+[cargo.dependencies.local-tool]
+version = "0.1.0"
+source = "path+tools/local-tool"
+requirement = { path = "tools/local-tool" }
+"#,
+        )
+        .unwrap();
+        assert!(expanded_isobin_lock
+            .check_locked(&isobin_manifest, &unexpanded_isobin_manifest, &dir)
+            .is_err());
+    }
+}
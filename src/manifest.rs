@@ -1,27 +1,50 @@
 use super::*;
 use crate::{
     paths::{isobin_manifest::IsobinManifestPathError, workspace::Workspace},
-    providers::ProviderKind,
+    providers::{
+        registered_provider_registry, AnyManifest, AnyProvider, ProviderKind, ProviderRegistry,
+    },
     specified_target::SpecifiedTarget,
     utils::{
         io_ext,
+        platform::TargetPlatform,
         serde_ext::{Json, Toml, Yaml},
     },
 };
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    fmt,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
+use anyhow::anyhow;
 use providers::cargo::CargoManifest;
-use serde_derive::{Deserialize, Serialize};
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use tokio::fs;
 
-#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Getters, Default, new)]
+/// The sections of the registered providers, keyed by the provider names in the file.
+#[derive(Clone, Debug)]
 pub struct IsobinManifest {
-    #[serde(default, skip_serializing_if = "CargoManifest::is_empty")]
-    cargo: CargoManifest,
+    /// The manifests to take the entries from, relative to this manifest.
+    include: Vec<PathBuf>,
+    /// The `groups` of the dependency entries, which are kept apart from the sections so that
+    /// every provider supports them.
+    groups: DependencyGroups,
+    /// The sections which apply only on the platforms, keyed by the `cfg(...)` expressions.
+    target: BTreeMap<String, IsobinManifest>,
+    sections: HashMap<ProviderKind, Box<dyn AnyManifest>>,
+    /// The providers which the sections are read and handled with.
+    provider_registry: ProviderRegistry,
 }
 
+/// The groups of the dependencies, keyed by the provider and then by the dependency name.
+type DependencyGroups = HashMap<ProviderKind, HashMap<String, Vec<String>>>;
+
 #[derive(thiserror::Error, Debug, new)]
 pub enum IsobinManifestError {
     #[error("The target file does not have extension\npath:{path}")]
@@ -39,25 +62,205 @@ pub enum IsobinManifestError {
     #[error("{0:#?}")]
     MultiValidate(Vec<Error>),
 
+    #[error("target.'{platform}'\n{error}")]
+    Target { platform: String, error: Error },
+
+    #[error("{key} can not be used in a platform table")]
+    NotAllowedInPlatform { key: String },
+
+    #[error("In the included manifest {path}\n{error}")]
+    Include { path: String, error: Error },
+
+    #[error("The manifests include each other\n{}", paths.join(" -> "))]
+    CircularInclude { paths: Vec<String> },
+
+    #[error("No dependency belongs to the group {group}")]
+    NotFoundGroup { group: String },
+
     #[error("Not found {name} in isobin manifest")]
     NotFoundDependency {
         provider: Option<ProviderKind>,
         name: String,
     },
+
+    #[error("{name} is written in the included manifest {path}, so edit it there")]
+    DefinedInInclude { name: String, path: String },
+}
+
+/// Where an entry of the dependencies is written, seen from the manifest file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DependencyLocation {
+    /// The section of the provider in the manifest file, or the one in its platform table.
+    Section { platform: Option<String> },
+    /// The included manifest, which has the entry itself or in its own includes.
+    Include { path: PathBuf },
+}
+
+impl DependencyLocation {
+    /// Finds the entry which the host takes, as the manifest is resolved: the last platform table
+    /// of the host overrides the section of the provider, which overrides the included manifests.
+    pub(crate) fn find_applied(locations: &[Self]) -> Option<&Self> {
+        locations
+            .iter()
+            .rev()
+            .find(|location| match location {
+                Self::Section {
+                    platform: Some(platform),
+                } => platform
+                    .parse::<TargetPlatform>()
+                    .is_ok_and(|platform| platform.matches_host()),
+                _ => false,
+            })
+            .or_else(|| {
+                locations
+                    .iter()
+                    .find(|location| matches!(location, Self::Section { platform: None }))
+            })
+            .or_else(|| locations.first())
+    }
+}
+
+/// A setting which is valid but probably not what the user intended.
+#[derive(Debug, PartialEq, Getters, new)]
+pub struct IsobinManifestWarning {
+    provider: ProviderKind,
+    name: String,
+    message: String,
 }
 
 impl IsobinManifest {
+    /// Loads the manifest with the manifests which it includes, whose entries it overrides.
     pub async fn load_from_file(path: impl AsRef<Path>) -> Result<IsobinManifest> {
-        let file_extension = Self::get_file_extension(path.as_ref())?;
-        let isobin_manifest = Self::parse(file_extension, path.as_ref()).await?;
+        Self::load_from_file_with_registry(path, &registered_provider_registry()).await
+    }
+
+    /// Loads the manifest like `load_from_file`, reading the sections of the given providers.
+    pub async fn load_from_file_with_registry(
+        path: impl AsRef<Path>,
+        provider_registry: &ProviderRegistry,
+    ) -> Result<IsobinManifest> {
+        Self::load_with_include(path.as_ref(), true, provider_registry).await
+    }
+
+    /// Loads the manifest with the manifests which it includes like `load_from_file`, but leaves the
+    /// entries as they are written, so the lock does not record the values of the environment variables.
+    pub(crate) async fn load_unexpanded_from_file(
+        path: impl AsRef<Path>,
+    ) -> Result<IsobinManifest> {
+        Self::load_with_include(path.as_ref(), false, &registered_provider_registry()).await
+    }
+
+    async fn load_with_include(
+        path: &Path,
+        fix: bool,
+        provider_registry: &ProviderRegistry,
+    ) -> Result<IsobinManifest> {
+        let isobin_manifest = Self::load_single_file(path, fix, provider_registry).await?;
+        // The canonical path lets the paths of the included manifests be made relative to it.
+        let path = fs::canonicalize(path)
+            .await
+            .unwrap_or_else(|_| path.to_path_buf());
+        isobin_manifest
+            .resolve_include(&path, &mut vec![path.clone()], fix)
+            .await
+    }
+
+    /// Loads only the file as it is written, so the environment variables are not expanded and
+    /// edited entries can be written back.
+    pub(crate) async fn load_unfixed_from_file(path: impl AsRef<Path>) -> Result<IsobinManifest> {
+        let path = path.as_ref();
+        Self::parse(
+            Self::get_file_extension(path)?,
+            path,
+            &registered_provider_registry(),
+        )
+        .await
+    }
+
+    async fn load_single_file(
+        path: &Path,
+        fix: bool,
+        provider_registry: &ProviderRegistry,
+    ) -> Result<IsobinManifest> {
+        let file_extension = Self::get_file_extension(path)?;
+        let isobin_manifest = Self::parse(file_extension, path, provider_registry).await?;
         let isobin_manifest_dir = path
-            .as_ref()
             .parent()
             .ok_or_else(IsobinManifestPathError::new_not_found_isobin_manifest)?;
 
-        let isobin_manifest = isobin_manifest.fix(isobin_manifest_dir);
-        isobin_manifest.validate()?;
-        Ok(isobin_manifest)
+        let isobin_manifest = if fix {
+            let isobin_manifest = isobin_manifest.fix(isobin_manifest_dir);
+            isobin_manifest.validate()?;
+            isobin_manifest
+        } else {
+            isobin_manifest
+        };
+        Ok(isobin_manifest.resolve_target())
+    }
+
+    /// Takes the entries of the platforms which the host matches over the common ones.
+    fn resolve_target(mut self) -> Self {
+        let target = std::mem::take(&mut self.target);
+        target
+            .into_iter()
+            .filter(|(platform, _)| {
+                platform
+                    .parse::<TargetPlatform>()
+                    .is_ok_and(|platform| platform.matches_host())
+            })
+            .fold(self, |isobin_manifest, (_, platform_manifest)| {
+                platform_manifest.inherit(&isobin_manifest)
+            })
+    }
+
+    /// `including_paths` holds the chain of the canonical paths which lead to this manifest.
+    #[async_recursion::async_recursion]
+    async fn resolve_include(
+        mut self,
+        path: &Path,
+        including_paths: &mut Vec<PathBuf>,
+        fix: bool,
+    ) -> Result<IsobinManifest> {
+        let include = std::mem::take(&mut self.include);
+        if include.is_empty() {
+            return Ok(self);
+        }
+        let isobin_manifest_dir = path
+            .parent()
+            .ok_or_else(IsobinManifestPathError::new_not_found_isobin_manifest)?;
+        let mut included_manifest = Self::from_provider_registry(self.provider_registry.clone());
+        for include_path in include.iter() {
+            let include_path = isobin_manifest_dir.join(include_path);
+            let include_path = fs::canonicalize(&include_path).await.map_err(|err| {
+                IsobinManifestError::new_include(io_ext::path_to_string(&include_path), err.into())
+            })?;
+            if including_paths.contains(&include_path) {
+                Err(IsobinManifestError::new_circular_include(
+                    including_paths
+                        .iter()
+                        .chain([&include_path])
+                        .map(io_ext::path_to_string)
+                        .collect(),
+                ))?;
+            }
+            let isobin_manifest =
+                Self::load_single_file(&include_path, fix, &self.provider_registry)
+                    .await
+                    .map_err(|err| {
+                        IsobinManifestError::new_include(io_ext::path_to_string(&include_path), err)
+                    })?;
+            including_paths.push(include_path.clone());
+            let isobin_manifest = isobin_manifest
+                .resolve_include(&include_path, including_paths, fix)
+                .await?;
+            including_paths.pop();
+            let include_dir = include_path
+                .parent()
+                .ok_or_else(IsobinManifestPathError::new_not_found_isobin_manifest)?;
+            let isobin_manifest = isobin_manifest.rebase(include_dir, isobin_manifest_dir);
+            included_manifest = isobin_manifest.inherit(&included_manifest);
+        }
+        Ok(self.inherit(&included_manifest))
     }
 
     #[allow(dead_code)]
@@ -70,10 +273,10 @@ impl IsobinManifest {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.cargo().dependencies().is_empty()
+        self.sections.values().all(|manifest| manifest.is_empty())
     }
 
-    fn get_file_extension(path: impl AsRef<Path>) -> Result<ManifestFileExtensions> {
+    pub fn get_file_extension(path: impl AsRef<Path>) -> Result<ManifestFileExtensions> {
         let extension = path
             .as_ref()
             .extension()
@@ -99,58 +302,257 @@ impl IsobinManifest {
             .into()),
         }
     }
+    /// Validates the entries of every platform, not only the ones of the host.
     pub fn validate(&self) -> Result<()> {
-        self.cargo.validate()
+        let errs = self
+            .provider_registry
+            .providers()
+            .iter()
+            .filter_map(|provider| provider.validate(self.section_of(provider).as_ref()).err())
+            .chain(
+                self.target
+                    .iter()
+                    .filter_map(|(platform, platform_manifest)| {
+                        Self::validate_platform(platform, platform_manifest)
+                            .err()
+                            .map(|err| {
+                                IsobinManifestError::new_target(platform.clone(), err).into()
+                            })
+                    }),
+            )
+            .flat_map(|err| match err.downcast::<IsobinManifestError>() {
+                Ok(IsobinManifestError::MultiValidate(errs)) => errs,
+                Ok(err) => vec![err.into()],
+                Err(err) => vec![err],
+            })
+            .collect::<Vec<_>>();
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(IsobinManifestError::MultiValidate(errs).into())
+        }
     }
-    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
-        self.cargo = self.cargo.fix(isobin_manifest_dir);
-        self
+    fn validate_platform(platform: &str, platform_manifest: &Self) -> Result<()> {
+        platform.parse::<TargetPlatform>()?;
+        if !platform_manifest.include.is_empty() {
+            Err(IsobinManifestError::new_not_allowed_in_platform(
+                INCLUDE_KEY.into(),
+            ))?;
+        }
+        if !platform_manifest.target.is_empty() {
+            Err(IsobinManifestError::new_not_allowed_in_platform(
+                TARGET_KEY.into(),
+            ))?;
+        }
+        platform_manifest.validate()
     }
 
-    pub fn filter_target(&self, targets: &[SpecifiedTarget]) -> Result<Self> {
-        let mut new_manifest = self.clone();
-        new_manifest.cargo = CargoManifest::default();
-        let mut errs = vec![];
-        for target in targets
+    pub fn warnings(&self) -> Vec<IsobinManifestWarning> {
+        self.provider_registry
+            .providers()
             .iter()
-            .filter(|t| t.provider_kind() == &Some(ProviderKind::Cargo))
-            .map(|t| t.name())
-        {
-            match self.cargo().filter_target(target) {
-                Ok(cargo_manifest) => {
-                    new_manifest.cargo = new_manifest.cargo.merge(&cargo_manifest);
-                }
-                Err(err) => {
-                    errs.push(err);
-                }
-            }
+            .flat_map(|provider| provider.warnings(self.section_of(provider).as_ref()))
+            .collect()
+    }
+    pub fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        Self {
+            target: self
+                .target
+                .iter()
+                .map(|(platform, platform_manifest)| {
+                    (
+                        platform.clone(),
+                        platform_manifest.clone().fix(isobin_manifest_dir),
+                    )
+                })
+                .collect(),
+            ..self.map_sections(|provider, manifest| provider.fix(manifest, isobin_manifest_dir))
         }
+    }
 
-        for target in targets
-            .iter()
-            .filter(|t| t.provider_kind().is_none())
-            .map(|t| t.name())
-        {
-            match self.cargo().filter_target(target) {
-                Ok(cargo_manifest) => {
-                    new_manifest.cargo = new_manifest.cargo.merge(&cargo_manifest);
+    /// Makes the paths relative to `from_dir` relative to `to_dir`, so the entries of an included
+    /// manifest resolve the same when the cache and the lock fix them with the root manifest dir.
+    /// The absolute paths are only resolved again when the manifest has already been fixed.
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        self.map_sections(|provider, manifest| provider.rebase(manifest, from_dir, to_dir))
+    }
+
+    pub fn filter_target(&self, targets: &[SpecifiedTarget]) -> Result<Self> {
+        let providers = self.provider_registry.providers();
+        let mut new_manifest = self.map_sections(|_, manifest| manifest.make_empty());
+        let mut errs = vec![];
+        for target in targets.iter() {
+            let name = target.name();
+            let mut found = false;
+            for provider in providers.iter() {
+                let provider_kind = provider.provider_kind();
+                if target
+                    .provider_kind()
+                    .as_ref()
+                    .is_some_and(|target_provider_kind| target_provider_kind != &provider_kind)
+                {
+                    continue;
                 }
-                Err(_) => {
-                    errs.push(
-                        IsobinManifestError::new_not_found_dependency(None, target.clone()).into(),
-                    );
+                if let Ok(manifest) = self.section_of(provider).filter_target(name) {
+                    let merged_manifest =
+                        new_manifest.section_of(provider).merge(manifest.as_ref());
+                    new_manifest.sections.insert(provider_kind, merged_manifest);
+                    found = true;
                 }
             }
+            if !found {
+                errs.push(
+                    IsobinManifestError::new_not_found_dependency(
+                        target.provider_kind().clone(),
+                        name.clone(),
+                    )
+                    .into(),
+                );
+            }
         }
         if errs.is_empty() {
-            Ok(new_manifest)
+            Ok(new_manifest.with_groups(self.groups.clone()))
         } else {
             Err(IsobinManifestError::new_multi_validate(errs).into())
         }
     }
 
+    /// Selects the dependencies which belong to one of `groups`, or to any group including none
+    /// when `groups` is empty, and to none of `without_groups`.
+    pub fn filter_groups(&self, groups: &[String], without_groups: &[String]) -> Result<Self> {
+        if let Some(group) = groups.iter().find(|group| {
+            !self
+                .groups
+                .values()
+                .flat_map(|groups| groups.values())
+                .any(|dependency_groups| dependency_groups.contains(group))
+        }) {
+            Err(IsobinManifestError::new_not_found_group(group.clone()))?;
+        }
+        let unselected_manifest = self.map_sections(|provider, manifest| {
+            let dependency_groups = self.groups.get(&provider.provider_kind());
+            manifest
+                .names()
+                .iter()
+                .filter(|name| {
+                    let dependency_groups = dependency_groups
+                        .and_then(|dependency_groups| dependency_groups.get(name.as_str()))
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    let selected = (groups.is_empty()
+                        || dependency_groups.iter().any(|group| groups.contains(group)))
+                        && !dependency_groups
+                            .iter()
+                            .any(|group| without_groups.contains(group));
+                    !selected
+                })
+                .fold(manifest.make_empty(), |unselected_manifest, name| {
+                    unselected_manifest.merge(
+                        manifest
+                            .filter_target(name)
+                            .expect("the name is in the manifest")
+                            .as_ref(),
+                    )
+                })
+        });
+        Ok(self.remove_targets(&unselected_manifest))
+    }
+
+    /// Returns the entry of the dependency in the manifest file, with the groups which it had.
+    pub(crate) fn make_dependency_entry(
+        &self,
+        provider_kind: &ProviderKind,
+        name: &str,
+        dependency: &impl Serialize,
+    ) -> Result<serde_json::Value> {
+        let mut entry = serde_json::to_value(dependency)?;
+        if let (Some(entry), Some(groups)) = (
+            entry.as_object_mut(),
+            self.groups
+                .get(provider_kind)
+                .and_then(|dependency_groups| dependency_groups.get(name)),
+        ) {
+            entry.insert(GROUPS_KEY.into(), groups.clone().into());
+        }
+        Ok(entry)
+    }
+
+    /// Finds where the dependency is written, in the manifest file and then in the included ones,
+    /// so that the entry can be edited where it takes effect.
+    pub(crate) async fn locate_dependency(
+        path: &Path,
+        provider_kind: &ProviderKind,
+        name: &str,
+    ) -> Result<Vec<DependencyLocation>> {
+        let isobin_manifest = Self::load_unfixed_from_file(path).await?;
+        let mut locations = vec![];
+        if isobin_manifest.has_dependency(provider_kind, name) {
+            locations.push(DependencyLocation::Section { platform: None });
+        }
+        for (platform, platform_manifest) in isobin_manifest.target.iter() {
+            if platform_manifest.has_dependency(provider_kind, name) {
+                locations.push(DependencyLocation::Section {
+                    platform: Some(platform.clone()),
+                });
+            }
+        }
+        let isobin_manifest_dir = path
+            .parent()
+            .ok_or_else(IsobinManifestPathError::new_not_found_isobin_manifest)?;
+        for include_path in isobin_manifest.include.iter() {
+            let include_path = isobin_manifest_dir.join(include_path);
+            let include_path = fs::canonicalize(&include_path)
+                .await
+                .unwrap_or(include_path);
+            let included_manifest =
+                Self::load_with_include(&include_path, false, &isobin_manifest.provider_registry)
+                    .await?;
+            if included_manifest.has_dependency(provider_kind, name) {
+                locations.push(DependencyLocation::Include { path: include_path });
+            }
+        }
+        Ok(locations)
+    }
+
+    /// Returns the manifest itself, or its table of the platform.
+    pub(crate) fn platform_manifest(&self, platform: Option<&str>) -> Option<&IsobinManifest> {
+        match platform {
+            Some(platform) => self.target.get(platform),
+            None => Some(self),
+        }
+    }
+
+    fn has_dependency(&self, provider_kind: &ProviderKind, name: &str) -> bool {
+        self.sections.get(provider_kind).is_some_and(|manifest| {
+            manifest
+                .names()
+                .iter()
+                .any(|dependency_name| dependency_name == name)
+        })
+    }
+
     pub fn exists_name(&self, name: impl AsRef<str>) -> bool {
-        self.cargo().dependencies().contains_key(name.as_ref())
+        self.sections.values().any(|manifest| {
+            manifest
+                .names()
+                .iter()
+                .any(|dependency_name| dependency_name == name.as_ref())
+        })
+    }
+
+    /// The names of the dependencies with their providers in the order of the registration.
+    pub fn dependency_names(&self) -> Vec<(ProviderKind, String)> {
+        self.provider_registry
+            .providers()
+            .iter()
+            .flat_map(|provider| {
+                self.section_of(provider)
+                    .names()
+                    .into_iter()
+                    .map(|name| (provider.provider_kind(), name))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
     }
 
     pub async fn ditect_difference(
@@ -160,30 +562,142 @@ impl IsobinManifest {
         name: impl AsRef<str>,
         workspace: &Workspace,
     ) -> Result<bool> {
-        match provider_kind {
-            ProviderKind::Cargo => {
-                self.cargo()
-                    .ditect_difference(other.cargo(), name.as_ref(), workspace)
+        match self.provider_registry.find(provider_kind) {
+            Some(provider) => {
+                provider
+                    .ditect_difference(
+                        self.section_of(provider).as_ref(),
+                        other.section_of(provider).as_ref(),
+                        name.as_ref(),
+                        workspace,
+                    )
                     .await
             }
+            // The bins of a provider which is not registered anymore are left as they are.
+            None => Ok(false),
         }
     }
 
+    fn inherit(&self, included_manifest: &Self) -> Self {
+        self.map_sections(|provider, manifest| {
+            manifest.inherit(included_manifest.section_of(provider).as_ref())
+        })
+        .with_groups(included_manifest.merge_groups(self))
+    }
+
     pub fn merge(&self, new_manifest: &Self) -> Self {
-        Self::new(self.cargo().merge(new_manifest.cargo()))
+        self.map_sections(|provider, manifest| {
+            manifest.merge(new_manifest.section_of(provider).as_ref())
+        })
+        .with_groups(self.merge_groups(new_manifest))
+    }
+
+    /// The groups of an entry follow the manifest which the entry is taken from.
+    fn merge_groups(&self, new_manifest: &Self) -> DependencyGroups {
+        let mut groups = self.groups.clone();
+        for (provider_kind, name) in new_manifest.dependency_names() {
+            match new_manifest
+                .groups
+                .get(&provider_kind)
+                .and_then(|dependency_groups| dependency_groups.get(&name))
+            {
+                Some(dependency_groups) => {
+                    groups
+                        .entry(provider_kind)
+                        .or_default()
+                        .insert(name, dependency_groups.clone());
+                }
+                None => {
+                    groups
+                        .get_mut(&provider_kind)
+                        .map(|dependency_groups| dependency_groups.remove(&name));
+                }
+            }
+        }
+        groups
+    }
+
+    /// Keeps the groups of the dependencies which are in the manifest.
+    fn with_groups(mut self, mut groups: DependencyGroups) -> Self {
+        for (provider_kind, dependency_groups) in groups.iter_mut() {
+            let names = self
+                .sections
+                .get(provider_kind)
+                .map(|manifest| manifest.names())
+                .unwrap_or_default();
+            dependency_groups.retain(|name, _| names.contains(name));
+        }
+        groups.retain(|_, dependency_groups| !dependency_groups.is_empty());
+        self.groups = groups;
+        self
     }
     pub fn remove_targets(&self, remove_target_manifest: &Self) -> Self {
-        Self::new(self.cargo().remove_targets(remove_target_manifest.cargo()))
+        self.map_sections(|provider, manifest| {
+            manifest.remove_targets(remove_target_manifest.section_of(provider).as_ref())
+        })
+    }
+
+    pub fn cargo(&self) -> &CargoManifest {
+        self.builtin_section(&ProviderKind::Cargo)
+    }
+
+    /// Replaces the section of the provider which the manifest belongs to.
+    pub fn with_section(mut self, manifest: impl ProviderManifest) -> Self {
+        self.sections
+            .insert(manifest.provider_kind(), Box::new(manifest));
+        self
+    }
+
+    fn builtin_section<M: ProviderManifest>(&self, provider_kind: &ProviderKind) -> &M {
+        self.sections
+            .get(provider_kind)
+            .and_then(|manifest| manifest.as_any().downcast_ref())
+            .expect("the builtin providers are always registered")
+    }
+
+    /// Returns the section of the provider, which is empty when the provider was registered
+    /// after the manifest was made.
+    pub(crate) fn section_of(&self, provider: &Arc<dyn AnyProvider>) -> Box<dyn AnyManifest> {
+        self.sections
+            .get(&provider.provider_kind())
+            .cloned()
+            .unwrap_or_else(|| provider.default_manifest())
+    }
+
+    fn map_sections(
+        &self,
+        f: impl Fn(&Arc<dyn AnyProvider>, &dyn AnyManifest) -> Box<dyn AnyManifest>,
+    ) -> Self {
+        Self {
+            include: self.include.clone(),
+            groups: HashMap::new(),
+            target: BTreeMap::new(),
+            sections: self
+                .provider_registry
+                .providers()
+                .iter()
+                .map(|provider| {
+                    (
+                        provider.provider_kind(),
+                        f(provider, self.section_of(provider).as_ref()),
+                    )
+                })
+                .collect(),
+            provider_registry: self.provider_registry.clone(),
+        }
+        .with_groups(self.groups.clone())
     }
 
     async fn parse(
         file_extension: ManifestFileExtensions,
         path: impl AsRef<Path>,
+        provider_registry: &ProviderRegistry,
     ) -> Result<IsobinManifest> {
+        let seed = IsobinManifestSeed(provider_registry);
         match file_extension {
-            ManifestFileExtensions::Toml => Ok(Toml::parse_from_file(path).await?),
-            ManifestFileExtensions::Yaml => Ok(Yaml::parse_from_file(path).await?),
-            ManifestFileExtensions::Json => Ok(Json::parse_from_file(path).await?),
+            ManifestFileExtensions::Toml => Ok(Toml::parse_from_file_with_seed(seed, path).await?),
+            ManifestFileExtensions::Yaml => Ok(Yaml::parse_from_file_with_seed(seed, path).await?),
+            ManifestFileExtensions::Json => Ok(Json::parse_from_file_with_seed(seed, path).await?),
         }
     }
     async fn save(
@@ -203,26 +717,350 @@ impl IsobinManifest {
         old: &Self,
         workspace: &Workspace,
     ) -> Result<Self> {
+        let mut sections = HashMap::new();
+        for provider in base.provider_registry.providers().iter() {
+            sections.insert(
+                provider.provider_kind(),
+                provider
+                    .get_need_install_dependency_manifest(
+                        base.section_of(provider).as_ref(),
+                        old.section_of(provider).as_ref(),
+                        workspace,
+                    )
+                    .await?,
+            );
+        }
         Ok(Self {
-            cargo: CargoManifest::get_need_install_dependency_manifest(
-                base.cargo(),
-                old.cargo(),
-                workspace,
-            )
-            .await?,
+            include: vec![],
+            groups: HashMap::new(),
+            target: BTreeMap::new(),
+            sections,
+            provider_registry: base.provider_registry.clone(),
         })
     }
 
     pub async fn get_need_uninstall_dependency_manifest(base: &Self, old: &Self) -> Result<Self> {
+        let mut sections = HashMap::new();
+        for provider in base.provider_registry.providers().iter() {
+            sections.insert(
+                provider.provider_kind(),
+                provider
+                    .get_need_uninstall_dependency_manifest(
+                        base.section_of(provider).as_ref(),
+                        old.section_of(provider).as_ref(),
+                    )
+                    .await?,
+            );
+        }
         Ok(Self {
-            cargo: CargoManifest::get_need_uninstall_dependency_manifest(base.cargo(), old.cargo())
-                .await?,
+            include: vec![],
+            groups: HashMap::new(),
+            target: BTreeMap::new(),
+            sections,
+            provider_registry: base.provider_registry.clone(),
         })
     }
 }
 
+impl Default for IsobinManifest {
+    fn default() -> Self {
+        Self::from_provider_registry(registered_provider_registry())
+    }
+}
+
+impl PartialEq for IsobinManifest {
+    fn eq(&self, other: &Self) -> bool {
+        self.include == other.include
+            && self.groups == other.groups
+            && self.target == other.target
+            && self
+                .provider_registry
+                .providers()
+                .iter()
+                .all(|provider| self.section_of(provider) == other.section_of(provider))
+    }
+}
+
+impl Serialize for IsobinManifest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.include.is_empty() {
+            map.serialize_entry(INCLUDE_KEY, &self.include)?;
+        }
+        for provider in self.provider_registry.providers().iter() {
+            if let Some(manifest) = self.sections.get(&provider.provider_kind()) {
+                if manifest.is_empty() {
+                    continue;
+                }
+                match self.groups.get(&provider.provider_kind()) {
+                    Some(dependency_groups) => {
+                        let mut section = serde_json::to_value(manifest.as_serialize())
+                            .map_err(ser::Error::custom)?;
+                        put_dependency_groups(&mut section, dependency_groups);
+                        map.serialize_entry(&provider.provider_kind().to_string(), &section)?;
+                    }
+                    None => map.serialize_entry(
+                        &provider.provider_kind().to_string(),
+                        manifest.as_serialize(),
+                    )?,
+                }
+            }
+        }
+        if !self.target.is_empty() {
+            map.serialize_entry(TARGET_KEY, &self.target)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for IsobinManifest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        IsobinManifestSeed(&registered_provider_registry()).deserialize(deserializer)
+    }
+}
+
+const INCLUDE_KEY: &str = "include";
+pub(crate) const TARGET_KEY: &str = "target";
+
+/// Reads the sections of the providers in the registry.
+#[derive(Clone, Copy)]
+struct IsobinManifestSeed<'a>(&'a ProviderRegistry);
+
+impl<'de> DeserializeSeed<'de> for IsobinManifestSeed<'_> {
+    type Value = IsobinManifest;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for IsobinManifestSeed<'_> {
+    type Value = IsobinManifest;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the sections of the providers")
+    }
+
+    // The sections are taken in the order of the providers as the derived struct used to.
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut isobin_manifest = IsobinManifest::from_provider_registry(self.0.clone());
+        for provider in self.0.providers().iter() {
+            match seq.next_element_seed(ManifestSectionSeed(provider))? {
+                Some((manifest, dependency_groups)) => {
+                    isobin_manifest.insert_section(provider, manifest, dependency_groups);
+                }
+                None => break,
+            }
+        }
+        Ok(isobin_manifest)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let providers = self.0.providers();
+        let mut isobin_manifest = IsobinManifest::from_provider_registry(self.0.clone());
+        while let Some(key) = map.next_key::<String>()? {
+            if key == INCLUDE_KEY {
+                isobin_manifest.include = map.next_value()?;
+                continue;
+            }
+            if key == TARGET_KEY {
+                isobin_manifest.target = map.next_value_seed(PlatformManifestsSeed(self.0))?;
+                continue;
+            }
+            // The sections of the providers which are not registered are ignored.
+            match providers
+                .iter()
+                .find(|provider| provider.provider_kind().to_string() == key)
+            {
+                Some(provider) => {
+                    let (manifest, dependency_groups) =
+                        map.next_value_seed(ManifestSectionSeed(provider))?;
+                    isobin_manifest.insert_section(provider, manifest, dependency_groups);
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(isobin_manifest)
+    }
+}
+
+/// Reads the platform tables with the same providers as the manifest which has them.
+struct PlatformManifestsSeed<'a>(&'a ProviderRegistry);
+
+impl<'de> DeserializeSeed<'de> for PlatformManifestsSeed<'_> {
+    type Value = BTreeMap<String, IsobinManifest>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PlatformManifestsSeed<'_> {
+    type Value = BTreeMap<String, IsobinManifest>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the manifests keyed by the platforms")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut platform_manifests = BTreeMap::new();
+        while let Some(platform) = map.next_key::<String>()? {
+            let platform_manifest = map.next_value_seed(IsobinManifestSeed(self.0))?;
+            platform_manifests.insert(platform, platform_manifest);
+        }
+        Ok(platform_manifests)
+    }
+}
+
+impl IsobinManifest {
+    /// Makes the manifest without dependencies, whose sections are of the providers.
+    fn from_provider_registry(provider_registry: ProviderRegistry) -> Self {
+        Self {
+            include: vec![],
+            groups: HashMap::new(),
+            target: BTreeMap::new(),
+            sections: provider_registry
+                .providers()
+                .iter()
+                .map(|provider| (provider.provider_kind(), provider.default_manifest()))
+                .collect(),
+            provider_registry,
+        }
+    }
+
+    /// The providers of the sections in the order of the registration.
+    pub(crate) fn providers(&self) -> &[Arc<dyn AnyProvider>] {
+        self.provider_registry.providers()
+    }
+
+    fn insert_section(
+        &mut self,
+        provider: &Arc<dyn AnyProvider>,
+        manifest: Box<dyn AnyManifest>,
+        dependency_groups: HashMap<String, Vec<String>>,
+    ) {
+        if !dependency_groups.is_empty() {
+            self.groups
+                .insert(provider.provider_kind(), dependency_groups);
+        }
+        self.sections.insert(provider.provider_kind(), manifest);
+    }
+}
+
+/// Takes the `groups` out of the dependency entries, so that the providers do not see them.
+struct ManifestSectionSeed<'a>(&'a Arc<dyn AnyProvider>);
+
+impl<'de> DeserializeSeed<'de> for ManifestSectionSeed<'_> {
+    type Value = (Box<dyn AnyManifest>, HashMap<String, Vec<String>>);
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        let mut section = serde_json::Value::deserialize(deserializer)?;
+        let dependency_groups = take_dependency_groups(&mut section).map_err(de::Error::custom)?;
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(section.clone());
+        let manifest = self
+            .0
+            .deserialize_manifest(&mut deserializer)
+            .map_err(|err| match self.find_invalid_dependency(&section) {
+                Some(name) => de::Error::custom(format!("{err} for key `{name}`")),
+                None => de::Error::custom(err),
+            })?;
+        Ok((manifest, dependency_groups))
+    }
+}
+
+impl ManifestSectionSeed<'_> {
+    /// The buffered section loses the position of an error, so the entry is told instead.
+    fn find_invalid_dependency(&self, section: &serde_json::Value) -> Option<String> {
+        let dependencies = section.get(DEPENDENCIES_KEY)?.as_object()?;
+        dependencies.iter().find_map(|(name, dependency)| {
+            let mut section = section.clone();
+            section[DEPENDENCIES_KEY] = serde_json::json!({ name: dependency });
+            let mut deserializer = <dyn erased_serde::Deserializer>::erase(section);
+            self.0
+                .deserialize_manifest(&mut deserializer)
+                .is_err()
+                .then(|| name.clone())
+        })
+    }
+}
+
+pub(crate) const DEPENDENCIES_KEY: &str = "dependencies";
+const GROUPS_KEY: &str = "groups";
+
+fn take_dependency_groups(section: &mut serde_json::Value) -> Result<HashMap<String, Vec<String>>> {
+    let mut dependency_groups = HashMap::new();
+    let dependencies = section
+        .get_mut(DEPENDENCIES_KEY)
+        .and_then(serde_json::Value::as_object_mut);
+    for (name, dependency) in dependencies.into_iter().flatten() {
+        if let Some(groups) = dependency
+            .as_object_mut()
+            .and_then(|dependency| dependency.remove(GROUPS_KEY))
+        {
+            let groups = serde_json::from_value(groups)
+                .map_err(|err| anyhow!("groups of {name} should be a list of names: {err}"))?;
+            dependency_groups.insert(name.clone(), groups);
+        }
+    }
+    Ok(dependency_groups)
+}
+
+fn put_dependency_groups(
+    section: &mut serde_json::Value,
+    dependency_groups: &HashMap<String, Vec<String>>,
+) {
+    let dependencies = section
+        .get_mut(DEPENDENCIES_KEY)
+        .and_then(serde_json::Value::as_object_mut);
+    for (name, dependency) in dependencies.into_iter().flatten() {
+        if let (Some(dependency), Some(groups)) =
+            (dependency.as_object_mut(), dependency_groups.get(name))
+        {
+            dependency.insert(GROUPS_KEY.into(), groups.clone().into());
+        }
+    }
+}
+
+/// An entry of the dependencies of a manifest section.
+pub trait ManifestDependency: Clone + PartialEq + Send + Sync {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Resolves the settings which are relative to the manifest dir.
+    fn fix(self, _isobin_manifest_dir: &Path) -> Self {
+        self
+    }
+
+    /// Makes the paths relative to `from_dir` relative to `to_dir`.
+    fn rebase(self, _from_dir: &Path, _to_dir: &Path) -> Self {
+        self
+    }
+}
+
 pub trait Manifest: Clone {
-    type Dependency: Clone;
+    type Dependency: ManifestDependency;
+    fn provider_kind(&self) -> ProviderKind;
     fn dependencies(&self) -> &HashMap<String, Self::Dependency>;
 
     fn is_empty(&self) -> bool {
@@ -231,6 +1069,11 @@ pub trait Manifest: Clone {
 
     fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self;
 
+    /// Makes the manifest without the dependencies, keeping the other settings.
+    fn make_empty(&self) -> Self {
+        self.make_from_new_dependencies(HashMap::new())
+    }
+
     fn filter_target(&self, target: impl AsRef<str>) -> Result<Self> {
         let mut new_dependencies = HashMap::<String, Self::Dependency>::new();
         if let Some(dependency) = self.dependencies().get(target.as_ref()) {
@@ -238,7 +1081,7 @@ pub trait Manifest: Clone {
             Ok(self.make_from_new_dependencies(new_dependencies))
         } else {
             Err(IsobinManifestError::new_not_found_dependency(
-                Some(ProviderKind::Cargo),
+                Some(self.provider_kind()),
                 target.as_ref().to_string(),
             )
             .into())
@@ -253,6 +1096,11 @@ pub trait Manifest: Clone {
         self.make_from_new_dependencies(new_dependencies)
     }
 
+    /// Takes the entries of an included manifest which this manifest does not override.
+    fn inherit(&self, included_manifest: &Self) -> Self {
+        included_manifest.merge(self)
+    }
+
     fn remove_targets(&self, remove_target_manifest: &Self) -> Self {
         let mut new_dependencies = self.dependencies().clone();
         for name in self.dependencies().keys() {
@@ -262,10 +1110,104 @@ pub trait Manifest: Clone {
         }
         self.make_from_new_dependencies(new_dependencies)
     }
+
+    /// Returns the dependencies of `base` whose entries differ from the ones of `old`.
+    fn get_need_install_dependency_manifest(base: &Self, old: &Self) -> Self {
+        base.make_from_new_dependencies(
+            base.dependencies()
+                .iter()
+                .filter(|(name, dependency)| old.dependencies().get(*name) != Some(dependency))
+                .map(|(name, dependency)| (name.to_string(), dependency.clone()))
+                .collect(),
+        )
+    }
+
+    /// Returns the dependencies of `old` which `base` does not have anymore.
+    fn get_need_uninstall_dependency_manifest(base: &Self, old: &Self) -> Self {
+        old.make_from_new_dependencies(
+            old.dependencies()
+                .iter()
+                .filter(|(name, _)| !base.dependencies().contains_key(*name))
+                .map(|(name, dependency)| (name.to_string(), dependency.clone()))
+                .collect(),
+        )
+    }
+
+    fn ditect_difference(&self, other: &Self, name: &str) -> bool {
+        match self.dependencies().get(name) {
+            Some(dependency) => other.dependencies().get(name) != Some(dependency),
+            None => false,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let errs = self
+            .dependencies()
+            .iter()
+            .filter_map(|(name, dependency)| {
+                dependency.validate().err().map(|err| {
+                    IsobinManifestError::new_validate(self.provider_kind(), name.to_string(), err)
+                        .into()
+                })
+            })
+            .collect::<Vec<_>>();
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(IsobinManifestError::MultiValidate(errs).into())
+        }
+    }
+
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        let dependencies = self
+            .dependencies()
+            .iter()
+            .map(|(name, dependency)| {
+                (
+                    name.to_string(),
+                    dependency.clone().fix(isobin_manifest_dir),
+                )
+            })
+            .collect();
+        self.make_from_new_dependencies(dependencies)
+    }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        let dependencies = self
+            .dependencies()
+            .iter()
+            .map(|(name, dependency)| {
+                (
+                    name.to_string(),
+                    dependency.clone().rebase(from_dir, to_dir),
+                )
+            })
+            .collect();
+        self.make_from_new_dependencies(dependencies)
+    }
+}
+
+/// A manifest section which isobin reads, writes and compares without knowing its type.
+pub trait ProviderManifest:
+    Manifest + Default + PartialEq + fmt::Debug + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+}
+
+impl<M> ProviderManifest for M where
+    M: Manifest
+        + Default
+        + PartialEq
+        + fmt::Debug
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static
+{
 }
 
 #[derive(PartialEq, Debug)]
-enum ManifestFileExtensions {
+pub enum ManifestFileExtensions {
     Yaml,
     Toml,
     Json,
@@ -283,10 +1225,9 @@ impl IsobinManifestCache {
         let cache_file_path = Self::make_cache_path(dir);
 
         if cache_file_path.exists() {
-            match Json::parse_or_default_if_not_found(cache_file_path).await {
-                Ok(cache) => cache,
-                Err(_) => IsobinManifest::default(),
-            }
+            Json::parse_or_default_if_not_found(cache_file_path)
+                .await
+                .unwrap_or_default()
         } else {
             IsobinManifest::default()
         }
@@ -310,7 +1251,16 @@ mod tests {
     use super::*;
     use anyhow::anyhow;
     use cargo::util::PartialVersion;
-    use providers::cargo::{CargoInstallDependency, CargoInstallDependencyDetail};
+    use providers::{
+        cargo::{CargoInstallDependency, CargoInstallDependencyDetail},
+        custom::{CustomCommands, CustomInstallDependency, CustomManifest},
+        go::{GoInstallDependency, GoInstallDependencyDetail, GoManifest},
+        local::{
+            LocalInstallDependency, LocalInstallDependencyDetail, LocalLinkMode, LocalManifest,
+        },
+        npm::{NpmInstallDependency, NpmInstallDependencyDetail, NpmManifest},
+        python::{PythonInstallDependency, PythonInstallDependencyDetail, PythonManifest},
+    };
 
     use utils::serde_ext::{ErrorHint, SerdeExtError};
 
@@ -319,6 +1269,11 @@ mod tests {
         "testdata/isobin_manifests/default_load.toml",
         tool_manifest(cargo_install_dependencies())
     )]
+    #[case("testdata/isobin_manifests/npm_load.toml", npm_tool_manifest())]
+    #[case("testdata/isobin_manifests/python_load.toml", python_tool_manifest())]
+    #[case("testdata/isobin_manifests/go_load.toml", go_tool_manifest())]
+    #[case("testdata/isobin_manifests/local_load.toml", local_tool_manifest())]
+    #[case("testdata/isobin_manifests/custom_load.toml", custom_tool_manifest())]
     #[tokio::test]
     async fn isobin_manifest_from_path_works(#[case] path: &str, #[case] expected: IsobinManifest) {
         let dir = current_source_dir!();
@@ -328,6 +1283,251 @@ mod tests {
         pretty_assertions::assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn isobin_manifest_include_works() {
+        let dir = current_source_dir!().join("testdata/isobin_manifests/include");
+        let actual = IsobinManifest::load_from_file(dir.join("root.toml"))
+            .await
+            .unwrap();
+        let expected = IsobinManifest::default()
+            .with_section(CargoManifest::new(
+                Some(true),
+                None,
+                [
+                    (
+                        "cargo-make".to_string(),
+                        CargoInstallDependency::Simple(PartialVersion::from_str("3.0.0").unwrap()),
+                    ),
+                    (
+                        "cargo-binstall".to_string(),
+                        CargoInstallDependency::Simple(PartialVersion::from_str("1.0.0").unwrap()),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_section(LocalManifest::new(
+                [(
+                    "fmt".to_string(),
+                    LocalInstallDependency::Detailed(LocalInstallDependencyDetail::new(
+                        "shared/scripts/fmt.sh".into(),
+                        Some(dir.join("shared/scripts/fmt.sh")),
+                        None,
+                        None,
+                    )),
+                )]
+                .into_iter()
+                .collect(),
+            ));
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn isobin_manifest_include_cache_works() {
+        let dir = current_source_dir!().join("testdata/isobin_manifests/include/nested");
+        let isobin_manifest = IsobinManifest::load_from_file(dir.join("root.toml"))
+            .await
+            .unwrap();
+        let Some(CargoInstallDependency::Detailed(tool)) =
+            isobin_manifest.cargo().dependencies().get("tool")
+        else {
+            panic!("tool should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(&Some(PathBuf::from("../shared/tools/tool")), tool.path());
+        let Some(LocalInstallDependency::Detailed(fmt)) = isobin_manifest
+            .builtin_section::<LocalManifest>(&ProviderKind::Local)
+            .dependencies()
+            .get("fmt")
+        else {
+            panic!("fmt should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(&PathBuf::from("../shared/scripts/fmt.sh"), fmt.path());
+
+        // The cache is reloaded and fixed with the root manifest dir as sync does.
+        let cache_dir = std::env::temp_dir().join(format!("isobin_cache_{}", nanoid::nanoid!()));
+        fs::create_dir_all(&cache_dir).await.unwrap();
+        IsobinManifestCache::save_cache_to_dir(&isobin_manifest, &cache_dir)
+            .await
+            .unwrap();
+        let isobin_manifest_cache = IsobinManifestCache::lenient_load_cache_from_dir(&cache_dir)
+            .await
+            .fix(&dir);
+        fs::remove_dir_all(&cache_dir).await.unwrap();
+        pretty_assertions::assert_eq!(isobin_manifest, isobin_manifest_cache);
+    }
+
+    #[rstest]
+    #[case(&[], &[], &["cargo-binstall", "cargo-make", "fmt"])]
+    #[case(&["ci"], &[], &["cargo-binstall"])]
+    #[case(&["ci", "dev"], &[], &["cargo-binstall", "fmt"])]
+    #[case(&[], &["ci"], &["cargo-make", "fmt"])]
+    #[case(&["dev"], &["ci"], &["fmt"])]
+    #[tokio::test]
+    async fn isobin_manifest_filter_groups_works(
+        #[case] groups: &[&str],
+        #[case] without_groups: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let dir = current_source_dir!();
+        let isobin_manifest =
+            IsobinManifest::load_from_file(dir.join("testdata/isobin_manifests/groups_load.toml"))
+                .await
+                .unwrap();
+        let to_strings = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        let mut actual = isobin_manifest
+            .filter_groups(&to_strings(groups), &to_strings(without_groups))
+            .unwrap()
+            .dependency_names()
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect::<Vec<_>>();
+        actual.sort();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn isobin_manifest_groups_works() {
+        let dir = current_source_dir!();
+        let isobin_manifest =
+            IsobinManifest::load_from_file(dir.join("testdata/isobin_manifests/groups_load.toml"))
+                .await
+                .unwrap();
+        assert!(isobin_manifest
+            .filter_groups(&["nothing".into()], &[])
+            .is_err());
+        let json = serde_json::to_string(&isobin_manifest).unwrap();
+        let actual = serde_json::from_str::<IsobinManifest>(&json)
+            .unwrap()
+            .fix(dir.join("testdata/isobin_manifests").as_path());
+        pretty_assertions::assert_eq!(isobin_manifest, actual);
+    }
+
+    #[tokio::test]
+    async fn isobin_manifest_target_works() {
+        let dir = current_source_dir!();
+        let actual = IsobinManifest::load_from_file(
+            dir.join("testdata/isobin_manifests/platform_load.toml"),
+        )
+        .await
+        .unwrap();
+        let cargo_install_dependencies = if cfg!(unix) {
+            vec![("cargo-make", "3.0.0")]
+        } else {
+            vec![("cargo-make", "4.0.0"), ("cargo-binstall", "1.0.0")]
+        };
+        let expected = IsobinManifest::default().with_section(CargoManifest::new(
+            None,
+            None,
+            cargo_install_dependencies
+                .into_iter()
+                .map(|(name, version)| {
+                    (
+                        name.to_string(),
+                        CargoInstallDependency::Simple(PartialVersion::from_str(version).unwrap()),
+                    )
+                })
+                .collect(),
+        ));
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case(
+        "platform_invalid_dependency.toml",
+        r#"cfg(target_os = "none")"#,
+        "local/fmt"
+    )]
+    #[case(
+        "platform_invalid_cfg.toml",
+        r#"cfg(target_oss = "linux")"#,
+        "target_oss is not a known cfg"
+    )]
+    #[case(
+        "platform_nested_include.toml",
+        "cfg(unix)",
+        "include can not be used in a platform table"
+    )]
+    #[tokio::test]
+    async fn isobin_manifest_target_error_works(
+        #[case] path: &str,
+        #[case] expected_platform: &str,
+        #[case] expected_cause: &str,
+    ) {
+        let dir = current_source_dir!().join("testdata/isobin_manifests");
+        let err = IsobinManifest::load_from_file(dir.join(path))
+            .await
+            .unwrap_err();
+        let Some(IsobinManifestError::MultiValidate(errs)) = err.downcast_ref() else {
+            panic!("unexpected error: {err}");
+        };
+        let [err] = errs.as_slice() else {
+            panic!("unexpected errors: {errs:?}");
+        };
+        let Some(IsobinManifestError::Target { platform, error }) = err.downcast_ref() else {
+            panic!("unexpected error: {err}");
+        };
+        pretty_assertions::assert_eq!(expected_platform, platform);
+        let causes = match error.downcast_ref::<IsobinManifestError>() {
+            Some(IsobinManifestError::MultiValidate(errs)) => {
+                errs.iter().map(|err| err.to_string()).collect()
+            }
+            _ => vec![error.to_string()],
+        };
+        assert!(
+            causes.iter().any(|cause| cause.starts_with(expected_cause)),
+            "{causes:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn isobin_manifest_env_vars_works() {
+        let dir = current_source_dir!().join("testdata/isobin_manifests");
+        let actual = IsobinManifest::load_from_file(dir.join("env_vars_load.toml"))
+            .await
+            .unwrap();
+        let dependencies = actual.cargo().dependencies();
+        let Some(CargoInstallDependency::Detailed(mirror_tool)) = dependencies.get("mirror-tool")
+        else {
+            panic!("mirror-tool should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(
+            &Some("https://github.com/example/mirror-tool.git".to_string()),
+            mirror_tool.git()
+        );
+        let Some(CargoInstallDependency::Detailed(local_tool)) = dependencies.get("local-tool")
+        else {
+            panic!("local-tool should be a detailed dependency");
+        };
+        pretty_assertions::assert_eq!(
+            &Some(dir.join("tools/local-tool")),
+            local_tool.absolute_path()
+        );
+
+        let err = IsobinManifest::load_from_file(dir.join("env_vars_undefined.toml"))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("registry-tool"), "{err}");
+        assert!(err.contains("ISOBIN_TEST_UNDEFINED_REGISTRY"), "{err}");
+    }
+
+    #[rstest]
+    #[case("cycle_a.toml", "The manifests include each other")]
+    #[case("invalid.toml", "In the included manifest")]
+    #[tokio::test]
+    async fn isobin_manifest_include_error_works(#[case] path: &str, #[case] expected: &str) {
+        let dir = current_source_dir!().join("testdata/isobin_manifests/include");
+        let err = IsobinManifest::load_from_file(dir.join(path))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with(expected), "{err}");
+    }
+
     #[rstest]
     #[case(
         ManifestFileExtensions::Toml,
@@ -346,7 +1546,9 @@ mod tests {
         #[case] expected: IsobinManifest,
     ) {
         let path = current_source_dir!().join(path);
-        let actual = IsobinManifest::parse(ft, path).await.unwrap();
+        let actual = IsobinManifest::parse(ft, path, &ProviderRegistry::default())
+            .await
+            .unwrap();
         pretty_assertions::assert_eq!(expected, actual);
     }
 
@@ -390,7 +1592,7 @@ mod tests {
         #[case] expected: SerdeExtError,
     ) {
         let path = current_source_dir!().join(path);
-        let result = IsobinManifest::parse(ft, path).await;
+        let result = IsobinManifest::parse(ft, path, &ProviderRegistry::default()).await;
         assert_error_result!(expected, result);
     }
 
@@ -415,9 +1617,140 @@ mod tests {
     fn tool_manifest(
         cargo_install_dependencies: Vec<(String, CargoInstallDependency)>,
     ) -> IsobinManifest {
-        IsobinManifest {
-            cargo: CargoManifest::new(cargo_install_dependencies.into_iter().collect()),
-        }
+        IsobinManifest::default().with_section(CargoManifest::new(
+            None,
+            None,
+            cargo_install_dependencies.into_iter().collect(),
+        ))
+    }
+
+    #[fixture]
+    fn npm_tool_manifest() -> IsobinManifest {
+        IsobinManifest::default()
+            .with_section(CargoManifest::new(
+                None,
+                None,
+                [(
+                    "cargo-make".to_string(),
+                    CargoInstallDependency::Simple(PartialVersion::from_str("2.0.0").unwrap()),
+                )]
+                .into_iter()
+                .collect(),
+            ))
+            .with_section(NpmManifest::new(
+                [
+                    (
+                        "prettier".to_string(),
+                        NpmInstallDependency::Simple("3.0.0".into()),
+                    ),
+                    (
+                        "markdownlint-cli".to_string(),
+                        NpmInstallDependency::Detailed(NpmInstallDependencyDetail::new(
+                            Some(vec!["markdownlint".into()]),
+                            Some("^0.37.0".into()),
+                            None,
+                            None,
+                            None,
+                        )),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+    }
+
+    #[fixture]
+    fn python_tool_manifest() -> IsobinManifest {
+        IsobinManifest::default().with_section(PythonManifest::new(
+            [
+                (
+                    "ruff".to_string(),
+                    PythonInstallDependency::Simple("0.1.6".into()),
+                ),
+                (
+                    "pre-commit".to_string(),
+                    PythonInstallDependency::Detailed(PythonInstallDependencyDetail::new(
+                        None,
+                        Some("3.5.0".into()),
+                        None,
+                        Some("python3.11".into()),
+                        None,
+                        None,
+                    )),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    #[fixture]
+    fn go_tool_manifest() -> IsobinManifest {
+        IsobinManifest::default().with_section(GoManifest::new(
+            [
+                (
+                    "gofumpt".to_string(),
+                    GoInstallDependency::Simple("mvdan.cc/gofumpt@v0.5.0".into()),
+                ),
+                (
+                    "golangci-lint".to_string(),
+                    GoInstallDependency::Detailed(GoInstallDependencyDetail::new(
+                        Some("github.com/golangci/golangci-lint/cmd/golangci-lint".into()),
+                        Some("v1.55.2".into()),
+                        None,
+                        None,
+                    )),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    fn local_tool_manifest() -> IsobinManifest {
+        IsobinManifest::default()
+            .with_section(LocalManifest::new(
+                [
+                    (
+                        "fmt".to_string(),
+                        LocalInstallDependency::Simple("scripts/fmt.sh".into()),
+                    ),
+                    (
+                        "tools".to_string(),
+                        LocalInstallDependency::Detailed(LocalInstallDependencyDetail::new(
+                            "tools/bin".into(),
+                            None,
+                            Some(vec!["release".into(), "bump".into()]),
+                            Some(LocalLinkMode::Copy),
+                        )),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .fix(&current_source_dir!().join("testdata/isobin_manifests"))
+    }
+
+    fn custom_tool_manifest() -> IsobinManifest {
+        IsobinManifest::default()
+            .with_section(CustomManifest::new(
+                [(
+                    "protoc".to_string(),
+                    CustomInstallDependency::new(
+                        Some("25.1".into()),
+                        CustomCommands::Multiple(vec![
+                            "curl -sSL -o $ISOBIN_PREFIX/protoc.zip https://github.com/protocolbuffers/protobuf/releases/download/v$ISOBIN_VERSION/protoc-$ISOBIN_VERSION-linux-x86_64.zip".into(),
+                            "unzip -q -o $ISOBIN_PREFIX/protoc.zip -d $ISOBIN_PREFIX".into(),
+                        ]),
+                        None,
+                        vec!["protoc".into()],
+                        None,
+                    ),
+                )]
+                .into_iter()
+                .collect(),
+            ))
+        .fix(&current_source_dir!().join("testdata/isobin_manifests"))
     }
 
     #[fixture]
@@ -438,6 +1771,17 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
         );
         cargos.push((
             "comrak".to_string(),
@@ -458,6 +1802,17 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
         );
         cargos.push((
             "cargo-make".to_string(),
@@ -0,0 +1,264 @@
+use anyhow::{anyhow, bail};
+use std::fmt::Display;
+
+use super::*;
+
+/// An editor which splices json source text, keeping everything outside the edited members.
+pub struct JsonDocument {
+    source: String,
+    indent_unit: usize,
+}
+
+struct JsonObject {
+    start: usize,
+    end: usize,
+    members: Vec<JsonMember>,
+}
+
+struct JsonMember {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+impl JsonDocument {
+    pub fn parse(source: &str) -> Result<Self> {
+        let source = if source.trim().is_empty() {
+            "{}\n".to_string()
+        } else {
+            source.to_string()
+        };
+        parse_object(&source, skip_whitespace(&source, 0))?;
+        let indent_unit = detect_indent_unit(source.lines());
+        Ok(Self {
+            source,
+            indent_unit,
+        })
+    }
+
+    pub fn upsert(&mut self, section: &[&str], key: &str, value: &serde_json::Value) -> Result<()> {
+        let mut object = parse_object(&self.source, skip_whitespace(&self.source, 0))?;
+        for (i, name) in section.iter().enumerate() {
+            match object.members.iter().find(|member| &member.key == name) {
+                Some(member) if self.source[member.value_start..].starts_with('{') => {
+                    object = parse_object(&self.source, member.value_start)?;
+                }
+                Some(member) if &self.source[member.value_start..member.value_end] == "null" => {
+                    let (start, end) = (member.value_start, member.value_end);
+                    self.source.replace_range(start..end, "{}");
+                    object = parse_object(&self.source, start)?;
+                }
+                Some(_) => bail!("{name} should be an object to be edited"),
+                None => {
+                    let nested = section[i + 1..].iter().rev().fold(
+                        serde_json::Value::Object(
+                            [(key.to_string(), value.clone())].into_iter().collect(),
+                        ),
+                        |value, name| {
+                            serde_json::Value::Object(
+                                [(name.to_string(), value)].into_iter().collect(),
+                            )
+                        },
+                    );
+                    return self.upsert_member(&object, name, &nested);
+                }
+            }
+        }
+        self.upsert_member(&object, key, value)
+    }
+
+    pub fn remove(&mut self, section: &[&str], key: &str) -> Result<bool> {
+        let mut object = parse_object(&self.source, skip_whitespace(&self.source, 0))?;
+        for name in section.iter() {
+            match object.members.iter().find(|member| &member.key == name) {
+                Some(member) if self.source[member.value_start..].starts_with('{') => {
+                    object = parse_object(&self.source, member.value_start)?;
+                }
+                _ => return Ok(false),
+            }
+        }
+        let position = match object.members.iter().position(|member| member.key == key) {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+        let member = &object.members[position];
+        let range = if object.members.len() == 1 {
+            object.start + 1..object.end - 1
+        } else if position + 1 < object.members.len() {
+            member.key_start..object.members[position + 1].key_start
+        } else {
+            object.members[position - 1].value_end..member.value_end
+        };
+        self.source.replace_range(range, "");
+        Ok(true)
+    }
+
+    fn upsert_member(
+        &mut self,
+        object: &JsonObject,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<()> {
+        if let Some(member) = object.members.iter().find(|member| member.key == key) {
+            let rendered = self.render_value(value, self.line_indent(member.key_start))?;
+            self.source
+                .replace_range(member.value_start..member.value_end, &rendered);
+            return Ok(());
+        }
+        let rendered_key = serde_json::to_string(key)?;
+        if let Some(last) = object.members.last() {
+            let multiline = self.source[object.start..last.key_start].contains('\n');
+            let inserted = if multiline {
+                let indent = self.line_indent(last.key_start);
+                let rendered = self.render_value(value, indent)?;
+                format!(",\n{}{rendered_key}: {rendered}", " ".repeat(indent))
+            } else {
+                format!(", {rendered_key}: {}", serde_json::to_string(value)?)
+            };
+            self.source.insert_str(last.value_end, &inserted);
+        } else {
+            let indent = self.line_indent(object.start);
+            let child_indent = indent + self.indent_unit;
+            let rendered = self.render_value(value, child_indent)?;
+            self.source.replace_range(
+                object.start + 1..object.end - 1,
+                &format!(
+                    "\n{}{rendered_key}: {rendered}\n{}",
+                    " ".repeat(child_indent),
+                    " ".repeat(indent)
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn render_value(&self, value: &serde_json::Value, indent: usize) -> Result<String> {
+        Ok(reindent(
+            &serde_json::to_string_pretty(value)?,
+            indent,
+            self.indent_unit,
+        ))
+    }
+
+    fn line_indent(&self, position: usize) -> usize {
+        let line_start = self.source[..position].rfind('\n').map_or(0, |i| i + 1);
+        let line = &self.source[line_start..position];
+        line.len() - line.trim_start_matches(' ').len()
+    }
+}
+
+impl Display for JsonDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn skip_whitespace(source: &str, mut position: usize) -> usize {
+    let bytes = source.as_bytes();
+    while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+        position += 1;
+    }
+    position
+}
+
+fn parse_object(source: &str, start: usize) -> Result<JsonObject> {
+    let bytes = source.as_bytes();
+    if bytes.get(start) != Some(&b'{') {
+        bail!("expected an object at {start}");
+    }
+    let mut members = vec![];
+    let mut position = skip_whitespace(source, start + 1);
+    if bytes.get(position) == Some(&b'}') {
+        return Ok(JsonObject {
+            start,
+            end: position + 1,
+            members,
+        });
+    }
+    loop {
+        let key_start = position;
+        let key_end = skip_string(source, key_start)?;
+        let key: String = serde_json::from_str(&source[key_start..key_end])?;
+        position = skip_whitespace(source, key_end);
+        if bytes.get(position) != Some(&b':') {
+            bail!("expected ':' at {position}");
+        }
+        let value_start = skip_whitespace(source, position + 1);
+        let value_end = skip_value(source, value_start)?;
+        members.push(JsonMember {
+            key,
+            key_start,
+            value_start,
+            value_end,
+        });
+        position = skip_whitespace(source, value_end);
+        match bytes.get(position) {
+            Some(b',') => position = skip_whitespace(source, position + 1),
+            Some(b'}') => {
+                return Ok(JsonObject {
+                    start,
+                    end: position + 1,
+                    members,
+                })
+            }
+            _ => bail!("expected ',' or '}}' at {position}"),
+        }
+    }
+}
+
+fn skip_string(source: &str, start: usize) -> Result<usize> {
+    let bytes = source.as_bytes();
+    if bytes.get(start) != Some(&b'"') {
+        bail!("expected a string at {start}");
+    }
+    let mut position = start + 1;
+    while position < bytes.len() {
+        match bytes[position] {
+            b'\\' => position += 2,
+            b'"' => return Ok(position + 1),
+            _ => position += 1,
+        }
+    }
+    Err(anyhow!("unterminated string at {start}"))
+}
+
+fn skip_value(source: &str, start: usize) -> Result<usize> {
+    let bytes = source.as_bytes();
+    match bytes.get(start) {
+        Some(b'"') => skip_string(source, start),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0;
+            let mut position = start;
+            while position < bytes.len() {
+                match bytes[position] {
+                    b'"' => {
+                        position = skip_string(source, position)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(position + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                position += 1;
+            }
+            Err(anyhow!("unterminated value at {start}"))
+        }
+        Some(_) => {
+            let mut position = start;
+            while position < bytes.len()
+                && !matches!(bytes[position], b',' | b'}' | b']')
+                && !bytes[position].is_ascii_whitespace()
+            {
+                position += 1;
+            }
+            Ok(position)
+        }
+        None => Err(anyhow!("expected a value at {start}")),
+    }
+}
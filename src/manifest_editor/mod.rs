@@ -0,0 +1,258 @@
+mod json_editor;
+mod toml_editor;
+mod yaml_editor;
+
+#[allow(unused_imports)]
+use super::*;
+use json_editor::JsonDocument;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use toml_editor::TomlDocument;
+use yaml_editor::YamlDocument;
+
+use crate::{
+    manifest::{IsobinManifest, ManifestFileExtensions, DEPENDENCIES_KEY, TARGET_KEY},
+    providers::ProviderKind,
+    utils::io_ext,
+};
+
+/// Edits the dependency entries of an isobin manifest file in place.
+/// Unlike `IsobinManifest::save_to_file`, only the edited entries are rewritten,
+/// so comments, key ordering and formatting of the rest of the document are kept.
+pub struct IsobinManifestEditor {
+    path: PathBuf,
+    document: ManifestDocument,
+}
+
+enum ManifestDocument {
+    Toml(TomlDocument),
+    Yaml(YamlDocument),
+    Json(JsonDocument),
+}
+
+impl IsobinManifestEditor {
+    pub async fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file_extension = IsobinManifest::get_file_extension(path)?;
+        let source = fs::read_to_string(path).await?;
+        let document = match file_extension {
+            ManifestFileExtensions::Toml => ManifestDocument::Toml(TomlDocument::parse(&source)?),
+            ManifestFileExtensions::Yaml => ManifestDocument::Yaml(YamlDocument::parse(&source)),
+            ManifestFileExtensions::Json => ManifestDocument::Json(JsonDocument::parse(&source)?),
+        };
+        Ok(Self {
+            path: path.into(),
+            document,
+        })
+    }
+
+    /// Writes the entry into the section of the provider, or into the one of the platform table
+    /// when `platform` is given.
+    pub fn upsert_dependency(
+        &mut self,
+        platform: Option<&str>,
+        provider_kind: &ProviderKind,
+        name: &str,
+        dependency: &impl serde::Serialize,
+    ) -> Result<()> {
+        let provider = provider_kind.to_string();
+        let section = Self::section(platform, &provider);
+        let value = serde_json::to_value(dependency)?;
+        match &mut self.document {
+            ManifestDocument::Toml(document) => document.upsert(&section, name, &value),
+            ManifestDocument::Yaml(document) => document.upsert(&section, name, &value),
+            ManifestDocument::Json(document) => document.upsert(&section, name, &value),
+        }
+        .map_err(|err| {
+            IsobinManifestEditorError::new_edit(io_ext::path_to_string(&self.path), err).into()
+        })
+    }
+
+    /// Returns whether the section of the provider, or the one of the platform table, had the entry.
+    pub fn remove_dependency(
+        &mut self,
+        platform: Option<&str>,
+        provider_kind: &ProviderKind,
+        name: &str,
+    ) -> Result<bool> {
+        let provider = provider_kind.to_string();
+        let section = Self::section(platform, &provider);
+        match &mut self.document {
+            ManifestDocument::Toml(document) => document.remove(&section, name),
+            ManifestDocument::Yaml(document) => document.remove(&section, name),
+            ManifestDocument::Json(document) => document.remove(&section, name),
+        }
+        .map_err(|err| {
+            IsobinManifestEditorError::new_edit(io_ext::path_to_string(&self.path), err).into()
+        })
+    }
+
+    fn section<'a>(platform: Option<&'a str>, provider: &'a str) -> Vec<&'a str> {
+        platform
+            .map(|platform| vec![TARGET_KEY, platform])
+            .unwrap_or_default()
+            .into_iter()
+            .chain([provider, DEPENDENCIES_KEY])
+            .collect()
+    }
+
+    pub async fn save(&self) -> Result<()> {
+        let source = match &self.document {
+            ManifestDocument::Toml(document) => document.to_string(),
+            ManifestDocument::Yaml(document) => document.to_string(),
+            ManifestDocument::Json(document) => document.to_string(),
+        };
+        fs::write(&self.path, source).await?;
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum IsobinManifestEditorError {
+    #[error("An error occurred in editing the isobin manifest\npath:{path}\nerror:{error}")]
+    Edit {
+        path: String,
+        #[source]
+        error: Error,
+    },
+    #[error("Not found {name} to edit in the isobin manifest\npath:{path}")]
+    NotFoundDependency { path: String, name: String },
+}
+
+/// Detects the indent width used by a document. Falls back to two spaces.
+fn detect_indent_unit<'a>(lines: impl Iterator<Item = &'a str>) -> usize {
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|indent| *indent > 0)
+        .min()
+        .unwrap_or(2)
+}
+
+/// Re-indents text rendered with two space indentation to `indent_unit`,
+/// prefixing every line except the first with `base_indent`.
+fn reindent(rendered: &str, base_indent: usize, indent_unit: usize) -> String {
+    rendered
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let trimmed = line.trim_start_matches(' ');
+            let depth = (line.len() - trimmed.len()) / 2;
+            let indent = if i == 0 { 0 } else { base_indent } + depth * indent_unit;
+            format!("{}{}", " ".repeat(indent), trimmed)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use crate::providers::cargo::CargoInstallDependency;
+
+    fn simple_dependency() -> CargoInstallDependency {
+        serde_json::from_str("\"0.9.1\"").unwrap()
+    }
+
+    fn detailed_dependency() -> CargoInstallDependency {
+        serde_json::from_str(r#"{"version":"1.0","features":["a","b"]}"#).unwrap()
+    }
+
+    #[rstest]
+    #[case(
+        "isobin.toml",
+        include_str!("testdata/upsert_works/case1_toml/given.toml"),
+        include_str!("testdata/upsert_works/case1_toml/expected.toml")
+    )]
+    #[case(
+        "isobin.yaml",
+        include_str!("testdata/upsert_works/case2_yaml/given.yaml"),
+        include_str!("testdata/upsert_works/case2_yaml/expected.yaml")
+    )]
+    #[case(
+        "isobin.json",
+        include_str!("testdata/upsert_works/case3_json/given.json"),
+        include_str!("testdata/upsert_works/case3_json/expected.json")
+    )]
+    #[case(
+        "isobin.toml",
+        "",
+        include_str!("testdata/upsert_works/case4_empty_toml/expected.toml")
+    )]
+    #[case(
+        "isobin.yaml",
+        "",
+        include_str!("testdata/upsert_works/case5_empty_yaml/expected.yaml")
+    )]
+    #[case(
+        "isobin.json",
+        "{}\n",
+        include_str!("testdata/upsert_works/case6_empty_json/expected.json")
+    )]
+    #[tokio::test]
+    async fn upsert_works(#[case] file_name: &str, #[case] given: &str, #[case] expected: &str) {
+        let path = std::env::temp_dir()
+            .join(format!("isobin_editor_upsert_{}", nanoid::nanoid!()))
+            .join(file_name);
+        crate::utils::fs_ext::smart_create_file(&path)
+            .await
+            .unwrap();
+        fs::write(&path, given).await.unwrap();
+        let mut editor = IsobinManifestEditor::load_from_file(&path).await.unwrap();
+        editor
+            .upsert_dependency(
+                None,
+                &ProviderKind::Cargo,
+                "cargo-make",
+                &simple_dependency(),
+            )
+            .unwrap();
+        editor
+            .upsert_dependency(None, &ProviderKind::Cargo, "comrak", &detailed_dependency())
+            .unwrap();
+        editor.save().await.unwrap();
+        let actual = fs::read_to_string(&path).await.unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).await.unwrap();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case(
+        "isobin.toml",
+        include_str!("testdata/upsert_works/case1_toml/given.toml"),
+        include_str!("testdata/remove_works/case1_toml/expected.toml")
+    )]
+    #[case(
+        "isobin.yaml",
+        include_str!("testdata/upsert_works/case2_yaml/given.yaml"),
+        include_str!("testdata/remove_works/case2_yaml/expected.yaml")
+    )]
+    #[case(
+        "isobin.json",
+        include_str!("testdata/upsert_works/case3_json/given.json"),
+        include_str!("testdata/remove_works/case3_json/expected.json")
+    )]
+    #[tokio::test]
+    async fn remove_works(#[case] file_name: &str, #[case] given: &str, #[case] expected: &str) {
+        let path = std::env::temp_dir()
+            .join(format!("isobin_editor_remove_{}", nanoid::nanoid!()))
+            .join(file_name);
+        crate::utils::fs_ext::smart_create_file(&path)
+            .await
+            .unwrap();
+        fs::write(&path, given).await.unwrap();
+        let mut editor = IsobinManifestEditor::load_from_file(&path).await.unwrap();
+        assert!(editor
+            .remove_dependency(None, &ProviderKind::Cargo, "cargo-make")
+            .unwrap());
+        assert!(!editor
+            .remove_dependency(None, &ProviderKind::Cargo, "not-found")
+            .unwrap());
+        editor.save().await.unwrap();
+        let actual = fs::read_to_string(&path).await.unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).await.unwrap();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{anyhow, bail};
+use std::fmt::Display;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+use super::*;
+
+pub struct TomlDocument {
+    document: DocumentMut,
+}
+
+impl TomlDocument {
+    pub fn parse(source: &str) -> Result<Self> {
+        Ok(Self {
+            document: source.parse::<DocumentMut>()?,
+        })
+    }
+
+    pub fn upsert(&mut self, section: &[&str], key: &str, value: &serde_json::Value) -> Result<()> {
+        let table = Self::section_mut(self.document.as_table_mut(), section)?;
+        let new_value = to_toml_value(value)?;
+        match (table.get_mut(key), new_value) {
+            (Some(Item::Table(old_table)), Value::InlineTable(new_table)) => {
+                let old_keys = old_table
+                    .iter()
+                    .map(|(k, _)| k.to_string())
+                    .collect::<Vec<_>>();
+                for old_key in old_keys.iter() {
+                    if !new_table.contains_key(old_key) {
+                        old_table.remove(old_key);
+                    }
+                }
+                for (k, v) in new_table.into_iter() {
+                    match old_table.get_mut(&k) {
+                        Some(Item::Value(old_value)) => replace_value(old_value, v),
+                        _ => {
+                            old_table.insert(&k, Item::Value(v));
+                        }
+                    }
+                }
+            }
+            (Some(Item::Value(old_value)), new_value) => replace_value(old_value, new_value),
+            (_, new_value) => {
+                table.remove(key);
+                table.insert(key, Item::Value(new_value));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, section: &[&str], key: &str) -> Result<bool> {
+        let mut table: &mut dyn TableLike = self.document.as_table_mut();
+        for name in section.iter() {
+            match table
+                .get_mut(name)
+                .and_then(|item| item.as_table_like_mut())
+            {
+                Some(child) => table = child,
+                None => return Ok(false),
+            }
+        }
+        Ok(table.remove(key).is_some())
+    }
+
+    fn section_mut<'a>(
+        mut table: &'a mut dyn TableLike,
+        section: &[&str],
+    ) -> Result<&'a mut dyn TableLike> {
+        for name in section.iter() {
+            if table.get(name).is_none() {
+                let mut child = Table::new();
+                child.set_implicit(true);
+                table.insert(name, Item::Table(child));
+            }
+            table = table
+                .get_mut(name)
+                .and_then(|item| item.as_table_like_mut())
+                .ok_or_else(|| anyhow!("{name} is not a table"))?;
+        }
+        Ok(table)
+    }
+}
+
+impl Display for TomlDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.document.fmt(f)
+    }
+}
+
+fn replace_value(old_value: &mut Value, new_value: Value) {
+    let decor = old_value.decor().clone();
+    *old_value = new_value;
+    *old_value.decor_mut() = decor;
+}
+
+fn to_toml_value(value: &serde_json::Value) -> Result<Value> {
+    match value {
+        serde_json::Value::Null => bail!("toml does not support null values"),
+        serde_json::Value::Bool(b) => Ok((*b).into()),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(i.into())
+            } else {
+                Ok(n.as_f64().unwrap_or_default().into())
+            }
+        }
+        serde_json::Value::String(s) => Ok(s.as_str().into()),
+        serde_json::Value::Array(values) => Ok(Value::Array(
+            values
+                .iter()
+                .map(to_toml_value)
+                .collect::<Result<Array>>()?,
+        )),
+        serde_json::Value::Object(map) => {
+            let mut table = InlineTable::new();
+            for (k, v) in map.iter().filter(|(_, v)| !v.is_null()) {
+                table.insert(k, to_toml_value(v)?);
+            }
+            Ok(Value::InlineTable(table))
+        }
+    }
+}
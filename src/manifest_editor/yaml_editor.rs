@@ -0,0 +1,221 @@
+use anyhow::bail;
+use std::fmt::Display;
+
+use super::*;
+
+/// A line based editor for block style yaml mappings.
+/// Flow style mappings are only supported when they are empty.
+pub struct YamlDocument {
+    lines: Vec<String>,
+    indent_unit: usize,
+    trailing_newline: bool,
+}
+
+struct YamlEntry {
+    line: usize,
+    indent: usize,
+    block_end: usize,
+}
+
+impl YamlDocument {
+    pub fn parse(source: &str) -> Self {
+        let lines = source.lines().map(String::from).collect::<Vec<_>>();
+        let indent_unit = detect_indent_unit(lines.iter().map(|line| line.as_str()));
+        Self {
+            lines,
+            indent_unit,
+            trailing_newline: source.is_empty() || source.ends_with('\n'),
+        }
+    }
+
+    pub fn upsert(&mut self, section: &[&str], key: &str, value: &serde_json::Value) -> Result<()> {
+        let (mut start, mut end, mut indent) = (0, self.lines.len(), None);
+        for name in section.iter() {
+            match self.find_entry(start, end, name) {
+                Some(entry) => {
+                    self.make_block_value(&entry)?;
+                    start = entry.line + 1;
+                    end = entry.block_end;
+                    indent = Some(entry.indent);
+                }
+                None => {
+                    let child_indent = self.child_indent(start, end, indent);
+                    let at = self.content_end(start, end);
+                    self.lines
+                        .insert(at, format!("{}{}:", " ".repeat(child_indent), name));
+                    start = at + 1;
+                    end = at + 1;
+                    indent = Some(child_indent);
+                }
+            }
+        }
+        let child_indent = self.child_indent(start, end, indent);
+        let rendered = self.render_entry(key, value, child_indent)?;
+        match self.find_entry(start, end, key) {
+            Some(entry) => {
+                let rendered = match (entry.block_end - entry.line, rendered.as_slice()) {
+                    (1, [line]) => vec![keep_trailing_comment(&self.lines[entry.line], line)],
+                    _ => rendered,
+                };
+                self.lines.splice(entry.line..entry.block_end, rendered);
+            }
+            None => {
+                let at = self.content_end(start, end);
+                self.lines.splice(at..at, rendered);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, section: &[&str], key: &str) -> Result<bool> {
+        let (mut start, mut end) = (0, self.lines.len());
+        for name in section.iter() {
+            match self.find_entry(start, end, name) {
+                Some(entry) => {
+                    start = entry.line + 1;
+                    end = entry.block_end;
+                }
+                None => return Ok(false),
+            }
+        }
+        match self.find_entry(start, end, key) {
+            Some(entry) => {
+                self.lines.drain(entry.line..entry.block_end);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn find_entry(&self, start: usize, end: usize, key: &str) -> Option<YamlEntry> {
+        let indent = self.first_content_indent(start, end)?;
+        let line = (start..end).find(|i| {
+            let line = &self.lines[*i];
+            !is_skippable(line)
+                && indent_of(line) == indent
+                && parse_key(line.trim()).map(|(k, _)| k) == Some(key.to_string())
+        })?;
+        let block_end = ((line + 1)..end)
+            .find(|i| !is_skippable(&self.lines[*i]) && indent_of(&self.lines[*i]) <= indent)
+            .unwrap_or(end);
+        let block_end = self.content_end(line + 1, block_end);
+        Some(YamlEntry {
+            line,
+            indent,
+            block_end,
+        })
+    }
+
+    /// Converts `key: {}` or `key: ~` into `key:` so that children can be added as a block.
+    fn make_block_value(&mut self, entry: &YamlEntry) -> Result<()> {
+        let line = &self.lines[entry.line];
+        if let Some((key, rest)) = parse_key(line.trim()) {
+            match rest.as_str() {
+                "" => {}
+                "{}" | "~" | "null" => {
+                    self.lines[entry.line] = format!("{}{}:", " ".repeat(entry.indent), key);
+                }
+                _ => bail!("{key} should be a block mapping to be edited"),
+            }
+        }
+        Ok(())
+    }
+
+    fn child_indent(&self, start: usize, end: usize, parent_indent: Option<usize>) -> usize {
+        self.first_content_indent(start, end)
+            .unwrap_or_else(|| parent_indent.map_or(0, |indent| indent + self.indent_unit))
+    }
+
+    fn first_content_indent(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end)
+            .map(|i| &self.lines[i])
+            .find(|line| !is_skippable(line))
+            .map(|line| indent_of(line))
+    }
+
+    /// Returns the index after the last content line in the range.
+    fn content_end(&self, start: usize, end: usize) -> usize {
+        (start..end)
+            .rev()
+            .find(|i| !is_skippable(&self.lines[*i]))
+            .map_or(start, |i| i + 1)
+    }
+
+    fn render_entry(
+        &self,
+        key: &str,
+        value: &serde_json::Value,
+        indent: usize,
+    ) -> Result<Vec<String>> {
+        let mut entry = serde_json::Map::new();
+        entry.insert(key.into(), value.clone());
+        let rendered = serde_yaml::to_string(&entry)?;
+        let rendered = rendered.trim_start_matches("---").trim_start_matches('\n');
+        Ok(reindent(rendered, indent, self.indent_unit)
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                if i == 0 {
+                    format!("{}{}", " ".repeat(indent), line)
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect())
+    }
+}
+
+impl Display for YamlDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lines.join("\n"))?;
+        if self.trailing_newline && !self.lines.is_empty() {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+fn keep_trailing_comment(old_line: &str, new_line: &str) -> String {
+    match old_line.find(" #") {
+        Some(i) if parse_key(old_line.trim()).is_some() => format!("{new_line}{}", &old_line[i..]),
+        _ => new_line.to_string(),
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_skippable(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---"
+}
+
+/// Parses `key: rest` and returns the unquoted key and the value without a trailing comment.
+fn parse_key(content: &str) -> Option<(String, String)> {
+    let (key, rest) =
+        if let Some(quote) = content.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let close = content[1..].find(quote)? + 1;
+            let rest = content[close + 1..].strip_prefix(':')?;
+            (content[1..close].to_string(), rest)
+        } else {
+            let colon = content
+                .match_indices(':')
+                .map(|(i, _)| i)
+                .find(|i| content[i + 1..].is_empty() || content[i + 1..].starts_with(' '))?;
+            (
+                content[..colon].trim_end().to_string(),
+                &content[colon + 1..],
+            )
+        };
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let rest = rest.trim();
+    let rest = if rest.starts_with('#') {
+        ""
+    } else {
+        rest.split(" #").next().unwrap_or("").trim()
+    };
+    Some((key, rest.to_string()))
+}
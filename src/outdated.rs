@@ -0,0 +1,290 @@
+use std::path::PathBuf;
+
+use cargo::util::PartialVersion;
+use semver::Version;
+use serde_derive::Serialize;
+
+use crate::{
+    flex_eprintln, join_futures,
+    lock::IsobinLock,
+    manifest::IsobinManifest,
+    paths::{
+        isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+        workspace::{Workspace, WorkspaceProvider},
+    },
+    providers::{
+        cargo::{CargoInstallDependency, CargoInstallDependencyDetail, CratesInfo, RegistryIndex},
+        ProviderKind,
+    },
+    Error, Result,
+};
+
+#[derive(Default)]
+pub struct OutdatedService {
+    workspace_provider: WorkspaceProvider,
+}
+
+impl OutdatedService {
+    pub async fn outdated(
+        &self,
+        outdated_service_option: OutdatedServiceOption,
+    ) -> Result<Vec<OutdatedItem>> {
+        let outdated_service_option = outdated_service_option.fix().await?;
+        let isobin_manifest =
+            IsobinManifest::load_from_file(outdated_service_option.isobin_manifest_path()).await?;
+        let isobin_manifest_dir =
+            isobin_manifest_dir(outdated_service_option.isobin_manifest_path())?;
+        let workspace = self
+            .workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(isobin_manifest_dir)
+            .await?;
+
+        let outdated_items = Self::make_outdated_items(&isobin_manifest, &workspace).await?;
+        let isobin_lock = IsobinLock::load_from_dir(isobin_manifest_dir).await?;
+        if outdated_items.iter().all(|outdated_item| {
+            outdated_item.is_up_to_date(
+                isobin_lock
+                    .cargo()
+                    .dependencies()
+                    .get(outdated_item.name())
+                    .map(|locked_dependency| locked_dependency.version().as_str()),
+            )
+        }) {
+            flex_eprintln!(
+                outdated_service_option.quiet,
+                "The dependencies are up to date."
+            );
+        }
+        Ok(outdated_items)
+    }
+
+    pub async fn make_outdated_items(
+        isobin_manifest: &IsobinManifest,
+        workspace: &Workspace,
+    ) -> Result<Vec<OutdatedItem>> {
+        let mut outdated_items = join_futures!(isobin_manifest
+            .cargo()
+            .dependencies()
+            .clone()
+            .into_iter()
+            .filter_map(|(name, dependency)| {
+                let dependency = match dependency {
+                    CargoInstallDependency::Simple(version) => {
+                        CargoInstallDependencyDetail::from_version(version)
+                    }
+                    CargoInstallDependency::Detailed(dependency) => dependency,
+                };
+                // Only the dependencies from a registry have newer versions to report.
+                if dependency.path().is_some() || dependency.git().is_some() {
+                    return None;
+                }
+                let requirement = dependency.version().clone()?;
+                let workspace = workspace.clone();
+                Some(async move {
+                    Self::make_cargo_outdated_item(name, requirement, dependency, workspace).await
+                })
+            }))
+        .await
+        .map_err(OutdatedServiceError::MultiOutdated)?;
+        outdated_items.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(outdated_items)
+    }
+
+    async fn make_cargo_outdated_item(
+        name: String,
+        requirement: PartialVersion,
+        dependency: CargoInstallDependencyDetail,
+        workspace: Workspace,
+    ) -> Result<OutdatedItem> {
+        let registry_index = RegistryIndex::from_dependency(&dependency)?;
+        let versions = registry_index.versions(&name).await?;
+        let installed = CratesInfo::lenient_load_from_workspace(&workspace, &name)
+            .await?
+            .and_then(|crates_info| {
+                crates_info
+                    .find_package(&name)
+                    .map(|package| package.version().to_string())
+            });
+        Ok(OutdatedItem::from_versions(
+            ProviderKind::Cargo,
+            name,
+            &requirement,
+            installed,
+            &versions,
+        ))
+    }
+}
+
+#[derive(Serialize, Getters, new, Debug, PartialEq)]
+pub struct OutdatedItem {
+    provider: ProviderKind,
+    name: String,
+    requirement: String,
+    installed: Option<String>,
+    compatible: Option<String>,
+    latest: Option<String>,
+}
+
+impl OutdatedItem {
+    fn from_versions(
+        provider: ProviderKind,
+        name: String,
+        requirement: &PartialVersion,
+        installed: Option<String>,
+        versions: &[Version],
+    ) -> Self {
+        // A full version is installed as it is, like `cargo install --version`.
+        let compatible = match requirement.version() {
+            Some(exact) => versions.iter().find(|version| **version == exact),
+            None => versions
+                .iter()
+                .filter(|version| requirement.caret_req().matches(version))
+                .max(),
+        };
+        // Pre-releases are only reported when the requirement opts into them.
+        let latest = versions
+            .iter()
+            .filter(|version| version.pre.is_empty() || requirement.pre.is_some())
+            .max();
+        Self::new(
+            provider,
+            name,
+            requirement.to_string(),
+            installed,
+            compatible.map(|version| version.to_string()),
+            latest.map(|version| version.to_string()),
+        )
+    }
+
+    /// Whether the installed version, or the `locked` one when it is not installed, is the latest.
+    pub fn is_up_to_date(&self, locked: Option<&str>) -> bool {
+        let Some(latest) = &self.latest else {
+            return true;
+        };
+        self.installed
+            .as_deref()
+            .or(locked)
+            .and_then(|current| Version::parse(current).ok())
+            .zip(Version::parse(latest).ok())
+            .is_some_and(|(current, latest)| current >= latest)
+    }
+}
+
+#[derive(Getters)]
+pub struct OutdatedServiceOptionBase<P> {
+    quiet: bool,
+    isobin_manifest_path: P,
+}
+
+pub type OutdatedServiceOption = OutdatedServiceOptionBase<Option<PathBuf>>;
+type FixedOutdatedServiceOption = OutdatedServiceOptionBase<PathBuf>;
+
+impl OutdatedServiceOption {
+    async fn fix(self) -> Result<FixedOutdatedServiceOption> {
+        let isobin_manifest_path =
+            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+        Ok(FixedOutdatedServiceOption {
+            quiet: self.quiet,
+            isobin_manifest_path,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct OutdatedServiceOptionBuilder {
+    quiet: bool,
+    isobin_manifest_path: Option<PathBuf>,
+}
+
+impl OutdatedServiceOptionBuilder {
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+    pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
+        self.isobin_manifest_path = Some(isobin_manifest_path);
+        self
+    }
+    pub fn build(self) -> OutdatedServiceOption {
+        OutdatedServiceOption {
+            quiet: self.quiet,
+            isobin_manifest_path: self.isobin_manifest_path,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OutdatedServiceError {
+    #[error("{0:#?}")]
+    MultiOutdated(Vec<Error>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::str::FromStr;
+
+    fn versions() -> Vec<Version> {
+        ["0.36.0", "0.37.0", "0.37.4", "1.0.0-alpha.1"]
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect()
+    }
+
+    #[rstest]
+    #[case("0.36", Some("0.36.0"), Some("0.36.0"), Some("0.37.4"))]
+    #[case("0.37", Some("0.37.0"), Some("0.37.4"), Some("0.37.4"))]
+    #[case("0.37.0", Some("0.37.0"), Some("0.37.0"), Some("0.37.4"))]
+    #[case("0.37.4", None, Some("0.37.4"), Some("0.37.4"))]
+    #[case("0.37.2", None, None, Some("0.37.4"))]
+    #[case("0.38", None, None, Some("0.37.4"))]
+    #[case("1.0.0-alpha.1", None, Some("1.0.0-alpha.1"), Some("1.0.0-alpha.1"))]
+    fn from_versions_works(
+        #[case] requirement: &str,
+        #[case] installed: Option<&str>,
+        #[case] compatible: Option<&str>,
+        #[case] latest: Option<&str>,
+    ) {
+        let requirement = PartialVersion::from_str(requirement).unwrap();
+        let expected = OutdatedItem::new(
+            ProviderKind::Cargo,
+            "cargo-make".into(),
+            requirement.to_string(),
+            installed.map(String::from),
+            compatible.map(String::from),
+            latest.map(String::from),
+        );
+        let actual = OutdatedItem::from_versions(
+            ProviderKind::Cargo,
+            "cargo-make".into(),
+            &requirement,
+            installed.map(String::from),
+            &versions(),
+        );
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case("0.37", Some("0.37.4"), None, true)]
+    #[case("0.37", Some("0.37.0"), None, false)]
+    #[case("0.37", None, Some("0.37.4"), true)]
+    #[case("0.37", Some("0.37.0"), Some("0.37.4"), false)]
+    #[case("0.37", None, None, false)]
+    #[case("0", Some("0.37.4"), None, true)]
+    fn is_up_to_date_works(
+        #[case] requirement: &str,
+        #[case] installed: Option<&str>,
+        #[case] locked: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let outdated_item = OutdatedItem::from_versions(
+            ProviderKind::Cargo,
+            "cargo-make".into(),
+            &PartialVersion::from_str(requirement).unwrap(),
+            installed.map(String::from),
+            &versions(),
+        );
+        pretty_assertions::assert_eq!(expected, outdated_item.is_up_to_date(locked));
+    }
+}
@@ -1,6 +1,8 @@
 use tokio::fs;
 
 use super::*;
+use crate::utils::fs_ext::smart_create_file;
+use project::Project;
 use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
@@ -66,6 +68,39 @@ pub async fn search_isobin_manifest_path(current_dir: impl AsRef<Path>) -> Resul
 }
 pub const BASE_FILE_NAME: &str = "isobin";
 
+/// The per-user manifest in the config dir, whose tools are available in every project.
+pub fn global_isobin_manifest_path() -> PathBuf {
+    global_isobin_manifest_path_in(Project::default().config_dir())
+}
+
+pub(crate) fn global_isobin_manifest_path_in(config_dir: &Path) -> PathBuf {
+    config_dir.join(BASE_FILE_NAME.to_string() + ".toml")
+}
+
+/// Creates an empty global manifest on the first use, so that tools can be added right away.
+pub async fn prepare_global_isobin_manifest_path() -> Result<PathBuf> {
+    let global_isobin_manifest_path = global_isobin_manifest_path();
+    if !global_isobin_manifest_path.exists() {
+        smart_create_file(&global_isobin_manifest_path).await?;
+    }
+    Ok(global_isobin_manifest_path)
+}
+
+/// Returns the global manifest for the commands which only read it, without creating it.
+pub fn existing_global_isobin_manifest_path() -> Result<PathBuf> {
+    let global_isobin_manifest_path = global_isobin_manifest_path();
+    if global_isobin_manifest_path.exists() {
+        Ok(global_isobin_manifest_path)
+    } else {
+        Err(
+            IsobinManifestPathError::new_not_found_global_isobin_manifest(
+                global_isobin_manifest_path.to_string_lossy().into(),
+            )
+            .into(),
+        )
+    }
+}
+
 pub fn make_isobin_manifest_paths(dir: impl AsRef<Path>) -> Vec<PathBuf> {
     let base_name = BASE_FILE_NAME;
     let allow_isobin_manifest_extensions = ["toml", "yaml", "yml", "json"];
@@ -83,4 +118,8 @@ pub enum IsobinManifestPathError {
     Conflict(Vec<String>),
     #[error("Not found isobin config file")]
     NotFoundIsobinManifest,
+    #[error(
+        "No global isobin manifest at {path}. Add or install a tool with --global to create it"
+    )]
+    NotFoundGlobalIsobinManifest { path: String },
 }
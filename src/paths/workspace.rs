@@ -1,4 +1,5 @@
-use crate::utils::serde_ext::Json;
+use crate::utils::{fs_ext::create_dir_if_not_exists, serde_ext::Json};
+use isobin_manifest::global_isobin_manifest_path_in;
 
 use super::*;
 use project::Project;
@@ -38,16 +39,26 @@ impl Workspace {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, new)]
 pub struct WorkspaceProvider {
     project: Project,
 }
 
 impl WorkspaceProvider {
+    const GLOBAL_WORKSPACE_ID: &'static str = "global";
+
     pub async fn base_unique_workspace_dir_from_isobin_manifest_dir(
         &self,
         isobin_manifest_dir: impl AsRef<Path>,
     ) -> Result<Workspace> {
+        if self
+            .is_global_isobin_manifest_dir(&isobin_manifest_dir)
+            .await
+        {
+            // The workspace is moved into the dir, which is otherwise created by saving the map.
+            create_dir_if_not_exists(self.project.data_local_dir()).await?;
+            return Ok(self.global_workspace());
+        }
         let mut workspace_path_map =
             WorkspacePathMap::parse_from_dir(self.project.data_local_dir()).await?;
         let id = if let Some(id) = workspace_path_map
@@ -73,6 +84,30 @@ impl WorkspaceProvider {
             unique_cache_dir,
         ))
     }
+    /// The workspace of the global manifest, which has a fixed id instead of an entry in the map.
+    pub fn global_workspace(&self) -> Workspace {
+        Workspace::new(
+            Self::GLOBAL_WORKSPACE_ID.into(),
+            self.project
+                .data_local_dir()
+                .join(Self::GLOBAL_WORKSPACE_ID),
+            self.project.cache_dir().join(Self::GLOBAL_WORKSPACE_ID),
+        )
+    }
+
+    /// The global manifest in the config dir of the project.
+    pub fn global_isobin_manifest_path(&self) -> PathBuf {
+        global_isobin_manifest_path_in(self.project.config_dir())
+    }
+
+    async fn is_global_isobin_manifest_dir(&self, isobin_manifest_dir: impl AsRef<Path>) -> bool {
+        let isobin_manifest_dir = isobin_manifest_dir.as_ref();
+        isobin_manifest_dir == self.project.config_dir()
+            || tokio::fs::canonicalize(self.project.config_dir())
+                .await
+                .is_ok_and(|config_dir| config_dir == isobin_manifest_dir)
+    }
+
     pub async fn remove_isobin_manifest_dir_from_workspace_map(
         &self,
         isobin_manifest_dir: impl AsRef<Path>,
@@ -135,4 +170,27 @@ mod tests {
         let actual = Workspace::new(id.into(), base_unique_workspace_dir, unique_cache_dir);
         pretty_assertions::assert_eq!(expected, actual);
     }
+
+    #[tokio::test]
+    async fn global_workspace_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_workspace_{}", nanoid!()));
+        let workspace_provider = WorkspaceProvider {
+            project: Project::new(dir.join("config"), dir.join("cache"), dir.join("data")),
+        };
+        let global_workspace = workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(dir.join("config"))
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(workspace_provider.global_workspace(), global_workspace);
+        pretty_assertions::assert_eq!(
+            &dir.join("data").join("global"),
+            global_workspace.base_dir()
+        );
+        let project_workspace = workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(dir.join("project"))
+            .await
+            .unwrap();
+        assert_ne!(global_workspace.id(), project_workspace.id());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
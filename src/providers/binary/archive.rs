@@ -0,0 +1,142 @@
+use std::{
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::Result;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    #[serde(rename = "tar.xz")]
+    TarXz,
+    #[serde(rename = "zip")]
+    Zip,
+    /// The download is the executable itself.
+    #[serde(rename = "binary")]
+    Binary,
+}
+
+impl ArchiveFormat {
+    pub fn from_url(url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+            Self::TarGz
+        } else if path.ends_with(".tar.xz") || path.ends_with(".txz") {
+            Self::TarXz
+        } else if path.ends_with(".zip") {
+            Self::Zip
+        } else {
+            Self::Binary
+        }
+    }
+
+    /// Extracts the archive into the dir. A plain binary is written to `binary_file_name` in the dir.
+    pub async fn extract(
+        &self,
+        content: Vec<u8>,
+        dir: impl AsRef<Path>,
+        binary_file_name: &str,
+    ) -> Result<()> {
+        let format = self.clone();
+        let dir = dir.as_ref().to_path_buf();
+        let binary_path = dir.join(binary_file_name);
+        tokio::task::spawn_blocking(move || format.extract_blocking(content, &dir, binary_path))
+            .await?
+    }
+
+    fn extract_blocking(self, content: Vec<u8>, dir: &Path, binary_path: PathBuf) -> Result<()> {
+        let content = Cursor::new(content);
+        match self {
+            Self::TarGz => unpack_tar(flate2::read::GzDecoder::new(content), dir),
+            Self::TarXz => unpack_tar(xz2::read::XzDecoder::new(content), dir),
+            Self::Zip => Ok(zip::ZipArchive::new(content)?.extract(dir)?),
+            Self::Binary => Ok(std::fs::write(binary_path, content.into_inner())?),
+        }
+    }
+}
+
+fn unpack_tar(reader: impl Read, dir: &Path) -> Result<()> {
+    // Entries which would be written outside of the dir are skipped by unpack.
+    Ok(tar::Archive::new(reader).unpack(dir)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io::Write;
+
+    #[rstest]
+    #[case(
+        "https://example.com/rg-14.1.0-x86_64-unknown-linux-musl.tar.gz",
+        ArchiveFormat::TarGz
+    )]
+    #[case("https://example.com/a.tgz?raw=true", ArchiveFormat::TarGz)]
+    #[case("https://example.com/a.tar.xz", ArchiveFormat::TarXz)]
+    #[case("file:///tmp/a.zip", ArchiveFormat::Zip)]
+    #[case("https://example.com/jq-linux-amd64", ArchiveFormat::Binary)]
+    fn from_url_works(#[case] url: &str, #[case] expected: ArchiveFormat) {
+        pretty_assertions::assert_eq!(expected, ArchiveFormat::from_url(url));
+    }
+
+    fn tar_gz(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder =
+            tar::Builder::new(flate2::write::GzEncoder::new(vec![], Default::default()));
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+        for (path, content) in files {
+            writer
+                .start_file(*path, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[rstest]
+    #[case(ArchiveFormat::TarGz, tar_gz(&[("tool-1.0/tool", "bin"), ("tool-1.0/README", "doc")]), vec!["tool-1.0/README", "tool-1.0/tool"])]
+    #[case(ArchiveFormat::Zip, zip(&[("tool.exe", "bin")]), vec!["tool.exe"])]
+    #[case(ArchiveFormat::Binary, b"bin".to_vec(), vec!["tool"])]
+    #[tokio::test]
+    async fn extract_works(
+        #[case] format: ArchiveFormat,
+        #[case] content: Vec<u8>,
+        #[case] expected: Vec<&str>,
+    ) {
+        let dir = std::env::temp_dir().join(nanoid::nanoid!());
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let result = format.extract(content, &dir, "tool").await;
+        let actual = ignore::WalkBuilder::new(&dir)
+            .standard_filters(false)
+            .build()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .map(|entry| entry.path().strip_prefix(&dir).unwrap().to_path_buf())
+            .collect::<std::collections::BTreeSet<_>>();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        result.unwrap();
+        pretty_assertions::assert_eq!(
+            expected
+                .into_iter()
+                .map(PathBuf::from)
+                .collect::<std::collections::BTreeSet<_>>(),
+            actual
+        );
+    }
+}
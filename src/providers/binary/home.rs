@@ -0,0 +1,20 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[derive(Getters, Clone)]
+pub struct BinaryWorkspace {
+    binary_home_dir: PathBuf,
+}
+
+impl BinaryWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let binary_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        Self { binary_home_dir }
+    }
+
+    pub fn install_dir(&self, name: &str) -> PathBuf {
+        self.binary_home_dir.join(name)
+    }
+}
@@ -0,0 +1,350 @@
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::{
+        download::download,
+        fs_ext::{make_hard_link, set_executable},
+    },
+};
+
+use super::*;
+
+pub struct BinaryInstallerFactory {
+    binary_workspace: BinaryWorkspace,
+    workspace: Workspace,
+}
+
+impl BinaryInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            binary_workspace: BinaryWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for BinaryInstallerFactory {
+    type InstallTarget = BinaryTargetDependency;
+    type CoreInstaller = BinaryCoreInstaller;
+    type BinPathInstaller = BinaryBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        Ok(BinaryCoreInstaller::new(self.binary_workspace.clone()))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(BinaryBinPathInstaller::new(
+            self.binary_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+/// The values which replace the placeholders in the URL and the bins.
+#[derive(new)]
+struct TemplateValues<'a> {
+    version: &'a str,
+    target: &'a str,
+    os: &'a str,
+    arch: &'a str,
+}
+
+impl TemplateValues<'_> {
+    fn expand(&self, template: &str) -> String {
+        template
+            .replace(VERSION_PLACEHOLDER, self.version)
+            .replace(TARGET_PLACEHOLDER, self.target)
+            .replace(OS_PLACEHOLDER, self.os)
+            .replace(ARCH_PLACEHOLDER, self.arch)
+    }
+}
+
+/// Returns the target triple of the running platform.
+pub fn current_target() -> String {
+    let arch = std::env::consts::ARCH;
+    match std::env::consts::OS {
+        "linux" if cfg!(target_env = "musl") => format!("{arch}-unknown-linux-musl"),
+        "linux" => format!("{arch}-unknown-linux-gnu"),
+        "macos" => format!("{arch}-apple-darwin"),
+        "windows" if cfg!(target_env = "gnu") => format!("{arch}-pc-windows-gnu"),
+        "windows" => format!("{arch}-pc-windows-msvc"),
+        os => format!("{arch}-unknown-{os}"),
+    }
+}
+
+fn expand_for_current_target(dependency: &BinaryInstallDependency, template: &str) -> String {
+    let target = current_target();
+    TemplateValues::new(
+        dependency.version(),
+        &target,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+    .expand(template)
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum BinaryInstallError {
+    #[error("no sha256 is given for {target}")]
+    NotFoundSha256 { target: String },
+
+    #[error("sha256 of {url} does not match.\nexpected: {expected}\nactual:   {actual}")]
+    MismatchSha256 {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("{bin} is not found in the download")]
+    NotFoundBin { bin: String },
+}
+
+#[derive(new, Clone)]
+pub struct BinaryCoreInstaller {
+    binary_workspace: BinaryWorkspace,
+}
+
+impl BinaryCoreInstaller {
+    fn verify_sha256(url: &str, content: &[u8], sha256: &BinarySha256, target: &str) -> Result<()> {
+        let expected = sha256
+            .for_target(target)
+            .ok_or_else(|| BinaryInstallError::new_not_found_sha256(target.into()))?;
+        let actual = format!("{:x}", Sha256::digest(content));
+        if actual.eq_ignore_ascii_case(expected) {
+            Ok(())
+        } else {
+            Err(BinaryInstallError::new_mismatch_sha256(url.into(), expected.into(), actual).into())
+        }
+    }
+
+    async fn install_core(&self, target: &BinaryTargetDependency) -> Result<()> {
+        let dependency = target.install_dependency();
+        let install_dir = self.binary_workspace.install_dir(target.name());
+        // The dir only holds the target download, so a reinstallation starts from scratch.
+        if install_dir.exists() {
+            fs::remove_dir_all(&install_dir).await?;
+        }
+        fs::create_dir_all(&install_dir).await?;
+        let url = expand_for_current_target(dependency, dependency.url());
+        let content = download(&url).await?;
+        Self::verify_sha256(&url, &content, dependency.sha256(), &current_target())?;
+        dependency
+            .archive_format()
+            .extract(content, &install_dir, &target.binary_file_name())
+            .await?;
+        for (bin_name, bin_path) in bin_entries(&self.binary_workspace, target) {
+            if !bin_path.is_file() {
+                Err(BinaryInstallError::new_not_found_bin(bin_name))?;
+            }
+            set_executable(&bin_path).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl providers::CoreInstaller for BinaryCoreInstaller {
+    type InstallTarget = BinaryTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        self.install_core(target).await.map_err(|err| {
+            InstallServiceError::new_install(
+                ProviderKind::Binary,
+                target.name().into(),
+                err.to_string(),
+                err,
+            )
+            .into()
+        })
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let install_dir = self.binary_workspace.install_dir(target.name());
+        if install_dir.exists() {
+            fs::remove_dir_all(&install_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct BinaryTargetDependency {
+    name: String,
+    install_dependency: BinaryInstallDependency,
+    mode: TargetMode,
+}
+
+impl BinaryTargetDependency {
+    /// The file name of a plain binary download.
+    fn binary_file_name(&self) -> String {
+        self.install_dependency()
+            .bins()
+            .as_ref()
+            .and_then(|bins| bins.first())
+            .map(|bin| expand_for_current_target(self.install_dependency(), bin))
+            .unwrap_or_else(|| self.name.clone())
+    }
+}
+
+impl providers::TargetDependency for BinaryTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Binary
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        self.install_dependency().version().clone()
+    }
+}
+
+/// Returns the bins of the target with their paths in the install dir.
+fn bin_entries(
+    binary_workspace: &BinaryWorkspace,
+    target: &BinaryTargetDependency,
+) -> Vec<(String, PathBuf)> {
+    let install_dir = binary_workspace.install_dir(target.name());
+    let bins = match target.install_dependency().archive_format() {
+        ArchiveFormat::Binary => vec![target.binary_file_name()],
+        _ => target
+            .install_dependency()
+            .bins()
+            .iter()
+            .flatten()
+            .map(|bin| expand_for_current_target(target.install_dependency(), bin))
+            .collect(),
+    };
+    bins.into_iter()
+        .map(|bin| {
+            let bin_path = install_dir.join(&bin);
+            let bin_name = bin_path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().to_string())
+                .unwrap_or(bin);
+            (bin_name, bin_path)
+        })
+        .collect()
+}
+
+#[derive(new, Clone)]
+pub struct BinaryBinPathInstaller {
+    binary_workspace: BinaryWorkspace,
+    workspace: Workspace,
+}
+
+#[async_trait]
+impl BinPathInstaller for BinaryBinPathInstaller {
+    type InstallTarget = BinaryTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        Ok(bin_entries(&self.binary_workspace, target)
+            .into_iter()
+            .filter(|(_, bin_path)| bin_path.exists())
+            .map(|(bin_name, _)| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(target.provider_kind(), target.name().to_string(), bin_name),
+                )
+            })
+            .collect())
+    }
+
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, bin_path) in bin_entries(&self.binary_workspace, target).iter() {
+            make_hard_link(bin_path, self.workspace.bin_dir().join(bin_name)).await?;
+        }
+        Ok(())
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, _) in bin_entries(&self.binary_workspace, target).iter() {
+            let workspace_bin_path = self.workspace.bin_dir().join(bin_name);
+            if workspace_bin_path.exists() {
+                fs::remove_file(workspace_bin_path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        "https://github.com/BurntSushi/ripgrep/releases/download/{version}/ripgrep-{version}-{target}.tar.gz",
+        "https://github.com/BurntSushi/ripgrep/releases/download/14.1.0/ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz"
+    )]
+    #[case(
+        "https://example.com/tool_{os}_{arch}",
+        "https://example.com/tool_linux_x86_64"
+    )]
+    #[case(
+        "ripgrep-{version}-{target}/rg",
+        "ripgrep-14.1.0-x86_64-unknown-linux-musl/rg"
+    )]
+    fn expand_works(#[case] template: &str, #[case] expected: &str) {
+        let values = TemplateValues::new("14.1.0", "x86_64-unknown-linux-musl", "linux", "x86_64");
+        pretty_assertions::assert_eq!(expected, values.expand(template));
+    }
+
+    #[rstest]
+    #[case(
+        BinarySha256::Single(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".into()
+        ),
+        "x86_64-unknown-linux-gnu",
+        true
+    )]
+    #[case(
+        BinarySha256::Single(
+            "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824".into()
+        ),
+        "x86_64-unknown-linux-gnu",
+        true
+    )]
+    #[case(
+        BinarySha256::Single(
+            "0000000000000000000000000000000000000000000000000000000000000000".into()
+        ),
+        "x86_64-unknown-linux-gnu",
+        false
+    )]
+    #[case(
+        BinarySha256::PerTarget(
+            [(
+                "aarch64-apple-darwin".to_string(),
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_string()
+            )]
+            .into_iter()
+            .collect()
+        ),
+        "x86_64-unknown-linux-gnu",
+        false
+    )]
+    fn verify_sha256_works(
+        #[case] sha256: BinarySha256,
+        #[case] target: &str,
+        #[case] expected: bool,
+    ) {
+        pretty_assertions::assert_eq!(
+            expected,
+            BinaryCoreInstaller::verify_sha256("file:///hello", b"hello", &sha256, target).is_ok()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    Result,
+};
+
+use super::ArchiveFormat;
+
+pub const VERSION_PLACEHOLDER: &str = "{version}";
+pub const TARGET_PLACEHOLDER: &str = "{target}";
+pub const OS_PLACEHOLDER: &str = "{os}";
+pub const ARCH_PLACEHOLDER: &str = "{arch}";
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct BinaryManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, BinaryInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, BinaryInstallDependency>,
+}
+
+impl Manifest for BinaryManifest {
+    type Dependency = BinaryInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Binary
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, new, Deserialize, Getters)]
+pub struct BinaryInstallDependency {
+    version: String,
+    /// The download URL, which may contain `{version}`, `{target}`, `{os}` and `{arch}`.
+    url: String,
+    sha256: BinarySha256,
+    /// Inferred from the extension of the URL when omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<ArchiveFormat>,
+    /// The paths of the bins in the archive, which may contain the same placeholders as the URL.
+    /// A plain binary is exposed by the dependency name unless a bin name is given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bins: Option<Vec<String>>,
+}
+
+/// The checksum of the download, or the checksums keyed by target triple.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BinarySha256 {
+    Single(String),
+    PerTarget(BTreeMap<String, String>),
+}
+
+impl BinarySha256 {
+    pub fn for_target(&self, target: &str) -> Option<&str> {
+        match self {
+            Self::Single(sha256) => Some(sha256),
+            Self::PerTarget(sha256s) => sha256s.get(target).map(|sha256| sha256.as_str()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let sha256s = match self {
+            Self::Single(sha256) => vec![sha256],
+            Self::PerTarget(sha256s) if sha256s.is_empty() => {
+                return Err(anyhow!("binary dependency sha256 should not be empty."))
+            }
+            Self::PerTarget(sha256s) => sha256s.values().collect(),
+        };
+        for sha256 in sha256s {
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(anyhow!(
+                    "binary dependency sha256 should be 64 hex digits: {sha256}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ManifestDependency for BinaryInstallDependency {
+    fn validate(&self) -> Result<()> {
+        if self.version.is_empty() {
+            return Err(anyhow!("binary dependency version should not be empty."));
+        }
+        if self.url.is_empty() {
+            return Err(anyhow!("binary dependency url should not be empty."));
+        }
+        self.sha256.validate()?;
+        let bin_count = self.bins.as_ref().map(|bins| bins.len()).unwrap_or(0);
+        match self.archive_format() {
+            ArchiveFormat::Binary if bin_count > 1 => Err(anyhow!(
+                "binary dependency of a plain binary can have only one bin."
+            )),
+            ArchiveFormat::Binary => Ok(()),
+            _ if bin_count == 0 => {
+                Err(anyhow!("binary dependency of an archive should have bins."))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl BinaryInstallDependency {
+    pub fn archive_format(&self) -> ArchiveFormat {
+        self.format
+            .clone()
+            .unwrap_or_else(|| ArchiveFormat::from_url(&self.url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const SHA256: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[rstest]
+    #[case(
+        format!(r#"{{"version":"14.1.0","url":"https://example.com/rg-{{version}}.tar.gz","sha256":"{SHA256}","bins":["rg"]}}"#),
+        true
+    )]
+    #[case(
+        format!(r#"{{"version":"14.1.0","url":"https://example.com/rg-{{version}}.tar.gz","sha256":"{SHA256}"}}"#),
+        false
+    )]
+    #[case(
+        format!(r#"{{"version":"1.0.0","url":"https://example.com/jq","sha256":{{"x86_64-unknown-linux-gnu":"{SHA256}"}}}}"#),
+        true
+    )]
+    #[case(
+        r#"{"version":"1.0.0","url":"https://example.com/jq","sha256":"abc"}"#.to_string(),
+        false
+    )]
+    #[case(
+        format!(r#"{{"version":"1.0.0","url":"https://example.com/jq","sha256":"{SHA256}","bins":["jq","jq2"]}}"#),
+        false
+    )]
+    fn validate_works(#[case] dependency: String, #[case] expected: bool) {
+        let dependency: BinaryInstallDependency = serde_json::from_str(&dependency).unwrap();
+        pretty_assertions::assert_eq!(expected, dependency.validate().is_ok());
+    }
+}
@@ -0,0 +1,15 @@
+mod archive;
+mod home;
+mod installer;
+mod manifest;
+mod provider;
+
+#[allow(unused_imports)]
+use super::*;
+pub use archive::*;
+use home::*;
+pub use installer::*;
+pub use manifest::*;
+pub use provider::*;
+
+pub const PROVIDER_NAME: &str = "binary";
@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct BinaryProvider;
+
+#[async_trait]
+impl Provider for BinaryProvider {
+    type Manifest = BinaryManifest;
+    type InstallerFactory = BinaryInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Binary
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> BinaryInstallerFactory {
+        BinaryInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &BinaryManifest,
+        name: &str,
+        dependency: &BinaryInstallDependency,
+        mode: TargetMode,
+    ) -> BinaryTargetDependency {
+        BinaryTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
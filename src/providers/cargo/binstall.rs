@@ -0,0 +1,832 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use cargo::util::PartialVersion;
+use semver::Version;
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use strum_macros::Display;
+use tokio::fs;
+
+use crate::{
+    paths::workspace::Workspace,
+    providers::binary::{current_target, ArchiveFormat, BinarySha256},
+    utils::{download::lenient_download, fs_ext::set_executable},
+    Result,
+};
+
+use super::{home::CargoWorkspace, CargoInstallDependencyDetail, RegistryIndex};
+
+const CRATES_INFO_FILE_NAME: &str = ".crates2.json";
+const CRATES_TOML_FILE_NAME: &str = ".crates.toml";
+const GITHUB_URL_PREFIX: &str = "https://github.com/";
+
+/// The release URLs which are tried for crates that do not declare a pkg-url,
+/// following the defaults of cargo-binstall.
+const DEFAULT_PKG_URLS: [&str; 6] = [
+    "{ repo }/releases/download/v{ version }/{ name }-{ target }-v{ version }{ archive-suffix }",
+    "{ repo }/releases/download/v{ version }/{ name }-v{ version }-{ target }{ archive-suffix }",
+    "{ repo }/releases/download/v{ version }/{ name }-{ target }{ archive-suffix }",
+    "{ repo }/releases/download/{ version }/{ name }-{ target }-v{ version }{ archive-suffix }",
+    "{ repo }/releases/download/{ version }/{ name }-{ version }-{ target }{ archive-suffix }",
+    "{ repo }/releases/download/{ version }/{ name }-{ target }{ archive-suffix }",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum InstallStrategy {
+    /// Always builds the crate with `cargo install`.
+    #[default]
+    Source,
+    /// Uses a prebuilt binary, guessing the GitHub release URLs when none is declared.
+    Binstall,
+    /// Uses a prebuilt binary only when the crate or the manifest declares its URL.
+    Auto,
+}
+
+impl InstallStrategy {
+    /// Loads the strategy which the cargo dependency installed in the workspace was installed by.
+    pub async fn lenient_load_from_workspace(workspace: &Workspace, name: &str) -> Option<Self> {
+        let path = CargoWorkspace::from_workspace(workspace).install_strategy_path(name);
+        let content = fs::read_to_string(path).await.ok()?;
+        serde_json::from_value(serde_json::Value::String(content.trim().into())).ok()
+    }
+
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.to_string()).await?)
+    }
+}
+
+/// The package formats of cargo-binstall which can be extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PkgFmt {
+    Tgz,
+    Txz,
+    Zip,
+    Bin,
+}
+
+impl PkgFmt {
+    fn archive_format(self) -> ArchiveFormat {
+        match self {
+            Self::Tgz => ArchiveFormat::TarGz,
+            Self::Txz => ArchiveFormat::TarXz,
+            Self::Zip => ArchiveFormat::Zip,
+            Self::Bin => ArchiveFormat::Binary,
+        }
+    }
+
+    fn archive_suffixes(self) -> &'static [&'static str] {
+        match self {
+            Self::Tgz => &[".tgz", ".tar.gz"],
+            Self::Txz => &[".txz", ".tar.xz"],
+            Self::Zip => &[".zip"],
+            Self::Bin => &[""],
+        }
+    }
+}
+
+/// The settings of `[package.metadata.binstall]`, which can also be given in the manifest.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Getters, new)]
+pub struct CargoBinstallDetail {
+    #[serde(
+        alias = "pkg-url",
+        alias = "pkgUrl",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pkg_url: Option<String>,
+    #[serde(
+        alias = "pkg-fmt",
+        alias = "pkgFmt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pkg_fmt: Option<PkgFmt>,
+    /// The path of the bins in the package, which defaults to a search by the bin name.
+    #[serde(
+        alias = "bin-dir",
+        alias = "binDir",
+        skip_serializing_if = "Option::is_none"
+    )]
+    bin_dir: Option<String>,
+    /// Only the manifest can give the checksum. Otherwise the `.sha256` file next to the package is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<BinarySha256>,
+}
+
+impl CargoBinstallDetail {
+    pub fn validate(&self) -> Result<()> {
+        match &self.sha256 {
+            Some(sha256) => sha256.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Fills the settings which are not set from the other.
+    fn or(self, other: Self) -> Self {
+        Self {
+            pkg_url: self.pkg_url.or(other.pkg_url),
+            pkg_fmt: self.pkg_fmt.or(other.pkg_fmt),
+            bin_dir: self.bin_dir.or(other.bin_dir),
+            sha256: self.sha256.or(other.sha256),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum BinstallError {
+    #[error("prebuilt binaries are only used for registry dependencies without build options")]
+    NotApplicable,
+
+    #[error("no version matches {requirement}")]
+    NotFoundVersion { requirement: String },
+
+    #[error("{name} {version} does not declare a prebuilt binary")]
+    NotDeclared { name: String, version: String },
+
+    #[error("no prebuilt binary is found for {target}")]
+    NotFoundPrebuilt { target: String },
+
+    #[error("{url} cannot be verified because no sha256 is given")]
+    Unverified { url: String },
+
+    #[error("sha256 of {url} does not match.\nexpected: {expected}\nactual:   {actual}")]
+    MismatchSha256 {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("unknown key in the binstall template: {key}")]
+    UnknownTemplateKey { key: String },
+
+    #[error("{bin} is not found in the prebuilt package")]
+    NotFoundBin { bin: String },
+}
+
+/// The parts of the crate's Cargo.toml which decide its prebuilt binaries.
+#[derive(Debug, Default, PartialEq)]
+struct CratePackage {
+    repository: Option<String>,
+    binstall: BinstallMetadata,
+    bins: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CrateManifest {
+    package: CrateManifestPackage,
+    #[serde(default)]
+    bin: Vec<CrateManifestBin>,
+}
+
+#[derive(Deserialize)]
+struct CrateManifestPackage {
+    name: String,
+    repository: Option<String>,
+    autobins: Option<bool>,
+    #[serde(default)]
+    metadata: CrateManifestMetadata,
+}
+
+#[derive(Deserialize, Default)]
+struct CrateManifestMetadata {
+    #[serde(default)]
+    binstall: BinstallMetadata,
+}
+
+#[derive(Deserialize)]
+struct CrateManifestBin {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct BinstallMetadata {
+    pkg_url: Option<String>,
+    pkg_fmt: Option<PkgFmt>,
+    bin_dir: Option<String>,
+    #[serde(default)]
+    overrides: BTreeMap<String, BinstallMetadata>,
+}
+
+impl BinstallMetadata {
+    fn for_target(&self, target: &str) -> CargoBinstallDetail {
+        let to_detail = |metadata: &Self| {
+            CargoBinstallDetail::new(
+                metadata.pkg_url.clone(),
+                metadata.pkg_fmt,
+                metadata.bin_dir.clone(),
+                None,
+            )
+        };
+        let detail = to_detail(self);
+        match self.overrides.get(target) {
+            Some(target_metadata) => to_detail(target_metadata).or(detail),
+            None => detail,
+        }
+    }
+}
+
+impl CratePackage {
+    /// Reads the package from a `.crate` file, which is a tar.gz of the package source.
+    fn from_crate_file(name: &str, version: &Version, content: &[u8]) -> Result<Self> {
+        let root = PathBuf::from(format!("{name}-{version}"));
+        let mut manifest = None;
+        let mut auto_bins = BTreeSet::new();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(content));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_path_buf();
+            let Ok(path) = path.strip_prefix(&root) else {
+                continue;
+            };
+            if path == Path::new("Cargo.toml") {
+                let mut content = String::new();
+                entry.read_to_string(&mut content)?;
+                manifest = Some(toml::from_str::<CrateManifest>(&content)?);
+            } else if path == Path::new("src/main.rs") {
+                auto_bins.insert(None);
+            } else if let Ok(bin_path) = path.strip_prefix("src/bin") {
+                let components = bin_path.iter().collect::<Vec<_>>();
+                let bin = match components.as_slice() {
+                    [file] => Path::new(file)
+                        .extension()
+                        .is_some_and(|extension| extension == "rs")
+                        .then(|| Path::new(file).file_stem())
+                        .flatten(),
+                    [dir, file] if *file == "main.rs" => Some(*dir),
+                    _ => None,
+                };
+                if let Some(bin) = bin {
+                    auto_bins.insert(Some(bin.to_string_lossy().to_string()));
+                }
+            }
+        }
+        let manifest = manifest
+            .ok_or_else(|| anyhow::anyhow!("Cargo.toml is not found in {name}-{version}.crate"))?;
+        let mut bins = manifest
+            .bin
+            .into_iter()
+            .map(|bin| bin.name)
+            .collect::<BTreeSet<_>>();
+        if manifest.package.autobins != Some(false) {
+            bins.extend(
+                auto_bins
+                    .into_iter()
+                    .map(|bin| bin.unwrap_or_else(|| manifest.package.name.clone())),
+            );
+        }
+        Ok(Self {
+            repository: manifest.package.repository,
+            binstall: manifest.package.metadata.binstall,
+            bins: bins.into_iter().collect(),
+        })
+    }
+}
+
+/// Expands a cargo-binstall template, which writes its keys as `{ name }`.
+fn expand_template(template: &str, values: &BTreeMap<&str, String>) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        let key = rest[start + 1..start + end].trim();
+        let value = values
+            .get(key)
+            .ok_or_else(|| BinstallError::new_unknown_template_key(key.into()))?;
+        expanded.push_str(value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// A package URL to try with the values which expand its bin-dir.
+#[derive(Debug, PartialEq)]
+struct PrebuiltCandidate {
+    url: String,
+    pkg_fmt: PkgFmt,
+    values: BTreeMap<&'static str, String>,
+}
+
+struct PrebuiltResolver<'a> {
+    name: &'a str,
+    version: &'a Version,
+    target: &'a str,
+    package: &'a CratePackage,
+    binstall: CargoBinstallDetail,
+}
+
+impl<'a> PrebuiltResolver<'a> {
+    fn new(
+        name: &'a str,
+        version: &'a Version,
+        target: &'a str,
+        package: &'a CratePackage,
+        binstall: Option<&CargoBinstallDetail>,
+    ) -> Self {
+        let binstall = binstall
+            .cloned()
+            .unwrap_or_default()
+            .or(package.binstall.for_target(target));
+        Self {
+            name,
+            version,
+            target,
+            package,
+            binstall,
+        }
+    }
+
+    fn values(&self, pkg_fmt: PkgFmt, archive_suffix: &str) -> BTreeMap<&'static str, String> {
+        let binary_ext = if self.target.contains("windows") {
+            ".exe"
+        } else {
+            ""
+        };
+        [
+            ("name", self.name.to_string()),
+            ("version", self.version.to_string()),
+            ("target", self.target.to_string()),
+            (
+                "repo",
+                self.package
+                    .repository
+                    .clone()
+                    .unwrap_or_default()
+                    .trim_end_matches('/')
+                    .trim_end_matches(".git")
+                    .to_string(),
+            ),
+            ("binary-ext", binary_ext.to_string()),
+            ("archive-format", pkg_fmt.to_string()),
+            ("format", pkg_fmt.to_string()),
+            ("archive-suffix", archive_suffix.to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn candidates(&self, strategy: InstallStrategy) -> Result<Vec<PrebuiltCandidate>> {
+        let pkg_urls = match &self.binstall.pkg_url {
+            Some(pkg_url) => vec![pkg_url.as_str()],
+            None if strategy == InstallStrategy::Binstall
+                && self
+                    .package
+                    .repository
+                    .as_ref()
+                    .is_some_and(|repository| repository.starts_with(GITHUB_URL_PREFIX)) =>
+            {
+                DEFAULT_PKG_URLS.to_vec()
+            }
+            None => Err(BinstallError::new_not_declared(
+                self.name.into(),
+                self.version.to_string(),
+            ))?,
+        };
+        let pkg_fmts = match self.binstall.pkg_fmt {
+            Some(pkg_fmt) => vec![pkg_fmt],
+            None if self.binstall.pkg_url.is_some() => vec![PkgFmt::Tgz],
+            None => vec![PkgFmt::Tgz, PkgFmt::Txz, PkgFmt::Zip],
+        };
+        let mut candidates = vec![];
+        for pkg_url in pkg_urls {
+            for pkg_fmt in pkg_fmts.iter() {
+                for archive_suffix in pkg_fmt.archive_suffixes() {
+                    let values = self.values(*pkg_fmt, archive_suffix);
+                    let url = expand_template(pkg_url, &values)?;
+                    if !candidates
+                        .iter()
+                        .any(|candidate: &PrebuiltCandidate| candidate.url == url)
+                    {
+                        candidates.push(PrebuiltCandidate {
+                            url,
+                            pkg_fmt: *pkg_fmt,
+                            values,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(candidates)
+    }
+
+    /// Returns the path of each bin in the extracted package.
+    fn bin_paths(
+        &self,
+        candidate: &PrebuiltCandidate,
+        bins: &[String],
+        package_dir: &Path,
+    ) -> Result<Vec<(String, PathBuf)>> {
+        let binary_ext = candidate.values["binary-ext"].clone();
+        bins.iter()
+            .map(|bin| {
+                let bin_file_name = format!("{bin}{binary_ext}");
+                let bin_path = match (candidate.pkg_fmt, &self.binstall.bin_dir) {
+                    (PkgFmt::Bin, _) => Some(package_dir.join(&bin_file_name)),
+                    (_, Some(bin_dir)) => {
+                        let mut values = candidate.values.clone();
+                        values.insert("bin", bin.clone());
+                        Some(package_dir.join(expand_template(bin_dir, &values)?))
+                    }
+                    (_, None) => find_file(package_dir, &bin_file_name),
+                };
+                match bin_path {
+                    Some(bin_path) if bin_path.is_file() => Ok((bin_file_name, bin_path)),
+                    _ => Err(BinstallError::new_not_found_bin(bin_file_name).into()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Finds the shallowest file of the name in the dir.
+fn find_file(dir: &Path, file_name: &str) -> Option<PathBuf> {
+    ignore::WalkBuilder::new(dir)
+        .standard_filters(false)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter(|entry| entry.file_name() == file_name)
+        .min_by_key(|entry| entry.depth())
+        .map(|entry| entry.into_path())
+}
+
+/// Selects the version which `cargo install` would select for the requirement.
+fn select_version(requirement: &PartialVersion, versions: &[Version]) -> Result<Version> {
+    let version = match requirement.version() {
+        Some(exact) => versions.iter().find(|version| **version == exact).cloned(),
+        None => versions
+            .iter()
+            .filter(|version| requirement.caret_req().matches(version))
+            .max()
+            .cloned(),
+    };
+    version.ok_or_else(|| BinstallError::new_not_found_version(requirement.to_string()).into())
+}
+
+/// Parses a `.sha256` file, which has the checksum optionally followed by the file name.
+fn parse_sha256_file(content: &[u8]) -> Option<String> {
+    let sha256 = String::from_utf8_lossy(content)
+        .split_whitespace()
+        .next()?
+        .to_string();
+    (sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit())).then_some(sha256)
+}
+
+async fn verify_sha256(
+    url: &str,
+    content: &[u8],
+    sha256: Option<&BinarySha256>,
+    target: &str,
+) -> Result<()> {
+    let expected = match sha256.and_then(|sha256| sha256.for_target(target)) {
+        Some(expected) => expected.to_string(),
+        None => lenient_download(&format!("{url}.sha256"))
+            .await?
+            .as_deref()
+            .and_then(parse_sha256_file)
+            .ok_or_else(|| BinstallError::new_unverified(url.into()))?,
+    };
+    let actual = format!("{:x}", Sha256::digest(content));
+    if actual.eq_ignore_ascii_case(&expected) {
+        Ok(())
+    } else {
+        Err(BinstallError::new_mismatch_sha256(url.into(), expected, actual).into())
+    }
+}
+
+/// Installs the prebuilt binaries of the dependency into the install dir in the same layout as
+/// `cargo install --root`, including the install information which cargo records.
+pub async fn install_prebuilt(
+    install_dir: &Path,
+    name: &str,
+    dependency: &CargoInstallDependencyDetail,
+    strategy: InstallStrategy,
+) -> Result<()> {
+    let requirement = match dependency.version() {
+        Some(requirement) if dependency.is_prebuilt_applicable() => requirement,
+        _ => Err(BinstallError::NotApplicable)?,
+    };
+    let registry_index = RegistryIndex::from_dependency(dependency)?;
+    let version = select_version(requirement, &registry_index.versions(name).await?)?;
+    let crate_file = registry_index.download_crate(name, &version).await?;
+    let package = CratePackage::from_crate_file(name, &version, &crate_file)?;
+    let target = current_target();
+    let resolver = PrebuiltResolver::new(
+        name,
+        &version,
+        &target,
+        &package,
+        dependency.binstall().as_ref(),
+    );
+    let bins = dependency.bins().clone().unwrap_or(package.bins.clone());
+    for candidate in resolver.candidates(strategy)? {
+        let Some(content) = lenient_download(&candidate.url).await? else {
+            continue;
+        };
+        verify_sha256(
+            &candidate.url,
+            &content,
+            resolver.binstall.sha256().as_ref(),
+            &target,
+        )
+        .await?;
+        let package_dir = std::env::temp_dir().join(nanoid::nanoid!());
+        fs::create_dir_all(&package_dir).await?;
+        let result = place_bins(
+            &resolver,
+            &candidate,
+            content,
+            &bins,
+            &package_dir,
+            install_dir,
+        )
+        .await;
+        fs::remove_dir_all(&package_dir).await?;
+        let bin_file_names = result?;
+        return save_crates_info(
+            install_dir,
+            name,
+            &version,
+            &registry_index,
+            requirement,
+            &target,
+            bin_file_names,
+        )
+        .await;
+    }
+    Err(BinstallError::new_not_found_prebuilt(target).into())
+}
+
+async fn place_bins(
+    resolver: &PrebuiltResolver<'_>,
+    candidate: &PrebuiltCandidate,
+    content: Vec<u8>,
+    bins: &[String],
+    package_dir: &Path,
+    install_dir: &Path,
+) -> Result<Vec<String>> {
+    let binary_file_name = format!(
+        "{}{}",
+        bins.first().map(String::as_str).unwrap_or(resolver.name),
+        candidate.values["binary-ext"]
+    );
+    candidate
+        .pkg_fmt
+        .archive_format()
+        .extract(content, package_dir, &binary_file_name)
+        .await?;
+    let bin_paths = resolver.bin_paths(candidate, bins, package_dir)?;
+    // The previous installation may have other bins, so the install dir starts from scratch.
+    if install_dir.exists() {
+        fs::remove_dir_all(install_dir).await?;
+    }
+    let bin_dir = install_dir.join("bin");
+    fs::create_dir_all(&bin_dir).await?;
+    for (bin_file_name, bin_path) in bin_paths.iter() {
+        let installed_path = bin_dir.join(bin_file_name);
+        // The package is extracted into the temp dir, which may be on another file system.
+        fs::copy(bin_path, &installed_path).await?;
+        set_executable(&installed_path).await?;
+    }
+    Ok(bin_paths
+        .into_iter()
+        .map(|(bin_file_name, _)| bin_file_name)
+        .collect())
+}
+
+/// Writes the install information in the format of cargo so that `cargo install` can update it.
+async fn save_crates_info(
+    install_dir: &Path,
+    name: &str,
+    version: &Version,
+    registry_index: &RegistryIndex,
+    requirement: &PartialVersion,
+    target: &str,
+    bin_file_names: Vec<String>,
+) -> Result<()> {
+    let package_id = format!("{name} {version} ({})", registry_index.source_id());
+    let crates_info = serde_json::json!({
+        "installs": {
+            &package_id: {
+                "version_req": requirement.to_string(),
+                "bins": bin_file_names,
+                "features": [],
+                "all_features": false,
+                "no_default_features": false,
+                "profile": "release",
+                "target": target,
+                "rustc": "",
+            }
+        }
+    });
+    fs::write(
+        install_dir.join(CRATES_INFO_FILE_NAME),
+        serde_json::to_string(&crates_info)?,
+    )
+    .await?;
+    let crates_toml = toml::to_string(&serde_json::json!({
+        "v1": { &package_id: bin_file_names }
+    }))?;
+    fs::write(install_dir.join(CRATES_TOML_FILE_NAME), crates_toml).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::str::FromStr;
+
+    fn values() -> BTreeMap<&'static str, String> {
+        [
+            ("name", "cargo-make".to_string()),
+            ("version", "0.37.4".to_string()),
+            ("target", "x86_64-unknown-linux-musl".to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[rstest]
+    #[case(
+        "{ name }-{ target }-v{ version }/{name}",
+        Some("cargo-make-x86_64-unknown-linux-musl-v0.37.4/cargo-make")
+    )]
+    #[case("{name}-{unknown}", None)]
+    #[case("{ name", Some("{ name"))]
+    fn expand_template_works(#[case] template: &str, #[case] expected: Option<&str>) {
+        pretty_assertions::assert_eq!(
+            expected.map(String::from),
+            expand_template(template, &values()).ok()
+        );
+    }
+
+    #[rstest]
+    #[case("1.2", &["1.1.0", "1.2.0", "1.3.1", "2.0.0"], Some("1.3.1"))]
+    #[case("1.2.0", &["1.2.0", "1.3.1"], Some("1.2.0"))]
+    #[case("2", &["1.2.0", "1.3.1"], None)]
+    fn select_version_works(
+        #[case] requirement: &str,
+        #[case] versions: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        let versions = versions
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(
+            expected.map(|version| Version::parse(version).unwrap()),
+            select_version(&PartialVersion::from_str(requirement).unwrap(), &versions).ok()
+        );
+    }
+
+    #[rstest]
+    #[case(
+        b"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  tool.tar.gz\n".as_slice(),
+        Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+    )]
+    #[case(b"<html>Not Found</html>".as_slice(), None)]
+    fn parse_sha256_file_works(#[case] content: &[u8], #[case] expected: Option<&str>) {
+        pretty_assertions::assert_eq!(expected.map(String::from), parse_sha256_file(content));
+    }
+
+    fn crate_file(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder =
+            tar::Builder::new(flate2::write::GzEncoder::new(vec![], Default::default()));
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    format!("tool-1.0.0/{path}"),
+                    content.as_bytes(),
+                )
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[rstest]
+    fn from_crate_file_works() {
+        let content = crate_file(&[
+            (
+                "Cargo.toml",
+                r#"
+[package]
+name = "tool"
+version = "1.0.0"
+repository = "https://github.com/example/tool"
+
+[package.metadata.binstall]
+pkg-url = "{ repo }/releases/download/v{ version }/tool-{ target }.tar.gz"
+bin-dir = "tool-{ target }/{ bin }{ binary-ext }"
+
+[package.metadata.binstall.overrides.x86_64-pc-windows-msvc]
+pkg-fmt = "zip"
+
+[[bin]]
+name = "tool-cli"
+path = "src/cli.rs"
+"#,
+            ),
+            ("src/main.rs", ""),
+            ("src/bin/helper.rs", ""),
+            ("src/bin/nested/main.rs", ""),
+            ("src/bin/nested/util.rs", ""),
+        ]);
+        let package =
+            CratePackage::from_crate_file("tool", &Version::parse("1.0.0").unwrap(), &content)
+                .unwrap();
+        pretty_assertions::assert_eq!(vec!["helper", "nested", "tool", "tool-cli"], package.bins);
+        pretty_assertions::assert_eq!(
+            CargoBinstallDetail::new(
+                Some("{ repo }/releases/download/v{ version }/tool-{ target }.tar.gz".into()),
+                Some(PkgFmt::Zip),
+                Some("tool-{ target }/{ bin }{ binary-ext }".into()),
+                None,
+            ),
+            package.binstall.for_target("x86_64-pc-windows-msvc")
+        );
+    }
+
+    #[rstest]
+    #[case(
+        InstallStrategy::Auto,
+        Some("https://github.com/example/tool"),
+        Some(CargoBinstallDetail::new(Some("{ repo }/releases/download/v{ version }/tool-{ target }.{ archive-format }".into()), Some(PkgFmt::Zip), None, None)),
+        Some(vec!["https://github.com/example/tool/releases/download/v1.0.0/tool-x86_64-unknown-linux-gnu.zip"])
+    )]
+    #[case(
+        InstallStrategy::Auto,
+        Some("https://github.com/example/tool"),
+        None,
+        None
+    )]
+    #[case(
+        InstallStrategy::Binstall,
+        Some("https://gitlab.com/example/tool"),
+        None,
+        None
+    )]
+    #[case(
+        InstallStrategy::Binstall,
+        Some("https://github.com/example/tool.git"),
+        Some(CargoBinstallDetail::new(None, Some(PkgFmt::Txz), None, None)),
+        Some(vec![
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-x86_64-unknown-linux-gnu-v1.0.0.txz",
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-x86_64-unknown-linux-gnu-v1.0.0.tar.xz",
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-v1.0.0-x86_64-unknown-linux-gnu.txz",
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-v1.0.0-x86_64-unknown-linux-gnu.tar.xz",
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-x86_64-unknown-linux-gnu.txz",
+            "https://github.com/example/tool/releases/download/v1.0.0/tool-x86_64-unknown-linux-gnu.tar.xz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-x86_64-unknown-linux-gnu-v1.0.0.txz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-x86_64-unknown-linux-gnu-v1.0.0.tar.xz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-1.0.0-x86_64-unknown-linux-gnu.txz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-1.0.0-x86_64-unknown-linux-gnu.tar.xz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-x86_64-unknown-linux-gnu.txz",
+            "https://github.com/example/tool/releases/download/1.0.0/tool-x86_64-unknown-linux-gnu.tar.xz",
+        ])
+    )]
+    fn candidates_works(
+        #[case] strategy: InstallStrategy,
+        #[case] repository: Option<&str>,
+        #[case] binstall: Option<CargoBinstallDetail>,
+        #[case] expected: Option<Vec<&str>>,
+    ) {
+        let version = Version::parse("1.0.0").unwrap();
+        let package = CratePackage {
+            repository: repository.map(String::from),
+            ..Default::default()
+        };
+        let resolver = PrebuiltResolver::new(
+            "tool",
+            &version,
+            "x86_64-unknown-linux-gnu",
+            &package,
+            binstall.as_ref(),
+        );
+        let actual = resolver.candidates(strategy).ok().map(|candidates| {
+            candidates
+                .into_iter()
+                .map(|candidate| candidate.url)
+                .collect::<Vec<_>>()
+        });
+        pretty_assertions::assert_eq!(
+            expected.map(|urls| urls.into_iter().map(String::from).collect::<Vec<_>>()),
+            actual
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use serde_derive::Deserialize;
+
+use crate::{paths::workspace::Workspace, utils::serde_ext::Json, Result};
+
+use super::home::CargoWorkspace;
+
+const CRATES_INFO_FILE_NAME: &str = ".crates2.json";
+
+/// The install information which cargo records in an install root.
+#[derive(Deserialize)]
+pub struct CratesInfo {
+    installs: HashMap<String, CratesInstallInfo>,
+}
+
+#[derive(Deserialize, Getters)]
+pub struct CratesInstallInfo {
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    all_features: bool,
+    #[serde(default)]
+    no_default_features: bool,
+}
+
+#[derive(Getters)]
+pub struct InstalledPackage<'a> {
+    version: &'a str,
+    source: &'a str,
+    install_info: &'a CratesInstallInfo,
+}
+
+impl CratesInfo {
+    /// Loads the install information of the cargo dependency installed in the workspace.
+    pub async fn lenient_load_from_workspace(
+        workspace: &Workspace,
+        name: &str,
+    ) -> Result<Option<Self>> {
+        let crates_info_path = CargoWorkspace::from_workspace(workspace)
+            .cargo_home_dir()
+            .join(name)
+            .join(CRATES_INFO_FILE_NAME);
+        if crates_info_path.exists() {
+            Ok(Some(Json::parse_from_file(crates_info_path).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Finds the installed package from its package id, which is formatted as `name version (source)`.
+    pub fn find_package(&self, name: &str) -> Option<InstalledPackage<'_>> {
+        self.installs.iter().find_map(|(package_id, install_info)| {
+            let mut parts = package_id.splitn(3, ' ');
+            let (package_name, version, source) = (parts.next()?, parts.next()?, parts.next()?);
+            if package_name != name {
+                return None;
+            }
+            Some(InstalledPackage {
+                version,
+                source: source.trim_start_matches('(').trim_end_matches(')'),
+                install_info,
+            })
+        })
+    }
+}
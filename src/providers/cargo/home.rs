@@ -1,8 +1,10 @@
-use crate::paths::workspace::Workspace;
+use crate::{paths::workspace::Workspace, utils::change_detection::ChangeDetection};
 use std::path::PathBuf;
 
 use super::*;
 
+const INSTALL_STRATEGY_FILE_NAME: &str = ".isobin-strategy";
+
 #[derive(Getters, Clone)]
 pub struct CargoWorkspace {
     cargo_home_dir: PathBuf,
@@ -13,4 +15,23 @@ impl CargoWorkspace {
         let cargo_home_dir = workspace.home_dir().join(PROVIDER_NAME);
         Self { cargo_home_dir }
     }
+
+    pub fn change_detection_cache_path(
+        &self,
+        name: &str,
+        change_detection: ChangeDetection,
+    ) -> PathBuf {
+        self.cargo_home_dir
+            .join(name)
+            .join(change_detection.cache_file_name())
+    }
+
+    pub fn install_dir(&self, name: &str) -> PathBuf {
+        self.cargo_home_dir.join(name)
+    }
+
+    /// The file which records the strategy the dependency was installed by.
+    pub fn install_strategy_path(&self, name: &str) -> PathBuf {
+        self.install_dir(name).join(INSTALL_STRATEGY_FILE_NAME)
+    }
 }
@@ -4,6 +4,7 @@ use tokio::{fs, process::Command};
 
 use crate::{
     bin_map::BinDependency,
+    flex_eprintln,
     install::InstallServiceError,
     paths::workspace::Workspace,
     utils::{
@@ -17,13 +18,15 @@ use super::*;
 pub struct CargoInstallerFactory {
     cargo_workspace: CargoWorkspace,
     workspace: Workspace,
+    quiet: bool,
 }
 
 impl CargoInstallerFactory {
-    pub fn new(workspace: Workspace) -> Self {
+    pub fn new(workspace: Workspace, quiet: bool) -> Self {
         Self {
             cargo_workspace: CargoWorkspace::from_workspace(&workspace),
             workspace,
+            quiet,
         }
     }
 }
@@ -35,7 +38,10 @@ impl InstallerFactory for CargoInstallerFactory {
     type BinPathInstaller = CargoBinPathInstaller;
 
     async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
-        Ok(CargoCoreInstaller::new(self.cargo_workspace.clone()))
+        Ok(CargoCoreInstaller::new(
+            self.cargo_workspace.clone(),
+            self.quiet,
+        ))
     }
     async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
         Ok(CargoBinPathInstaller::new(
@@ -48,6 +54,7 @@ impl InstallerFactory for CargoInstallerFactory {
 #[derive(new, Clone)]
 pub struct CargoCoreInstaller {
     cargo_workspace: CargoWorkspace,
+    quiet: bool,
 }
 
 impl CargoCoreInstaller {
@@ -92,21 +99,130 @@ impl CargoCoreInstaller {
                 args.push("--all-features".into());
             }
         }
+        if let Some(no_default_features) = dependency.no_default_features() {
+            if *no_default_features {
+                args.push("--no-default-features".into());
+            }
+        }
+        if let Some(examples) = dependency.examples() {
+            for example in examples.iter() {
+                args.extend_from_slice(&["--example".into(), example.into()]);
+            }
+        }
+        if let Some(profile) = dependency.profile() {
+            args.extend_from_slice(&["--profile".into(), profile.into()]);
+        }
+        if let Some(debug) = dependency.debug() {
+            if *debug {
+                args.push("--debug".into());
+            }
+        }
+        if let Some(target) = dependency.target() {
+            args.extend_from_slice(&["--target".into(), target.into()]);
+        }
+        if let Some(locked) = dependency.locked() {
+            if *locked {
+                args.push("--locked".into());
+            }
+        }
+        if let Some(frozen) = dependency.frozen() {
+            if *frozen {
+                args.push("--frozen".into());
+            }
+        }
+        if let Some(offline) = dependency.offline() {
+            if *offline {
+                args.push("--offline".into());
+            }
+        }
+        if let Some(unstable_flags) = dependency.unstable_flags() {
+            for unstable_flag in unstable_flags.iter() {
+                args.extend_from_slice(&["-Z".into(), unstable_flag.into()]);
+            }
+        }
         args
     }
 }
 
+impl CargoCoreInstaller {
+    async fn save_change_detection_cache(&self, target: &CargoTargetDependency) -> Result<()> {
+        if let CargoInstallDependency::Detailed(dependency) = target.install_dependency() {
+            if let Some(absolute_path) = dependency.absolute_path() {
+                let change_detection = dependency.change_detection().unwrap_or_default();
+                change_detection
+                    .save_snapshot(
+                        enumerate_tracked_files(absolute_path).await?,
+                        &self
+                            .cargo_workspace
+                            .change_detection_cache_path(target.name(), change_detection),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl providers::CoreInstaller for CargoCoreInstaller {
     type InstallTarget = CargoTargetDependency;
-    fn provider_kind(&self) -> providers::ProviderKind {
-        providers::ProviderKind::Cargo
-    }
     fn multi_install_mode(&self) -> providers::MultiInstallMode {
         providers::MultiInstallMode::Parallel
     }
 
     async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        let strategy_path = self.cargo_workspace.install_strategy_path(target.name());
+        let strategy = target.strategy();
+        if strategy != InstallStrategy::Source {
+            if let CargoInstallDependency::Detailed(dependency) = target.install_dependency() {
+                let install_dir = self.cargo_workspace.install_dir(target.name());
+                // A checksum mismatch means the binary may be tampered with, so it is not hidden by
+                // the build from source which the other failures fall back to.
+                match install_prebuilt(&install_dir, target.name(), dependency, strategy).await {
+                    Ok(()) => return InstallStrategy::Binstall.save(strategy_path).await,
+                    Err(err)
+                        if matches!(
+                            err.downcast_ref::<BinstallError>(),
+                            Some(BinstallError::MismatchSha256 { .. })
+                        ) =>
+                    {
+                        Err(InstallServiceError::new_install(
+                            ProviderKind::Cargo,
+                            target.name().into(),
+                            err.to_string(),
+                            err,
+                        ))?
+                    }
+                    Err(err) => flex_eprintln!(
+                        self.quiet,
+                        "Building {} from source because no prebuilt binary is used.\n{err}",
+                        target.name()
+                    ),
+                }
+            }
+        }
+        self.install_from_source(target).await?;
+        if strategy == InstallStrategy::Source {
+            if strategy_path.exists() {
+                fs::remove_file(strategy_path).await?;
+            }
+            Ok(())
+        } else {
+            InstallStrategy::Source.save(strategy_path).await
+        }
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let install_dir = self.cargo_workspace.cargo_home_dir().join(target.name());
+        if install_dir.exists() {
+            fs::remove_dir_all(&install_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+impl CargoCoreInstaller {
+    async fn install_from_source(&self, target: &CargoTargetDependency) -> Result<()> {
         let install_dir = self.cargo_workspace.cargo_home_dir().join(target.name());
         let mut command = Command::new(PROVIDER_NAME);
         let mut args: Vec<String> = vec![
@@ -127,9 +243,8 @@ impl providers::CoreInstaller for CargoCoreInstaller {
         args.extend_from_slice(&dependency_args);
         args.push(target.name().into());
         command.args(args);
-        run_commnad(command)
-            .await
-            .map_err(|err| match err.downcast::<RunCommandError>() {
+        run_commnad(command).await.map_err(|err| -> Error {
+            match err.downcast::<RunCommandError>() {
                 Ok(err) => InstallServiceError::new_install(
                     ProviderKind::Cargo,
                     target.name().into(),
@@ -144,15 +259,9 @@ impl providers::CoreInstaller for CargoCoreInstaller {
                     err,
                 )
                 .into(),
-            })
-    }
-
-    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
-        let install_dir = self.cargo_workspace.cargo_home_dir().join(target.name());
-        if install_dir.exists() {
-            fs::remove_dir_all(&install_dir).await?;
-        }
-        Ok(())
+            }
+        })?;
+        self.save_change_detection_cache(target).await
     }
 }
 
@@ -161,20 +270,27 @@ pub struct CargoTargetDependency {
     name: String,
     install_dependency: CargoInstallDependency,
     mode: TargetMode,
+    /// The strategy which the installed dependency was installed by, if known.
+    #[new(default)]
+    installed_strategy: Option<InstallStrategy>,
 }
 
-impl providers::TargetDependency for CargoTargetDependency {
-    fn provider_kind(&self) -> ProviderKind {
-        ProviderKind::Cargo
-    }
-    fn name(&self) -> &str {
-        &self.name
+impl CargoTargetDependency {
+    pub fn with_installed_strategy(mut self, installed_strategy: Option<InstallStrategy>) -> Self {
+        self.installed_strategy = installed_strategy;
+        self
     }
 
-    fn mode(&self) -> &TargetMode {
-        &self.mode
+    pub fn strategy(&self) -> InstallStrategy {
+        match self.install_dependency() {
+            CargoInstallDependency::Simple(_) => InstallStrategy::Source,
+            CargoInstallDependency::Detailed(dependency) => {
+                dependency.strategy().unwrap_or_default()
+            }
+        }
     }
-    fn summary(&self) -> String {
+
+    fn version_summary(&self) -> String {
         match self.install_dependency() {
             CargoInstallDependency::Simple(version) => version.to_string(),
             CargoInstallDependency::Detailed(dependency) => {
@@ -197,6 +313,29 @@ impl providers::TargetDependency for CargoTargetDependency {
         }
     }
 }
+
+impl providers::TargetDependency for CargoTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Cargo
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        // The strategy is only shown when the dependency opts into prebuilt binaries.
+        match (self.strategy(), self.installed_strategy) {
+            (InstallStrategy::Source, _) => self.version_summary(),
+            (_, Some(installed_strategy)) => {
+                format!("{} ({installed_strategy})", self.version_summary())
+            }
+            (strategy, None) => format!("{} ({strategy})", self.version_summary()),
+        }
+    }
+}
 #[derive(Clone)]
 pub struct CargoBinPathInstaller {
     cargo_workspace: CargoWorkspace,
@@ -256,3 +395,43 @@ impl BinPathInstaller for CargoBinPathInstaller {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#"{"version":"1.0"}"#, vec!["--version", "1.0"])]
+    #[case(
+        r#"{"version":"1.0","features":["a","b"],"no-default-features":true}"#,
+        vec!["--version", "1.0", "--features", "a,b", "--no-default-features"]
+    )]
+    #[case(r#"{"version":"1.0","noDefaultFeatures":false}"#, vec!["--version", "1.0"])]
+    #[case(
+        r#"{"version":"1.0","examples":["ex"],"profile":"dist","target":"x86_64-unknown-linux-musl","locked":true,"frozen":true,"offline":true,"unstable-flags":["sparse-registry"]}"#,
+        vec![
+            "--version",
+            "1.0",
+            "--example",
+            "ex",
+            "--profile",
+            "dist",
+            "--target",
+            "x86_64-unknown-linux-musl",
+            "--locked",
+            "--frozen",
+            "--offline",
+            "-Z",
+            "sparse-registry"
+        ]
+    )]
+    #[case(r#"{"version":"1.0","debug":true}"#, vec!["--version", "1.0", "--debug"])]
+    fn dependency_to_args_works(#[case] dependency: &str, #[case] expected: Vec<&str>) {
+        let dependency: CargoInstallDependencyDetail = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            CargoCoreInstaller::dependency_to_args(&dependency)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use cargo::util::PartialVersion;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{manifest::Manifest, paths::workspace::Workspace, Result};
+
+use super::{
+    crates_info::CratesInfo, CargoInstallDependency, CargoInstallDependencyDetail, CargoManifest,
+};
+
+const PATH_SOURCE_PREFIX: &str = "path+";
+const GIT_SOURCE_PREFIX: &str = "git+";
+
+/// The lock takes the requirements from the unexpanded manifest, which has the same dependencies as the
+/// fixed one as they are written, so that it does not record the environment variables of a machine.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Default, Getters)]
+pub struct CargoLock {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, CargoLockedDependency>,
+}
+
+/// The exact package a cargo dependency was resolved to, together with the requirement it was resolved from.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Getters, new)]
+pub struct CargoLockedDependency {
+    version: String,
+    source: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    all_features: bool,
+    #[serde(default)]
+    no_default_features: bool,
+    requirement: CargoInstallDependency,
+}
+
+impl CargoLock {
+    /// Returns the names of the dependencies whose requirement is not locked and of the locked entries
+    /// which are no longer in the manifest.
+    pub fn stale_names(
+        &self,
+        cargo_manifest: &CargoManifest,
+        unexpanded_cargo_manifest: &CargoManifest,
+    ) -> Vec<String> {
+        let mut names = cargo_manifest
+            .dependencies()
+            .keys()
+            .filter(|name| self.get_locked(name, unexpanded_cargo_manifest).is_none())
+            .cloned()
+            .chain(
+                self.dependencies
+                    .keys()
+                    .filter(|name| !cargo_manifest.dependencies().contains_key(*name))
+                    .cloned(),
+            )
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn pin(
+        &self,
+        cargo_manifest: &CargoManifest,
+        unexpanded_cargo_manifest: &CargoManifest,
+    ) -> CargoManifest {
+        cargo_manifest.make_from_new_dependencies(
+            cargo_manifest
+                .dependencies()
+                .iter()
+                .map(|(name, dependency)| {
+                    let dependency = match self.get_locked(name, unexpanded_cargo_manifest) {
+                        Some(locked_dependency) => locked_dependency.pin(dependency),
+                        None => dependency.clone(),
+                    };
+                    (name.to_string(), dependency)
+                })
+                .collect(),
+        )
+    }
+
+    /// Makes a new lock for `cargo_manifest`.
+    /// Entries whose requirement did not change are kept, and the entries of `resolved_cargo_manifest` are
+    /// read from what cargo recorded in the install root.
+    pub async fn update(
+        &self,
+        cargo_manifest: &CargoManifest,
+        unexpanded_cargo_manifest: &CargoManifest,
+        resolved_cargo_manifest: &CargoManifest,
+        workspace: &Workspace,
+    ) -> Result<Self> {
+        let mut dependencies = BTreeMap::new();
+        for (name, dependency) in cargo_manifest.dependencies().iter() {
+            let Some(requirement) = unexpanded_cargo_manifest.dependencies().get(name) else {
+                continue;
+            };
+            if let Some(locked_dependency) = self.get_locked(name, unexpanded_cargo_manifest) {
+                dependencies.insert(name.to_string(), locked_dependency.clone());
+            } else if resolved_cargo_manifest.dependencies().get(name) == Some(dependency) {
+                let crates_info = CratesInfo::lenient_load_from_workspace(workspace, name).await?;
+                if let Some(locked_dependency) = crates_info.and_then(|crates_info| {
+                    CargoLockedDependency::from_crates_info(name, requirement, &crates_info)
+                }) {
+                    dependencies.insert(name.to_string(), locked_dependency);
+                }
+            }
+        }
+        Ok(Self { dependencies })
+    }
+
+    fn get_locked(
+        &self,
+        name: &str,
+        unexpanded_cargo_manifest: &CargoManifest,
+    ) -> Option<&CargoLockedDependency> {
+        let requirement = unexpanded_cargo_manifest.dependencies().get(name)?;
+        self.dependencies
+            .get(name)
+            .filter(|locked_dependency| locked_dependency.requirement() == requirement)
+    }
+}
+
+impl CargoLockedDependency {
+    /// Returns `dependency`, the fixed requirement, made to install exactly the locked package.
+    pub fn pin(&self, dependency: &CargoInstallDependency) -> CargoInstallDependency {
+        let mut dependency = match dependency {
+            CargoInstallDependency::Simple(version) => {
+                CargoInstallDependencyDetail::from_version(version.clone())
+            }
+            CargoInstallDependency::Detailed(dependency) => dependency.clone(),
+        };
+        if let Some(source) = self.source.strip_prefix(GIT_SOURCE_PREFIX) {
+            if let Some((_, commit)) = source.rsplit_once('#') {
+                dependency = dependency.pin_git_rev(commit.to_string());
+            }
+        } else if !self.source.starts_with(PATH_SOURCE_PREFIX) {
+            if let Ok(version) = PartialVersion::from_str(&self.version) {
+                dependency = dependency.pin_version(version);
+            }
+        }
+        CargoInstallDependency::Detailed(dependency)
+    }
+
+    fn from_crates_info(
+        name: &str,
+        dependency: &CargoInstallDependency,
+        crates_info: &CratesInfo,
+    ) -> Option<Self> {
+        let package = crates_info.find_package(name)?;
+        // Absolute paths differ between machines, so a path source is recorded as written in the manifest.
+        let source = match dependency {
+            CargoInstallDependency::Detailed(detail)
+                if package.source().starts_with(PATH_SOURCE_PREFIX) =>
+            {
+                format!(
+                    "{PATH_SOURCE_PREFIX}{}",
+                    detail.path().as_ref()?.to_string_lossy()
+                )
+            }
+            _ => package.source().to_string(),
+        };
+        let install_info = package.install_info();
+        Some(Self::new(
+            package.version().to_string(),
+            source,
+            install_info.features().clone(),
+            *install_info.all_features(),
+            *install_info.no_default_features(),
+            dependency.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn locked(source: &str, requirement: &str) -> CargoLockedDependency {
+        CargoLockedDependency::new(
+            "1.2.3".into(),
+            source.into(),
+            vec![],
+            false,
+            false,
+            serde_json::from_str(requirement).unwrap(),
+        )
+    }
+
+    #[rstest]
+    #[case(
+        locked("registry+https://github.com/rust-lang/crates.io-index", r#""1""#),
+        r#"{"version":"1.2.3"}"#
+    )]
+    #[case(
+        locked(
+            "registry+https://github.com/rust-lang/crates.io-index",
+            r#"{"version":"1.2","features":["a"]}"#
+        ),
+        r#"{"version":"1.2.3","features":["a"]}"#
+    )]
+    #[case(
+        locked(
+            "git+https://github.com/foo/bar?branch=main#0123abcd",
+            r#"{"git":"https://github.com/foo/bar","branch":"main"}"#
+        ),
+        r#"{"git":"https://github.com/foo/bar","rev":"0123abcd"}"#
+    )]
+    #[case(
+        locked("path+tools/foo", r#"{"path":"tools/foo"}"#),
+        r#"{"path":"tools/foo"}"#
+    )]
+    fn pin_works(#[case] locked_dependency: CargoLockedDependency, #[case] expected: &str) {
+        let expected: CargoInstallDependency = serde_json::from_str(expected).unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            locked_dependency.pin(locked_dependency.requirement())
+        );
+    }
+}
@@ -6,21 +6,34 @@ use std::{
 use anyhow::anyhow;
 use cargo::util::PartialVersion;
 use serde_derive::{Deserialize, Serialize};
-use tokio::fs;
 
 use crate::{
-    manifest::Manifest,
+    manifest::{Manifest, ManifestDependency},
     paths::workspace::Workspace,
     providers::ProviderKind,
-    utils::file_modified::{
-        has_file_diff_in_dir, FileDiffHelpers, FILE_MODIFIED_CACHE_MAP_FILE_NAME,
-    },
-    IsobinManifestError, Result,
+    utils::{change_detection::ChangeDetection, env_var::expand_env_vars, fs_ext::rebase_path},
+    IsobinManifestWarning, Result,
+};
+
+use super::{
+    binstall::{CargoBinstallDetail, InstallStrategy},
+    home::CargoWorkspace,
+    tracked_files::enumerate_tracked_files,
 };
 
-use super::home::CargoWorkspace;
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
 pub struct CargoManifest {
+    /// The default of `locked` for the dependencies which do not set it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    locked: Option<bool>,
+    /// The default of `change_detection` for the dependencies which do not set it.
+    #[serde(
+        default,
+        alias = "change-detection",
+        alias = "changeDetection",
+        skip_serializing_if = "Option::is_none"
+    )]
+    change_detection: Option<ChangeDetection>,
     #[serde(
         serialize_with = "toml::ser::tables_last",
         skip_serializing_if = "HashMap::<String, CargoInstallDependency>::is_empty"
@@ -38,8 +51,9 @@ impl CargoManifest {
         let cargo_workspace = CargoWorkspace::from_workspace(workspace);
         for (name, dependency) in base.dependencies().iter() {
             if let Some(old_dependency) = old.dependencies().get(name) {
-                if dependency != old_dependency
-                    || Self::check_need_build_in_path(name, dependency, &cargo_workspace).await?
+                let dependency = dependency.with_manifest_defaults(base);
+                if dependency != old_dependency.with_manifest_defaults(old)
+                    || Self::check_need_build_in_path(name, &dependency, &cargo_workspace).await?
                 {
                     new_cargo_manifest
                         .dependencies
@@ -54,18 +68,6 @@ impl CargoManifest {
         Ok(new_cargo_manifest)
     }
 
-    pub async fn get_need_uninstall_dependency_manifest(base: &Self, old: &Self) -> Result<Self> {
-        let mut new_cargo_manifest = Self::default();
-        for (name, dependency) in old.dependencies().iter() {
-            if base.dependencies().get(name).is_none() {
-                new_cargo_manifest
-                    .dependencies
-                    .insert(name.to_string(), dependency.clone());
-            }
-        }
-        Ok(new_cargo_manifest)
-    }
-
     pub async fn ditect_difference(
         &self,
         other: &Self,
@@ -75,8 +77,9 @@ impl CargoManifest {
         let cargo_workspace = CargoWorkspace::from_workspace(workspace);
         if let Some(dependency) = self.dependencies().get(name) {
             if let Some(other_dependency) = other.dependencies().get(name) {
-                Ok(dependency != other_dependency
-                    || Self::check_need_build_in_path(name, dependency, &cargo_workspace).await?)
+                let dependency = dependency.with_manifest_defaults(self);
+                Ok(dependency != other_dependency.with_manifest_defaults(other)
+                    || Self::check_need_build_in_path(name, &dependency, &cargo_workspace).await?)
             } else {
                 Ok(true)
             }
@@ -93,69 +96,64 @@ impl CargoManifest {
         match dependency {
             CargoInstallDependency::Simple(_) => Ok(false),
             CargoInstallDependency::Detailed(dependency) => {
-                if let Some(path) = dependency.path() {
-                    let file_modified_cache_map_file_path = cargo_workspace
-                        .cargo_home_dir()
-                        .join(name)
-                        .join(FILE_MODIFIED_CACHE_MAP_FILE_NAME);
-                    let modified_cache_map_data =
-                        fs::read(file_modified_cache_map_file_path).await?;
-                    let modified_cache_map = serde_json::from_slice(&modified_cache_map_data)?;
-                    has_file_diff_in_dir(
-                        path,
-                        FileDiffHelpers {
-                            target_exts: vec!["rs".into()],
-                            target_file_names: vec!["Cargo.toml".into(), "Cargo.lock".into()],
-                            exclude_file_names: vec![],
-                            exclude_dir_names: vec![".git".into(), "target".into()],
-                        },
-                        modified_cache_map,
-                    )
-                    .await
+                if let Some(path) = dependency.absolute_path() {
+                    let change_detection = dependency.change_detection().unwrap_or_default();
+                    change_detection
+                        .has_changes(
+                            enumerate_tracked_files(path).await?,
+                            &cargo_workspace.change_detection_cache_path(name, change_detection),
+                        )
+                        .await
                 } else {
                     Ok(false)
                 }
             }
         }
     }
-    pub fn validate(&self) -> Result<()> {
-        let errs = self
+
+    pub fn warnings(&self) -> Vec<IsobinManifestWarning> {
+        let mut warnings = self
             .dependencies
             .iter()
-            .map(|(name, dependency)| match dependency.validate() {
-                Ok(_) => Ok(()),
-                Err(err) => Err(IsobinManifestError::new_validate(
-                    ProviderKind::Cargo,
-                    name.to_string(),
-                    err,
-                )
-                .into()),
+            .flat_map(|(name, dependency)| {
+                dependency.warnings().into_iter().map(|message| {
+                    IsobinManifestWarning::new(ProviderKind::Cargo, name.to_string(), message)
+                })
             })
-            .filter(|r| r.is_err())
-            .map(|r| r.unwrap_err())
             .collect::<Vec<_>>();
-        if errs.is_empty() {
-            Ok(())
-        } else {
-            Err(IsobinManifestError::MultiValidate(errs).into())
-        }
-    }
-    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
-        for (name, dependency) in self.dependencies.clone().into_iter() {
-            self.dependencies
-                .insert(name, dependency.fix(isobin_manifest_dir));
-        }
-        self
+        warnings.sort_by(|a, b| a.name().cmp(b.name()));
+        warnings
     }
 }
 
 impl Manifest for CargoManifest {
     type Dependency = CargoInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Cargo
+    }
     fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
         &self.dependencies
     }
     fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
-        Self { dependencies }
+        Self {
+            locked: self.locked,
+            change_detection: self.change_detection,
+            dependencies,
+        }
+    }
+    // The defaults follow the newer manifest as the dependencies do.
+    fn merge(&self, new_manifest: &Self) -> Self {
+        let mut new_dependencies = self.dependencies.clone();
+        new_dependencies.extend(new_manifest.dependencies.clone());
+        new_manifest.make_from_new_dependencies(new_dependencies)
+    }
+    // Unlike a newer manifest, an including manifest keeps the defaults it does not set.
+    fn inherit(&self, included_manifest: &Self) -> Self {
+        Self {
+            locked: self.locked.or(included_manifest.locked),
+            change_detection: self.change_detection.or(included_manifest.change_detection),
+            ..included_manifest.merge(self)
+        }
     }
 }
 
@@ -167,65 +165,346 @@ pub enum CargoInstallDependency {
     Detailed(CargoInstallDependencyDetail),
 }
 
-impl CargoInstallDependency {
-    pub fn validate(&self) -> Result<()> {
+impl ManifestDependency for CargoInstallDependency {
+    fn validate(&self) -> Result<()> {
         match self {
             Self::Simple(_) => Ok(()),
             Self::Detailed(dependency) => dependency.validate(),
         }
     }
 
-    pub fn fix(self, isobin_manifest_dir: &Path) -> Self {
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
         match self {
             Self::Simple(_) => self,
             Self::Detailed(dependency) => Self::Detailed(dependency.fix(isobin_manifest_dir)),
         }
     }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.rebase(from_dir, to_dir)),
+        }
+    }
+}
+
+impl CargoInstallDependency {
+    pub fn warnings(&self) -> Vec<String> {
+        match self {
+            Self::Simple(_) => vec![],
+            Self::Detailed(dependency) => dependency.warnings(),
+        }
+    }
+
+    /// Applies the manifest level defaults to the settings which the dependency does not set.
+    pub fn with_manifest_defaults(&self, cargo_manifest: &CargoManifest) -> Self {
+        if cargo_manifest.locked.is_none() && cargo_manifest.change_detection.is_none() {
+            return self.clone();
+        }
+        let dependency = match self {
+            Self::Simple(version) => CargoInstallDependencyDetail::from_version(version.clone()),
+            Self::Detailed(dependency) => dependency.clone(),
+        };
+        Self::from_detail(CargoInstallDependencyDetail {
+            locked: dependency.locked.or(cargo_manifest.locked),
+            change_detection: dependency
+                .change_detection
+                .or(cargo_manifest.change_detection),
+            ..dependency
+        })
+    }
+
+    pub fn from_detail(dependency: CargoInstallDependencyDetail) -> Self {
+        match dependency.version() {
+            Some(version)
+                if dependency == CargoInstallDependencyDetail::from_version(version.clone()) =>
+            {
+                Self::Simple(version.clone())
+            }
+            _ => Self::Detailed(dependency),
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 #[derive(Debug, Clone, PartialEq, Default, Serialize, new, Deserialize, Getters)]
 pub struct CargoInstallDependencyDetail {
+    #[serde(skip_serializing_if = "Option::is_none")]
     bins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<PartialVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     registry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     index: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     path: Option<PathBuf>,
     #[serde(skip)]
     absolute_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     git: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     rev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     features: Option<Vec<String>>,
-    #[serde(alias = "no-default-features", alias = "noDefaultFeatures")]
+    #[serde(
+        alias = "no-default-features",
+        alias = "noDefaultFeatures",
+        skip_serializing_if = "Option::is_none"
+    )]
     no_default_features: Option<bool>,
-    #[serde(alias = "all-features", alias = "allFeatures")]
+    #[serde(
+        alias = "all-features",
+        alias = "allFeatures",
+        skip_serializing_if = "Option::is_none"
+    )]
     all_features: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frozen: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    debug: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    examples: Option<Vec<String>>,
+    #[serde(
+        alias = "unstable-flags",
+        alias = "unstableFlags",
+        skip_serializing_if = "Option::is_none"
+    )]
+    unstable_flags: Option<Vec<String>>,
+    #[serde(
+        alias = "change-detection",
+        alias = "changeDetection",
+        skip_serializing_if = "Option::is_none"
+    )]
+    change_detection: Option<ChangeDetection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<InstallStrategy>,
+    /// Overrides `[package.metadata.binstall]` of the crate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binstall: Option<CargoBinstallDetail>,
+    #[serde(skip)]
+    #[new(default)]
+    undefined_env_vars: Vec<String>,
 }
 
 impl CargoInstallDependencyDetail {
+    /// Expands `${VAR}` and `${VAR:-default}` in the string fields. The undefined variables are
+    /// kept to be reported by `validate`.
     pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        let mut undefined_env_vars = vec![];
+        let mut expand = |value: &mut String| match expand_env_vars(value) {
+            Ok(expanded) => *value = expanded,
+            Err(names) => undefined_env_vars.extend(names),
+        };
+        [
+            &mut self.registry,
+            &mut self.index,
+            &mut self.git,
+            &mut self.branch,
+            &mut self.tag,
+            &mut self.rev,
+            &mut self.profile,
+            &mut self.target,
+        ]
+        .into_iter()
+        .flatten()
+        .for_each(&mut expand);
+        [
+            &mut self.bins,
+            &mut self.features,
+            &mut self.examples,
+            &mut self.unstable_flags,
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .for_each(&mut expand);
+        if let Some(path) = self.path.as_mut().filter(|path| path.to_str().is_some()) {
+            let mut expanded = path.to_string_lossy().to_string();
+            expand(&mut expanded);
+            *path = expanded.into();
+        }
+        undefined_env_vars.sort();
+        undefined_env_vars.dedup();
+        self.undefined_env_vars = undefined_env_vars;
         if let Some(path) = &self.path {
             self.absolute_path = Some(isobin_manifest_dir.join(path));
         }
         self
     }
 
+    pub fn rebase(mut self, from_dir: &Path, to_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            let path = rebase_path(path, from_dir, to_dir);
+            self.absolute_path = self.absolute_path.map(|_| to_dir.join(&path));
+            self.path = Some(path);
+        }
+        self
+    }
+
     pub fn validate(&self) -> Result<()> {
-        if self.version().is_none() && self.path.is_none() && self.git.is_none() {
+        if !self.undefined_env_vars.is_empty() {
+            Err(anyhow!(
+                "environment variable {} is not defined.",
+                self.undefined_env_vars.join(", ")
+            ))
+        } else if self.version().is_none() && self.path.is_none() && self.git.is_none() {
             Err(anyhow!(
                 "cargo dependency dependency should have version or path or git."
             ))
+        } else if self.debug == Some(true) && self.profile.is_some() {
+            Err(anyhow!("debug and profile cannot be used together."))
+        } else if self
+            .profile
+            .as_ref()
+            .is_some_and(|profile| profile.is_empty())
+        {
+            Err(anyhow!("profile should not be empty."))
+        } else if self.target.as_ref().is_some_and(|target| target.is_empty()) {
+            Err(anyhow!("target should not be empty."))
+        } else if self
+            .examples
+            .iter()
+            .chain(self.unstable_flags.iter())
+            .flatten()
+            .any(|value| value.is_empty())
+        {
+            Err(anyhow!(
+                "examples and unstable_flags should not contain empty values."
+            ))
+        } else if let Some(binstall) = &self.binstall {
+            binstall.validate()
         } else {
             Ok(())
         }
     }
 
+    /// Returns the messages for settings which are valid but probably not intended.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        if self.no_default_features == Some(true) && self.all_features == Some(true) {
+            warnings.push(
+                "no_default_features has no effect because all_features enables every feature."
+                    .into(),
+            );
+        }
+        if self
+            .strategy
+            .is_some_and(|strategy| strategy != InstallStrategy::Source)
+            && !self.is_prebuilt_applicable()
+        {
+            warnings.push(
+                "strategy has no effect because prebuilt binaries are only used for registry dependencies without build options."
+                    .into(),
+            );
+        }
+        warnings
+    }
+
+    /// Returns whether a prebuilt binary can replace the build, which is only true for
+    /// registry dependencies without settings that change the build.
+    pub fn is_prebuilt_applicable(&self) -> bool {
+        self.version.is_some()
+            && self.path.is_none()
+            && self.git.is_none()
+            && self.features.is_none()
+            && self.no_default_features != Some(true)
+            && self.all_features != Some(true)
+            && self.profile.is_none()
+            && self.debug != Some(true)
+            && self.target.is_none()
+            && self.examples.is_none()
+            && self.unstable_flags.is_none()
+    }
+
     pub fn from_version(version: impl Into<PartialVersion>) -> Self {
         Self {
             version: Some(version.into()),
             ..Default::default()
         }
     }
+
+    pub fn pin_version(mut self, version: PartialVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn pin_git_rev(mut self, rev: String) -> Self {
+        self.branch = None;
+        self.tag = None;
+        self.rev = Some(rev);
+        self
+    }
+}
+
+/// Builds a detail from the fields which are given, leaving the others unset.
+#[derive(Default)]
+pub struct CargoInstallDependencyDetailBuilder {
+    detail: CargoInstallDependencyDetail,
+}
+
+impl CargoInstallDependencyDetailBuilder {
+    pub fn bins(mut self, bins: Option<Vec<String>>) -> Self {
+        self.detail.bins = bins;
+        self
+    }
+    pub fn version(mut self, version: Option<PartialVersion>) -> Self {
+        self.detail.version = version;
+        self
+    }
+    pub fn registry(mut self, registry: Option<String>) -> Self {
+        self.detail.registry = registry;
+        self
+    }
+    pub fn index(mut self, index: Option<String>) -> Self {
+        self.detail.index = index;
+        self
+    }
+    pub fn path(mut self, path: Option<PathBuf>) -> Self {
+        self.detail.path = path;
+        self
+    }
+    pub fn git(mut self, git: Option<String>) -> Self {
+        self.detail.git = git;
+        self
+    }
+    pub fn branch(mut self, branch: Option<String>) -> Self {
+        self.detail.branch = branch;
+        self
+    }
+    pub fn tag(mut self, tag: Option<String>) -> Self {
+        self.detail.tag = tag;
+        self
+    }
+    pub fn rev(mut self, rev: Option<String>) -> Self {
+        self.detail.rev = rev;
+        self
+    }
+    pub fn features(mut self, features: Option<Vec<String>>) -> Self {
+        self.detail.features = features;
+        self
+    }
+    pub fn no_default_features(mut self, no_default_features: Option<bool>) -> Self {
+        self.detail.no_default_features = no_default_features;
+        self
+    }
+    pub fn all_features(mut self, all_features: Option<bool>) -> Self {
+        self.detail.all_features = all_features;
+        self
+    }
+    pub fn build(self) -> CargoInstallDependencyDetail {
+        self.detail
+    }
 }
@@ -1,11 +1,23 @@
+mod binstall;
+mod crates_info;
 mod home;
 mod installer;
+mod lock;
 mod manifest;
+mod provider;
+mod registry_index;
+mod tracked_files;
 
 #[allow(unused_imports)]
 use super::*;
+pub use binstall::*;
+pub use crates_info::*;
 use home::*;
 pub use installer::*;
+pub use lock::*;
 pub use manifest::*;
+pub use provider::*;
+pub use registry_index::*;
+use tracked_files::*;
 
 pub const PROVIDER_NAME: &str = "cargo";
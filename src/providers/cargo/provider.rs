@@ -0,0 +1,68 @@
+use crate::{paths::workspace::Workspace, IsobinManifestWarning};
+
+use super::*;
+
+pub struct CargoProvider;
+
+#[async_trait]
+impl Provider for CargoProvider {
+    type Manifest = CargoManifest;
+    type InstallerFactory = CargoInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Cargo
+    }
+    fn warnings(&self, manifest: &CargoManifest) -> Vec<IsobinManifestWarning> {
+        manifest.warnings()
+    }
+    async fn get_need_install_dependency_manifest(
+        &self,
+        base: &CargoManifest,
+        old: &CargoManifest,
+        workspace: &Workspace,
+    ) -> Result<CargoManifest> {
+        CargoManifest::get_need_install_dependency_manifest(base, old, workspace).await
+    }
+    async fn ditect_difference(
+        &self,
+        manifest: &CargoManifest,
+        other: &CargoManifest,
+        name: &str,
+        workspace: &Workspace,
+    ) -> Result<bool> {
+        manifest.ditect_difference(other, name, workspace).await
+    }
+
+    fn make_installer_factory(&self, workspace: Workspace, quiet: bool) -> CargoInstallerFactory {
+        CargoInstallerFactory::new(workspace, quiet)
+    }
+    fn make_target(
+        &self,
+        manifest: &CargoManifest,
+        name: &str,
+        dependency: &CargoInstallDependency,
+        mode: TargetMode,
+    ) -> CargoTargetDependency {
+        CargoTargetDependency::new(
+            name.into(),
+            dependency.with_manifest_defaults(manifest),
+            mode,
+        )
+    }
+    async fn make_list_target(
+        &self,
+        _manifest: &CargoManifest,
+        name: &str,
+        dependency: &CargoInstallDependency,
+        workspace: &Workspace,
+    ) -> Result<CargoTargetDependency> {
+        let installed_strategy =
+            InstallStrategy::lenient_load_from_workspace(workspace, name).await;
+        Ok(CargoTargetDependency::new(
+            name.into(),
+            dependency.clone(),
+            TargetMode::AlreadyInstalled,
+        )
+        .with_installed_strategy(installed_strategy))
+    }
+}
@@ -0,0 +1,286 @@
+use std::path::PathBuf;
+
+use semver::Version;
+use serde_derive::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::{utils::download::download, Result};
+
+use super::CargoInstallDependencyDetail;
+
+const CRATES_IO_INDEX: &str = "sparse+https://index.crates.io/";
+const SPARSE_INDEX_PREFIX: &str = "sparse+";
+const FILE_INDEX_PREFIX: &str = "file://";
+const CRATES_IO_SOURCE_ID: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+/// A registry index which can be read without cloning it.
+/// Sparse http indexes and local directories with the index layout are supported.
+#[derive(Debug, PartialEq)]
+pub enum RegistryIndex {
+    Sparse { url: String },
+    Local { dir: PathBuf },
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum RegistryIndexError {
+    #[error("The registry index is not supported. Use a sparse index or a local directory\nindex:{index}")]
+    UnsupportedIndex { index: String },
+
+    #[error("Not found {name} in the registry index\nindex:{index}")]
+    NotFoundCrate { name: String, index: String },
+
+    #[error("Not found {path} in the registry index\nindex:{index}")]
+    NotFoundFile { path: String, index: String },
+
+    #[error("The checksum of the crate does not match the registry index\nurl:{url}")]
+    MismatchChecksum { url: String },
+
+    #[error("An error occurred in fetching the registry index\nindex:{index}\nerror:{error}")]
+    Fetch {
+        index: String,
+        #[source]
+        error: reqwest::Error,
+    },
+}
+
+#[derive(Deserialize)]
+struct IndexEntry {
+    vers: Version,
+    #[serde(default)]
+    cksum: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Deserialize)]
+struct IndexConfig {
+    dl: String,
+}
+
+impl RegistryIndex {
+    pub fn from_dependency(dependency: &CargoInstallDependencyDetail) -> Result<Self> {
+        let index = if let Some(index) = dependency.index() {
+            index.to_string()
+        } else if let Some(registry) = dependency.registry() {
+            cargo::Config::default()?
+                .get_registry_index(registry)?
+                .to_string()
+        } else {
+            CRATES_IO_INDEX.to_string()
+        };
+        Self::from_index(index)
+    }
+
+    pub fn from_index(index: String) -> Result<Self> {
+        if let Some(url) = index.strip_prefix(SPARSE_INDEX_PREFIX) {
+            Ok(Self::Sparse {
+                url: format!("{}/", url.trim_end_matches('/')),
+            })
+        } else if let Some(dir) = index.strip_prefix(FILE_INDEX_PREFIX) {
+            Ok(Self::Local { dir: dir.into() })
+        } else {
+            Err(RegistryIndexError::new_unsupported_index(index).into())
+        }
+    }
+
+    /// Returns the versions of the crate which are not yanked.
+    pub async fn versions(&self, name: &str) -> Result<Vec<Version>> {
+        Ok(self
+            .entries(name)
+            .await?
+            .into_iter()
+            .map(|entry| entry.vers)
+            .collect())
+    }
+
+    /// Downloads the `.crate` file of the version and verifies its checksum.
+    pub async fn download_crate(&self, name: &str, version: &Version) -> Result<Vec<u8>> {
+        let entry = self
+            .entries(name)
+            .await?
+            .into_iter()
+            .find(|entry| &entry.vers == version)
+            .ok_or_else(|| {
+                RegistryIndexError::new_not_found_crate(name.into(), self.to_string())
+            })?;
+        let config: IndexConfig = serde_json::from_str(&self.read("config.json").await?)?;
+        let url = crate_download_url(&config.dl, name, version, &entry.cksum);
+        let content = download(&url).await?;
+        let actual = format!("{:x}", Sha256::digest(&content));
+        if !entry.cksum.is_empty() && !actual.eq_ignore_ascii_case(&entry.cksum) {
+            Err(RegistryIndexError::new_mismatch_checksum(url))?;
+        }
+        Ok(content)
+    }
+
+    /// Returns the source id which cargo records for the packages installed from the index.
+    pub fn source_id(&self) -> String {
+        match self {
+            Self::Sparse { url } if format!("{SPARSE_INDEX_PREFIX}{url}") == CRATES_IO_INDEX => {
+                CRATES_IO_SOURCE_ID.into()
+            }
+            Self::Sparse { .. } => self.to_string(),
+            Self::Local { dir } => format!("registry+{FILE_INDEX_PREFIX}{}", dir.display()),
+        }
+    }
+
+    async fn entries(&self, name: &str) -> Result<Vec<IndexEntry>> {
+        let content = self.read(&index_path(name)).await.map_err(|err| match err
+            .downcast::<RegistryIndexError>()
+        {
+            Ok(RegistryIndexError::NotFoundFile { .. }) => {
+                RegistryIndexError::new_not_found_crate(name.into(), self.to_string()).into()
+            }
+            Ok(err) => err.into(),
+            Err(err) => err,
+        })?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str::<IndexEntry>(line)?))
+            .filter(|entry| !matches!(entry, Ok(IndexEntry { yanked: true, .. })))
+            .collect()
+    }
+
+    /// Reads the file of the index.
+    async fn read(&self, path: &str) -> Result<String> {
+        let content = match self {
+            Self::Sparse { url } => {
+                let response = reqwest::Client::new()
+                    .get(format!("{url}{path}"))
+                    .header(reqwest::header::USER_AGENT, "isobin")
+                    .send()
+                    .await
+                    .map_err(|err| RegistryIndexError::new_fetch(self.to_string(), err))?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    Err(RegistryIndexError::new_not_found_file(
+                        path.into(),
+                        self.to_string(),
+                    ))?;
+                }
+                response
+                    .error_for_status()
+                    .map_err(|err| RegistryIndexError::new_fetch(self.to_string(), err))?
+                    .text()
+                    .await
+                    .map_err(|err| RegistryIndexError::new_fetch(self.to_string(), err))?
+            }
+            Self::Local { dir } => {
+                let file_path = dir.join(path);
+                if !file_path.exists() {
+                    Err(RegistryIndexError::new_not_found_file(
+                        path.into(),
+                        self.to_string(),
+                    ))?;
+                }
+                fs::read_to_string(file_path).await?
+            }
+        };
+        Ok(content)
+    }
+}
+
+impl std::fmt::Display for RegistryIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sparse { url } => write!(f, "{SPARSE_INDEX_PREFIX}{url}"),
+            Self::Local { dir } => write!(f, "{FILE_INDEX_PREFIX}{}", dir.display()),
+        }
+    }
+}
+
+/// Returns the path of the crate in the index as described in the cargo book.
+fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    format!("{}/{name}", index_prefix(&name))
+}
+
+fn index_prefix(name: &str) -> String {
+    match name.len() {
+        1 => "1".into(),
+        2 => "2".into(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Expands the `dl` of the index config as described in the cargo book.
+fn crate_download_url(dl: &str, name: &str, version: &Version, cksum: &str) -> String {
+    const MARKERS: [&str; 5] = [
+        "{crate}",
+        "{version}",
+        "{prefix}",
+        "{lowerprefix}",
+        "{sha256-checksum}",
+    ];
+    if !MARKERS.iter().any(|marker| dl.contains(marker)) {
+        return format!("{}/{name}/{version}/download", dl.trim_end_matches('/'));
+    }
+    dl.replace("{crate}", name)
+        .replace("{version}", &version.to_string())
+        .replace("{prefix}", &index_prefix(name))
+        .replace("{lowerprefix}", &index_prefix(&name.to_lowercase()))
+        .replace("{sha256-checksum}", cksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::current_source_dir;
+    use rstest::*;
+    use std::path::Path;
+
+    #[rstest]
+    #[case("a", "1/a")]
+    #[case("ab", "2/ab")]
+    #[case("abc", "3/a/abc")]
+    #[case("Cargo-Make", "ca/rg/cargo-make")]
+    fn index_path_works(#[case] name: &str, #[case] expected: &str) {
+        pretty_assertions::assert_eq!(expected, index_path(name));
+    }
+
+    #[rstest]
+    #[case(
+        "https://crates.io/api/v1/crates",
+        "https://crates.io/api/v1/crates/Cargo-Make/0.37.4/download"
+    )]
+    #[case(
+        "https://example.com/{prefix}/{lowerprefix}/{crate}-{version}.crate?{sha256-checksum}",
+        "https://example.com/Ca/rg/ca/rg/Cargo-Make-0.37.4.crate?abc"
+    )]
+    fn crate_download_url_works(#[case] dl: &str, #[case] expected: &str) {
+        let version = Version::parse("0.37.4").unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            crate_download_url(dl, "Cargo-Make", &version, "abc")
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "sparse+https://index.crates.io",
+        RegistryIndex::Sparse { url: "https://index.crates.io/".into() }
+    )]
+    #[case(
+        "file:///tmp/index",
+        RegistryIndex::Local { dir: "/tmp/index".into() }
+    )]
+    fn from_index_works(#[case] index: &str, #[case] expected: RegistryIndex) {
+        pretty_assertions::assert_eq!(expected, RegistryIndex::from_index(index.into()).unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn local_versions_works() {
+        let registry_index = RegistryIndex::Local {
+            dir: current_source_dir!().join("testdata/registry_index"),
+        };
+        let actual = registry_index.versions("cargo-make").await.unwrap();
+        let expected = ["0.36.0", "0.37.0", "0.37.4", "1.0.0-alpha.1"]
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,371 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::{Path, PathBuf},
+};
+
+use tokio::fs;
+
+use crate::{
+    utils::file_modified::{enumurate_target_files, FileDiffHelpers},
+    Result,
+};
+
+const CARGO_MANIFEST_FILE_NAME: &str = "Cargo.toml";
+const CARGO_LOCK_FILE_NAME: &str = "Cargo.lock";
+const RERUN_IF_CHANGED: &str = "rerun-if-changed=";
+
+/// The files which affect the build of a path dependency.
+pub fn file_diff_helpers() -> FileDiffHelpers {
+    FileDiffHelpers {
+        target_exts: vec!["rs".into()],
+        target_file_names: vec!["Cargo.toml".into(), "Cargo.lock".into()],
+        exclude_file_names: vec![],
+        exclude_dir_names: vec![".git".into(), "target".into()],
+    }
+}
+
+/// Enumerates the files which affect the build of the package in the dir.
+/// The path dependencies of the package, including the workspace members it depends on,
+/// the manifest and the lock file of its workspace and the inputs of its build script are
+/// tracked as well as the package itself.
+pub async fn enumerate_tracked_files(package_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let mut package_dirs = vec![package_dir.as_ref().to_path_buf()];
+    let mut visited_dirs = HashSet::new();
+    let mut files = BTreeSet::new();
+    while let Some(dir) = package_dirs.pop() {
+        let Ok(dir) = fs::canonicalize(&dir).await else {
+            continue;
+        };
+        if !visited_dirs.insert(dir.clone()) {
+            continue;
+        }
+        files.extend(enumurate_target_files(&dir, &file_diff_helpers()).await?);
+        let cargo_manifest_path = dir.join(CARGO_MANIFEST_FILE_NAME);
+        if !cargo_manifest_path.exists() {
+            continue;
+        }
+        let cargo_manifest = read_cargo_manifest(&cargo_manifest_path).await?;
+        let workspace_root = workspace_root(&dir, &cargo_manifest).await?;
+        package_dirs.extend(path_dependency_dirs(
+            &dir,
+            &cargo_manifest,
+            workspace_root.as_ref(),
+        ));
+        if let Some((workspace_root_dir, _)) = &workspace_root {
+            // Only the files of the root which every member is built with are tracked,
+            // the other members are tracked when the package depends on them.
+            files.extend(
+                [CARGO_MANIFEST_FILE_NAME, CARGO_LOCK_FILE_NAME]
+                    .into_iter()
+                    .map(|file_name| workspace_root_dir.join(file_name))
+                    .filter(|path| path.exists()),
+            );
+        }
+        files.extend(build_script_inputs(&dir, &cargo_manifest).await?);
+    }
+    Ok(files.into_iter().collect())
+}
+
+async fn read_cargo_manifest(cargo_manifest_path: &Path) -> Result<toml::Value> {
+    Ok(toml::from_str(
+        &fs::read_to_string(cargo_manifest_path).await?,
+    )?)
+}
+
+/// Returns the dirs of the path dependencies, where the dependencies inherited with
+/// `workspace = true` are resolved by the `[workspace.dependencies]` of `workspace_root`.
+fn path_dependency_dirs(
+    dir: &Path,
+    cargo_manifest: &toml::Value,
+    workspace_root: Option<&(PathBuf, toml::Value)>,
+) -> Vec<PathBuf> {
+    let mut dependency_tables = vec![
+        cargo_manifest.get("dependencies"),
+        cargo_manifest.get("build-dependencies"),
+    ];
+    if let Some(targets) = cargo_manifest.get("target").and_then(|t| t.as_table()) {
+        for target in targets.values() {
+            dependency_tables.push(target.get("dependencies"));
+            dependency_tables.push(target.get("build-dependencies"));
+        }
+    }
+    let workspace_dependencies = workspace_root.and_then(|(workspace_root_dir, cargo_manifest)| {
+        cargo_manifest
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies"))
+            .map(|dependencies| (workspace_root_dir, dependencies))
+    });
+    dependency_tables
+        .into_iter()
+        .flatten()
+        .filter_map(|dependencies| dependencies.as_table())
+        .flatten()
+        .filter_map(|(name, dependency)| {
+            if dependency
+                .get("workspace")
+                .and_then(|workspace| workspace.as_bool())
+                == Some(true)
+            {
+                let (workspace_root_dir, dependencies) = workspace_dependencies?;
+                dependency_path(dependencies.get(name)?).map(|path| workspace_root_dir.join(path))
+            } else {
+                dependency_path(dependency).map(|path| dir.join(path))
+            }
+        })
+        .collect()
+}
+
+fn dependency_path(dependency: &toml::Value) -> Option<&str> {
+    dependency.get("path").and_then(|path| path.as_str())
+}
+
+/// Returns the dir and the manifest of the workspace root of the package in `dir`.
+/// Like cargo, the root is the package itself when it has `[workspace]`, the one which
+/// `package.workspace` points to, or else the nearest ancestor whose `[workspace]` lists it.
+async fn workspace_root(
+    dir: &Path,
+    cargo_manifest: &toml::Value,
+) -> Result<Option<(PathBuf, toml::Value)>> {
+    if cargo_manifest.get("workspace").is_some() {
+        return Ok(Some((dir.to_path_buf(), cargo_manifest.clone())));
+    }
+    if let Some(workspace) = cargo_manifest
+        .get("package")
+        .and_then(|package| package.get("workspace"))
+        .and_then(|workspace| workspace.as_str())
+    {
+        let workspace_root_dir = dir.join(workspace);
+        let cargo_manifest_path = workspace_root_dir.join(CARGO_MANIFEST_FILE_NAME);
+        return Ok(if cargo_manifest_path.exists() {
+            Some((
+                workspace_root_dir,
+                read_cargo_manifest(&cargo_manifest_path).await?,
+            ))
+        } else {
+            None
+        });
+    }
+    for ancestor_dir in dir.ancestors().skip(1) {
+        let cargo_manifest_path = ancestor_dir.join(CARGO_MANIFEST_FILE_NAME);
+        if !cargo_manifest_path.exists() {
+            continue;
+        }
+        let cargo_manifest = read_cargo_manifest(&cargo_manifest_path).await?;
+        if let Some(workspace) = cargo_manifest.get("workspace") {
+            if is_workspace_member(ancestor_dir, workspace, dir).await? {
+                return Ok(Some((ancestor_dir.to_path_buf(), cargo_manifest)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Whether `members` of the workspace in `workspace_root_dir` has the canonical `dir`
+/// and `exclude` does not.
+async fn is_workspace_member(
+    workspace_root_dir: &Path,
+    workspace: &toml::Value,
+    dir: &Path,
+) -> Result<bool> {
+    for exclude in string_array(workspace.get("exclude")) {
+        if fs::canonicalize(workspace_root_dir.join(exclude))
+            .await
+            .is_ok_and(|exclude_dir| dir.starts_with(exclude_dir))
+        {
+            return Ok(false);
+        }
+    }
+    for member in string_array(workspace.get("members")) {
+        let pattern = workspace_root_dir.join(member);
+        for member_dir in glob::glob(&pattern.to_string_lossy())? {
+            if fs::canonicalize(member_dir?)
+                .await
+                .is_ok_and(|member_dir| member_dir == dir)
+            {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the build script and the paths it reports with `cargo:rerun-if-changed`.
+/// Only the literal paths can be found.
+async fn build_script_inputs(dir: &Path, cargo_manifest: &toml::Value) -> Result<Vec<PathBuf>> {
+    let build = cargo_manifest
+        .get("package")
+        .and_then(|package| package.get("build"));
+    let build_script_path = match build {
+        Some(toml::Value::Boolean(false)) => return Ok(vec![]),
+        Some(toml::Value::String(build)) => dir.join(build),
+        _ => dir.join("build.rs"),
+    };
+    if !build_script_path.exists() {
+        return Ok(vec![]);
+    }
+    let build_script = fs::read_to_string(&build_script_path).await?;
+    let mut inputs = vec![build_script_path];
+    for input in parse_rerun_if_changed(&build_script) {
+        let input = dir.join(input);
+        if input.is_dir() {
+            inputs.extend(enumerate_all_files(&input)?);
+        } else if input.exists() {
+            inputs.push(input);
+        }
+    }
+    Ok(inputs)
+}
+
+fn parse_rerun_if_changed(build_script: &str) -> Vec<&str> {
+    build_script
+        .match_indices(RERUN_IF_CHANGED)
+        .map(|(index, _)| {
+            let rest = &build_script[index + RERUN_IF_CHANGED.len()..];
+            &rest[..rest.find(['"', '\n']).unwrap_or(rest.len())]
+        })
+        .filter(|input| !input.is_empty() && !input.contains('{'))
+        .collect()
+}
+
+fn enumerate_all_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in ignore::WalkBuilder::new(dir)
+        .standard_filters(false)
+        .build()
+    {
+        let entry = entry?;
+        if entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn string_array(value: Option<&toml::Value>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(|value| value.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        r#"fn main() { println!("cargo:rerun-if-changed=proto/a.proto"); }"#,
+        vec!["proto/a.proto"]
+    )]
+    #[case(
+        r#"println!("cargo::rerun-if-changed=assets");
+println!("cargo:rerun-if-changed={}", path.display());"#,
+        vec!["assets"]
+    )]
+    #[case(r#"fn main() {}"#, vec![])]
+    fn parse_rerun_if_changed_works(#[case] build_script: &str, #[case] expected: Vec<&str>) {
+        pretty_assertions::assert_eq!(expected, parse_rerun_if_changed(build_script));
+    }
+
+    #[rstest]
+    #[case(
+        r#"
+[dependencies]
+a = { path = "../a" }
+b = "1.0"
+[build-dependencies]
+c = { path = "c" }
+[target.'cfg(unix)'.dependencies]
+d = { path = "d" }
+"#,
+        vec!["/p/../a", "/p/c", "/p/d"]
+    )]
+    #[case(
+        r#"
+[dependencies]
+e = { workspace = true }
+f = { workspace = true }
+"#,
+        vec!["/w/crates/e"]
+    )]
+    fn path_dependency_dirs_works(#[case] cargo_manifest: &str, #[case] expected: Vec<&str>) {
+        let cargo_manifest: toml::Value = toml::from_str(cargo_manifest).unwrap();
+        let workspace_root = (
+            PathBuf::from("/w"),
+            toml::from_str(
+                r#"
+[workspace.dependencies]
+e = { path = "crates/e" }
+f = "1.0"
+"#,
+            )
+            .unwrap(),
+        );
+        let mut actual =
+            path_dependency_dirs(Path::new("/p"), &cargo_manifest, Some(&workspace_root));
+        actual.sort();
+        pretty_assertions::assert_eq!(
+            expected.into_iter().map(PathBuf::from).collect::<Vec<_>>(),
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn enumerate_tracked_files_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_tracked_files_{}", nanoid::nanoid!()));
+        let files = [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n[workspace.dependencies]\nlib = { path = \"crates/lib\" }\n",
+            ),
+            ("Cargo.lock", ""),
+            (
+                "crates/app/Cargo.toml",
+                "[package]\nname = \"app\"\n[dependencies]\nlib = { workspace = true }\n",
+            ),
+            ("crates/app/src/main.rs", ""),
+            ("crates/lib/Cargo.toml", "[package]\nname = \"lib\"\n"),
+            ("crates/lib/src/lib.rs", ""),
+            ("crates/other/Cargo.toml", "[package]\nname = \"other\"\n"),
+            ("crates/other/src/lib.rs", ""),
+            ("tools/standalone/Cargo.toml", "[package]\nname = \"standalone\"\n"),
+            ("tools/standalone/src/main.rs", ""),
+        ];
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(path, content).await.unwrap();
+        }
+        let root_dir = fs::canonicalize(&dir).await.unwrap();
+
+        let app_files = enumerate_tracked_files(dir.join("crates/app"))
+            .await
+            .unwrap();
+        let standalone_files = enumerate_tracked_files(dir.join("tools/standalone"))
+            .await
+            .unwrap();
+        fs::remove_dir_all(&dir).await.unwrap();
+        let expected = [
+            "Cargo.lock",
+            "Cargo.toml",
+            "crates/app/Cargo.toml",
+            "crates/app/src/main.rs",
+            "crates/lib/Cargo.toml",
+            "crates/lib/src/lib.rs",
+        ]
+        .map(|path| root_dir.join(path));
+        pretty_assertions::assert_eq!(expected.to_vec(), app_files);
+        // The package is not a member, so the workspace is not tracked for it.
+        let expected = [
+            "tools/standalone/Cargo.toml",
+            "tools/standalone/src/main.rs",
+        ]
+        .map(|path| root_dir.join(path));
+        pretty_assertions::assert_eq!(expected.to_vec(), standalone_files);
+    }
+}
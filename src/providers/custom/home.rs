@@ -0,0 +1,25 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[derive(Getters, Clone)]
+pub struct CustomWorkspace {
+    custom_home_dir: PathBuf,
+}
+
+impl CustomWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let custom_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        Self { custom_home_dir }
+    }
+
+    /// The prefix which the install commands install the tool into.
+    pub fn prefix_dir(&self, name: &str) -> PathBuf {
+        self.custom_home_dir.join(name)
+    }
+
+    pub fn bin_dir(&self, name: &str) -> PathBuf {
+        self.prefix_dir(name).join("bin")
+    }
+}
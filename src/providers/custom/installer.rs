@@ -0,0 +1,241 @@
+use tokio::{fs, process::Command};
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::{
+        command_ext::{run_commnad, RunCommandError},
+        fs_ext::make_hard_link,
+    },
+};
+
+use super::*;
+
+const PREFIX_ENV: &str = "ISOBIN_PREFIX";
+const BIN_DIR_ENV: &str = "ISOBIN_BIN_DIR";
+const VERSION_ENV: &str = "ISOBIN_VERSION";
+
+pub struct CustomInstallerFactory {
+    custom_workspace: CustomWorkspace,
+    workspace: Workspace,
+}
+
+impl CustomInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            custom_workspace: CustomWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for CustomInstallerFactory {
+    type InstallTarget = CustomTargetDependency;
+    type CoreInstaller = CustomCoreInstaller;
+    type BinPathInstaller = CustomBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        Ok(CustomCoreInstaller::new(self.custom_workspace.clone()))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(CustomBinPathInstaller::new(
+            self.custom_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum CustomInstallError {
+    #[error("{bin} is not found in ISOBIN_BIN_DIR after the install commands")]
+    NotFoundBin { bin: String },
+}
+
+#[derive(new, Clone)]
+pub struct CustomCoreInstaller {
+    custom_workspace: CustomWorkspace,
+}
+
+impl CustomCoreInstaller {
+    #[cfg(not(windows))]
+    fn shell_command(command: &str) -> Command {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+
+    #[cfg(windows)]
+    fn shell_command(command: &str) -> Command {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    }
+
+    async fn run_commands(
+        &self,
+        target: &CustomTargetDependency,
+        commands: &CustomCommands,
+    ) -> Result<()> {
+        let dependency = target.install_dependency();
+        for command in commands.commands() {
+            let mut shell = Self::shell_command(command);
+            shell
+                .env(PREFIX_ENV, self.custom_workspace.prefix_dir(target.name()))
+                .env(BIN_DIR_ENV, self.custom_workspace.bin_dir(target.name()))
+                .env(
+                    VERSION_ENV,
+                    dependency.version().clone().unwrap_or_default(),
+                );
+            if let Some(working_dir) = dependency.working_dir() {
+                shell.current_dir(working_dir);
+            }
+            run_commnad(shell).await.map_err(|err| -> Error {
+                match err.downcast::<RunCommandError>() {
+                    Ok(err) => InstallServiceError::new_install(
+                        ProviderKind::Custom,
+                        target.name().into(),
+                        err.stderr().into(),
+                        err.into(),
+                    )
+                    .into(),
+                    Err(err) => InstallServiceError::new_install(
+                        ProviderKind::Custom,
+                        target.name().into(),
+                        err.to_string(),
+                        err,
+                    )
+                    .into(),
+                }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl providers::CoreInstaller for CustomCoreInstaller {
+    type InstallTarget = CustomTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        let prefix_dir = self.custom_workspace.prefix_dir(target.name());
+        // The prefix only holds the tool, so a reinstallation starts from scratch.
+        if prefix_dir.exists() {
+            fs::remove_dir_all(&prefix_dir).await?;
+        }
+        fs::create_dir_all(self.custom_workspace.bin_dir(target.name())).await?;
+        self.run_commands(target, target.install_dependency().install())
+            .await?;
+        for bin in target.install_dependency().bins().iter() {
+            if !self
+                .custom_workspace
+                .bin_dir(target.name())
+                .join(bin)
+                .is_file()
+            {
+                let err = CustomInstallError::new_not_found_bin(bin.clone());
+                Err(InstallServiceError::new_install(
+                    ProviderKind::Custom,
+                    target.name().into(),
+                    err.to_string(),
+                    err.into(),
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let prefix_dir = self.custom_workspace.prefix_dir(target.name());
+        if let Some(uninstall) = target.install_dependency().uninstall() {
+            if prefix_dir.exists() {
+                self.run_commands(target, uninstall).await?;
+            }
+        }
+        if prefix_dir.exists() {
+            fs::remove_dir_all(&prefix_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct CustomTargetDependency {
+    name: String,
+    install_dependency: CustomInstallDependency,
+    mode: TargetMode,
+}
+
+impl providers::TargetDependency for CustomTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Custom
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        self.install_dependency()
+            .version()
+            .clone()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(new, Clone)]
+pub struct CustomBinPathInstaller {
+    custom_workspace: CustomWorkspace,
+    workspace: Workspace,
+}
+
+#[async_trait]
+impl BinPathInstaller for CustomBinPathInstaller {
+    type InstallTarget = CustomTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        let bin_dir = self.custom_workspace.bin_dir(target.name());
+        Ok(target
+            .install_dependency()
+            .bins()
+            .iter()
+            .filter(|bin| bin_dir.join(bin).exists())
+            .map(|bin| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(
+                        target.provider_kind(),
+                        target.name().to_string(),
+                        bin.clone(),
+                    ),
+                )
+            })
+            .collect())
+    }
+
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        let bin_dir = self.custom_workspace.bin_dir(target.name());
+        for bin in target.install_dependency().bins().iter() {
+            // The commands may put a link in the bin dir, so the file it refers to is linked.
+            let bin_path = fs::canonicalize(bin_dir.join(bin)).await?;
+            make_hard_link(bin_path, self.workspace.bin_dir().join(bin)).await?;
+        }
+        Ok(())
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for bin in target.install_dependency().bins().iter() {
+            let workspace_bin_path = self.workspace.bin_dir().join(bin);
+            if workspace_bin_path.exists() {
+                fs::remove_file(workspace_bin_path).await?;
+            }
+        }
+        Ok(())
+    }
+}
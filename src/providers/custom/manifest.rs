@@ -0,0 +1,129 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    Result,
+};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct CustomManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, CustomInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, CustomInstallDependency>,
+}
+
+impl Manifest for CustomManifest {
+    type Dependency = CustomInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Custom
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+/// One shell command or the commands which run in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CustomCommands {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl CustomCommands {
+    pub fn commands(&self) -> Vec<&str> {
+        match self {
+            Self::Single(command) => vec![command],
+            Self::Multiple(commands) => commands.iter().map(|command| command.as_str()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, new, Deserialize, Getters)]
+pub struct CustomInstallDependency {
+    /// Passed to the commands as `ISOBIN_VERSION`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// Run in the manifest dir with `ISOBIN_PREFIX` and `ISOBIN_BIN_DIR` set.
+    install: CustomCommands,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uninstall: Option<CustomCommands>,
+    /// The file names which the install commands put in `ISOBIN_BIN_DIR`.
+    bins: Vec<String>,
+    #[serde(skip)]
+    working_dir: Option<PathBuf>,
+}
+
+impl ManifestDependency for CustomInstallDependency {
+    fn validate(&self) -> Result<()> {
+        let commands = self
+            .install
+            .commands()
+            .into_iter()
+            .chain(
+                self.uninstall
+                    .iter()
+                    .flat_map(|uninstall| uninstall.commands()),
+            )
+            .collect::<Vec<_>>();
+        if self.install.commands().is_empty() {
+            Err(anyhow!("custom dependency should have install commands."))
+        } else if commands.iter().any(|command| command.trim().is_empty()) {
+            Err(anyhow!("custom dependency commands should not be empty."))
+        } else if self.bins.is_empty() {
+            Err(anyhow!("custom dependency should have bins."))
+        } else if self
+            .bins
+            .iter()
+            .any(|bin| bin.is_empty() || bin.contains(['/', '\\']))
+        {
+            Err(anyhow!(
+                "custom dependency bins should be file names in ISOBIN_BIN_DIR."
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        self.working_dir = Some(isobin_manifest_dir.to_path_buf());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        r#"{"install":"make install PREFIX=$ISOBIN_PREFIX","bins":["tool"]}"#,
+        true
+    )]
+    #[case(
+        r#"{"version":"1.0","install":["./build.sh","cp out/tool $ISOBIN_BIN_DIR"],"uninstall":"./clean.sh","bins":["tool"]}"#,
+        true
+    )]
+    #[case(r#"{"install":[],"bins":["tool"]}"#, false)]
+    #[case(r#"{"install":["./build.sh"," "],"bins":["tool"]}"#, false)]
+    #[case(r#"{"install":"./build.sh","uninstall":"","bins":["tool"]}"#, false)]
+    #[case(r#"{"install":"./build.sh","bins":[]}"#, false)]
+    #[case(r#"{"install":"./build.sh","bins":["out/tool"]}"#, false)]
+    fn validate_works(#[case] dependency: &str, #[case] expected: bool) {
+        let dependency: CustomInstallDependency = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(expected, dependency.validate().is_ok());
+    }
+}
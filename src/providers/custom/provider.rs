@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct CustomProvider;
+
+#[async_trait]
+impl Provider for CustomProvider {
+    type Manifest = CustomManifest;
+    type InstallerFactory = CustomInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Custom
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> CustomInstallerFactory {
+        CustomInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &CustomManifest,
+        name: &str,
+        dependency: &CustomInstallDependency,
+        mode: TargetMode,
+    ) -> CustomTargetDependency {
+        CustomTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
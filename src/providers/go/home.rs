@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[derive(Getters, Clone)]
+pub struct GoWorkspace {
+    go_home_dir: PathBuf,
+    /// The GOPATH shared by the tools, which holds the module cache.
+    /// It is kept out of the home dir so that the cache survives reinstallations.
+    go_path_dir: PathBuf,
+}
+
+impl GoWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let go_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        let go_path_dir = workspace.cache_dir().join(PROVIDER_NAME);
+        Self {
+            go_home_dir,
+            go_path_dir,
+        }
+    }
+
+    pub fn bin_dir(&self, name: &str) -> PathBuf {
+        self.go_home_dir.join(name).join("bin")
+    }
+}
@@ -0,0 +1,216 @@
+use tokio::{fs, process::Command};
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::{
+        command_ext::{run_commnad, RunCommandError},
+        fs_ext::{enumerate_executable_files, make_hard_links_in_dir},
+    },
+};
+
+use super::*;
+
+pub struct GoInstallerFactory {
+    go_workspace: GoWorkspace,
+    workspace: Workspace,
+}
+
+impl GoInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            go_workspace: GoWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for GoInstallerFactory {
+    type InstallTarget = GoTargetDependency;
+    type CoreInstaller = GoCoreInstaller;
+    type BinPathInstaller = GoBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        Ok(GoCoreInstaller::new(self.go_workspace.clone()))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(GoBinPathInstaller::new(
+            self.go_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+#[derive(new, Clone)]
+pub struct GoCoreInstaller {
+    go_workspace: GoWorkspace,
+}
+
+impl GoCoreInstaller {
+    fn dependency_to_args(dependency: &GoInstallDependencyDetail) -> Vec<String> {
+        // The module cache is made writable so that the workspace can be cleaned.
+        let mut args: Vec<String> = vec!["install".into(), "-modcacherw".into()];
+        match (dependency.module(), dependency.version()) {
+            (Some(module), Some(version)) => args.push(format!("{module}@{version}")),
+            (Some(module), None) => args.push(module.into()),
+            // A local module is installed in its dir.
+            (None, _) => args.push("./...".into()),
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl providers::CoreInstaller for GoCoreInstaller {
+    type InstallTarget = GoTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        let bin_dir = self.go_workspace.bin_dir(target.name());
+        // GOBIN only holds the target executables, so a reinstallation starts from scratch.
+        if bin_dir.exists() {
+            fs::remove_dir_all(&bin_dir).await?;
+        }
+        fs::create_dir_all(&bin_dir).await?;
+        let dependency = target.install_dependency().detail();
+        let mut command = Command::new(PROVIDER_NAME);
+        command
+            .args(Self::dependency_to_args(&dependency))
+            .env("GOBIN", &bin_dir)
+            .env("GOPATH", self.go_workspace.go_path_dir());
+        if let Some(absolute_path) = dependency.absolute_path() {
+            command.current_dir(absolute_path);
+        }
+        run_commnad(command)
+            .await
+            .map_err(|err| match err.downcast::<RunCommandError>() {
+                Ok(err) => InstallServiceError::new_install(
+                    ProviderKind::Go,
+                    target.name().into(),
+                    err.stderr().into(),
+                    err.into(),
+                )
+                .into(),
+                Err(err) => InstallServiceError::new_install(
+                    ProviderKind::Go,
+                    target.name().into(),
+                    err.to_string(),
+                    err,
+                )
+                .into(),
+            })
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let bin_dir = self.go_workspace.bin_dir(target.name());
+        if bin_dir.exists() {
+            fs::remove_dir_all(&bin_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct GoTargetDependency {
+    name: String,
+    install_dependency: GoInstallDependency,
+    mode: TargetMode,
+}
+
+impl providers::TargetDependency for GoTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Go
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        let dependency = self.install_dependency().detail();
+        match (dependency.module(), dependency.version(), dependency.path()) {
+            (_, _, Some(path)) => path.to_string_lossy().to_string(),
+            (Some(module), Some(version), _) => format!("{module}@{version}"),
+            (Some(module), None, _) => module.clone(),
+            (None, _, None) => String::new(),
+        }
+    }
+}
+
+#[derive(new, Clone)]
+pub struct GoBinPathInstaller {
+    go_workspace: GoWorkspace,
+    workspace: Workspace,
+}
+
+#[async_trait]
+impl BinPathInstaller for GoBinPathInstaller {
+    type InstallTarget = GoTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        let bin_paths =
+            enumerate_executable_files(self.go_workspace.bin_dir(target.name())).await?;
+        Ok(bin_paths
+            .into_iter()
+            .map(|bin_path| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(
+                        target.provider_kind(),
+                        target.name().to_string(),
+                        bin_path.file_name().unwrap().to_str().unwrap().to_string(),
+                    ),
+                )
+            })
+            .collect())
+    }
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        make_hard_links_in_dir(
+            self.go_workspace.bin_dir(target.name()),
+            self.workspace.bin_dir(),
+        )
+        .await
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        let bin_paths = self.bin_paths(target).await?;
+        for bin_path in bin_paths.iter() {
+            let file_name = bin_path.bin_dependency().bin_file_name();
+            let workspace_bin_path = self.workspace.bin_dir().join(file_name);
+            if workspace_bin_path.exists() {
+                fs::remove_file(workspace_bin_path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        r#""mvdan.cc/gofumpt@v0.5.0""#,
+        vec!["install", "-modcacherw", "mvdan.cc/gofumpt@v0.5.0"]
+    )]
+    #[case(
+        r#"{"module":"github.com/golangci/golangci-lint/cmd/golangci-lint","version":"v1.55.2"}"#,
+        vec!["install", "-modcacherw", "github.com/golangci/golangci-lint/cmd/golangci-lint@v1.55.2"]
+    )]
+    #[case(r#"{"path":"tools/hello"}"#, vec!["install", "-modcacherw", "./..."])]
+    fn dependency_to_args_works(#[case] dependency: &str, #[case] expected: Vec<&str>) {
+        let dependency: GoInstallDependency = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            GoCoreInstaller::dependency_to_args(&dependency.detail())
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    utils::fs_ext::rebase_path,
+    Result,
+};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct GoManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, GoInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, GoInstallDependency>,
+}
+
+impl Manifest for GoManifest {
+    type Dependency = GoInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Go
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GoInstallDependency {
+    /// A `module@version` entry such as `mvdan.cc/gofumpt@v0.5.0`.
+    Simple(String),
+    Detailed(GoInstallDependencyDetail),
+}
+
+impl ManifestDependency for GoInstallDependency {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Simple(_) => self.detail().validate(),
+            Self::Detailed(dependency) => dependency.validate(),
+        }
+    }
+
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.fix(isobin_manifest_dir)),
+        }
+    }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.rebase(from_dir, to_dir)),
+        }
+    }
+}
+
+impl GoInstallDependency {
+    pub fn detail(&self) -> GoInstallDependencyDetail {
+        match self {
+            Self::Simple(module_version) => match module_version.rsplit_once('@') {
+                Some((module, version)) => GoInstallDependencyDetail {
+                    module: Some(module.into()),
+                    version: Some(version.into()),
+                    ..Default::default()
+                },
+                None => GoInstallDependencyDetail {
+                    module: Some(module_version.clone()),
+                    ..Default::default()
+                },
+            },
+            Self::Detailed(dependency) => dependency.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, new, Deserialize, Getters)]
+pub struct GoInstallDependencyDetail {
+    /// The package path to install, such as `github.com/golangci/golangci-lint/cmd/golangci-lint`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// A local module directory whose main packages are installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    absolute_path: Option<PathBuf>,
+}
+
+impl GoInstallDependencyDetail {
+    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            self.absolute_path = Some(isobin_manifest_dir.join(path));
+        }
+        self
+    }
+
+    pub fn rebase(mut self, from_dir: &Path, to_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            let path = rebase_path(path, from_dir, to_dir);
+            self.absolute_path = self.absolute_path.map(|_| to_dir.join(&path));
+            self.path = Some(path);
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match (&self.module, &self.version, &self.path) {
+            (None, _, None) => Err(anyhow!("go dependency should have module or path.")),
+            (Some(_), _, Some(_)) => {
+                Err(anyhow!("go dependency cannot have both module and path."))
+            }
+            (None, Some(_), Some(_)) => {
+                Err(anyhow!("go dependency cannot have version with path."))
+            }
+            (Some(module), _, _) if module.is_empty() => {
+                Err(anyhow!("go dependency module should not be empty."))
+            }
+            (Some(_), None, _) => Err(anyhow!(
+                "go dependency should have version, such as module@latest."
+            )),
+            (Some(_), Some(version), _) if version.is_empty() => {
+                Err(anyhow!("go dependency version should not be empty."))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#""mvdan.cc/gofumpt@v0.5.0""#, true)]
+    #[case(r#""mvdan.cc/gofumpt""#, false)]
+    #[case(r#""mvdan.cc/gofumpt@""#, false)]
+    #[case(r#"{"module":"mvdan.cc/gofumpt","version":"latest"}"#, true)]
+    #[case(r#"{"path":"tools/hello"}"#, true)]
+    #[case(r#"{"path":"tools/hello","version":"v1.0.0"}"#, false)]
+    #[case(
+        r#"{"module":"mvdan.cc/gofumpt","version":"v0.5.0","path":"tools/hello"}"#,
+        false
+    )]
+    fn validate_works(#[case] dependency: &str, #[case] expected: bool) {
+        let dependency: GoInstallDependency = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(expected, dependency.validate().is_ok());
+    }
+}
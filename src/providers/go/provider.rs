@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct GoProvider;
+
+#[async_trait]
+impl Provider for GoProvider {
+    type Manifest = GoManifest;
+    type InstallerFactory = GoInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Go
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> GoInstallerFactory {
+        GoInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &GoManifest,
+        name: &str,
+        dependency: &GoInstallDependency,
+        mode: TargetMode,
+    ) -> GoTargetDependency {
+        GoTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
@@ -4,7 +4,7 @@ use super::*;
 
 #[async_trait]
 pub trait InstallerFactory: 'static + Send + Sync {
-    type InstallTarget: TargetDependency;
+    type InstallTarget: TargetDependency + Clone;
     type CoreInstaller: CoreInstaller<InstallTarget = Self::InstallTarget>;
     type BinPathInstaller: BinPathInstaller<InstallTarget = Self::InstallTarget>;
     async fn create_core_installer(&self) -> Result<Self::CoreInstaller>;
@@ -14,7 +14,6 @@ pub trait InstallerFactory: 'static + Send + Sync {
 #[async_trait]
 pub trait CoreInstaller: 'static + Send + Sync + Clone {
     type InstallTarget: TargetDependency;
-    fn provider_kind(&self) -> providers::ProviderKind;
     fn multi_install_mode(&self) -> MultiInstallMode;
     async fn install(&self, target: &Self::InstallTarget) -> Result<()>;
     async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()>;
@@ -33,7 +32,7 @@ pub enum TargetMode {
     Uninstall,
 }
 
-pub trait TargetDependency: 'static + Send + Sync + Clone {
+pub trait TargetDependency: 'static + Send + Sync {
     fn mode(&self) -> &TargetMode;
     fn provider_kind(&self) -> ProviderKind;
     fn name(&self) -> &str;
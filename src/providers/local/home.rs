@@ -0,0 +1,21 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[derive(Getters, Clone)]
+pub struct LocalWorkspace {
+    local_home_dir: PathBuf,
+}
+
+impl LocalWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let local_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        Self { local_home_dir }
+    }
+
+    /// The dir which holds the copies of the files or the links to them.
+    pub fn install_dir(&self, name: &str) -> PathBuf {
+        self.local_home_dir.join(name)
+    }
+}
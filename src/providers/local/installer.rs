@@ -0,0 +1,281 @@
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::fs_ext::{
+        make_hard_link, make_relative_symlink, make_symlink, read_dir, remove_file_if_exists,
+    },
+};
+
+use super::*;
+
+pub struct LocalInstallerFactory {
+    local_workspace: LocalWorkspace,
+    workspace: Workspace,
+}
+
+impl LocalInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            local_workspace: LocalWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for LocalInstallerFactory {
+    type InstallTarget = LocalTargetDependency;
+    type CoreInstaller = LocalCoreInstaller;
+    type BinPathInstaller = LocalBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        Ok(LocalCoreInstaller::new(self.local_workspace.clone()))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(LocalBinPathInstaller::new(
+            self.local_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum LocalInstallError {
+    #[error("{} is not found", path.display())]
+    NotFound { path: PathBuf },
+
+    #[error("{} is not executable", path.display())]
+    NotExecutable { path: PathBuf },
+
+    #[error("{bin} is not found in {}", dir.display())]
+    NotFoundBin { bin: String, dir: PathBuf },
+
+    #[error("bins can be given only when the path is a directory")]
+    BinsForFile,
+}
+
+#[derive(new, Clone)]
+pub struct LocalCoreInstaller {
+    local_workspace: LocalWorkspace,
+}
+
+impl LocalCoreInstaller {
+    /// Returns the file names to expose with the files which they refer to.
+    async fn source_entries(target: &LocalTargetDependency) -> Result<Vec<(String, PathBuf)>> {
+        let dependency = target.install_dependency().detail();
+        let path = dependency
+            .absolute_path()
+            .clone()
+            .unwrap_or_else(|| dependency.path().clone());
+        if path.is_file() {
+            if dependency.bins().is_some() {
+                Err(LocalInstallError::BinsForFile)?;
+            }
+            return Ok(vec![(file_bin_name(target.name(), &path), path)]);
+        }
+        if !path.is_dir() {
+            Err(LocalInstallError::new_not_found(path.clone()))?;
+        }
+        match dependency.bins() {
+            Some(bins) => bins
+                .iter()
+                .map(|bin| {
+                    let bin_path = path.join(bin);
+                    if bin_path.is_file() {
+                        Ok((bin.clone(), bin_path))
+                    } else {
+                        Err(LocalInstallError::new_not_found_bin(bin.clone(), path.clone()).into())
+                    }
+                })
+                .collect(),
+            None => {
+                let mut entries = vec![];
+                let mut dir = read_dir(&path).await?;
+                while let Some(entry) = dir.next_entry().await? {
+                    let bin_path = entry.path();
+                    if bin_path.is_file() && is_executable::is_executable(&bin_path) {
+                        entries.push((entry.file_name().to_string_lossy().to_string(), bin_path));
+                    }
+                }
+                entries.sort();
+                Ok(entries)
+            }
+        }
+    }
+
+    async fn install_core(&self, target: &LocalTargetDependency) -> Result<()> {
+        let entries = Self::source_entries(target).await?;
+        if let Some((_, path)) = entries
+            .iter()
+            .find(|(_, path)| !is_executable::is_executable(path))
+        {
+            Err(LocalInstallError::new_not_executable(path.clone()))?;
+        }
+        let install_dir = self.local_workspace.install_dir(target.name());
+        // The dir only holds the target files, so a reinstallation starts from scratch.
+        if install_dir.exists() {
+            fs::remove_dir_all(&install_dir).await?;
+        }
+        fs::create_dir_all(&install_dir).await?;
+        let link_mode = target
+            .install_dependency()
+            .detail()
+            .link()
+            .unwrap_or_default();
+        for (bin_name, path) in entries.iter() {
+            let installed_path = install_dir.join(bin_name);
+            match link_mode {
+                // The original is outside of the workspace, so the link keeps its absolute path.
+                LocalLinkMode::Symlink => {
+                    make_symlink(fs::canonicalize(path).await?, installed_path).await?
+                }
+                LocalLinkMode::Copy => {
+                    fs::copy(path, installed_path).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A file is exposed by the dependency name, keeping its extension on Windows where it
+/// decides how the file is run.
+fn file_bin_name(name: &str, path: &Path) -> String {
+    match path.extension() {
+        Some(extension) if cfg!(windows) => format!("{name}.{}", extension.to_string_lossy()),
+        _ => name.to_string(),
+    }
+}
+
+#[async_trait]
+impl providers::CoreInstaller for LocalCoreInstaller {
+    type InstallTarget = LocalTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        self.install_core(target).await.map_err(|err| {
+            InstallServiceError::new_install(
+                ProviderKind::Local,
+                target.name().into(),
+                err.to_string(),
+                err,
+            )
+            .into()
+        })
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let install_dir = self.local_workspace.install_dir(target.name());
+        if install_dir.exists() {
+            fs::remove_dir_all(&install_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct LocalTargetDependency {
+    name: String,
+    install_dependency: LocalInstallDependency,
+    mode: TargetMode,
+}
+
+impl providers::TargetDependency for LocalTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Local
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        self.install_dependency()
+            .detail()
+            .path()
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+#[derive(new, Clone)]
+pub struct LocalBinPathInstaller {
+    local_workspace: LocalWorkspace,
+    workspace: Workspace,
+}
+
+impl LocalBinPathInstaller {
+    /// Returns the installed files, including the links whose originals were removed.
+    async fn installed_entries(
+        &self,
+        target: &LocalTargetDependency,
+    ) -> Result<Vec<(String, PathBuf)>> {
+        let install_dir = self.local_workspace.install_dir(target.name());
+        let mut entries = vec![];
+        if install_dir.is_dir() {
+            let mut dir = read_dir(&install_dir).await?;
+            while let Some(entry) = dir.next_entry().await? {
+                entries.push((
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                ));
+            }
+        }
+        entries.sort();
+        Ok(entries)
+    }
+}
+
+#[async_trait]
+impl BinPathInstaller for LocalBinPathInstaller {
+    type InstallTarget = LocalTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        Ok(self
+            .installed_entries(target)
+            .await?
+            .into_iter()
+            .map(|(bin_name, _)| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(target.provider_kind(), target.name().to_string(), bin_name),
+                )
+            })
+            .collect())
+    }
+
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        let link_mode = target
+            .install_dependency()
+            .detail()
+            .link()
+            .unwrap_or_default();
+        for (bin_name, installed_path) in self.installed_entries(target).await?.iter() {
+            let workspace_bin_path = self.workspace.bin_dir().join(bin_name);
+            match link_mode {
+                // A relative link keeps working after the workspace is moved into place.
+                LocalLinkMode::Symlink => {
+                    make_relative_symlink(installed_path, workspace_bin_path).await?
+                }
+                LocalLinkMode::Copy => make_hard_link(installed_path, workspace_bin_path).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, _) in self.installed_entries(target).await?.iter() {
+            remove_file_if_exists(self.workspace.bin_dir().join(bin_name)).await?;
+        }
+        Ok(())
+    }
+}
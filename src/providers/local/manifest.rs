@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    utils::fs_ext::rebase_path,
+    Result,
+};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct LocalManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, LocalInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, LocalInstallDependency>,
+}
+
+impl Manifest for LocalManifest {
+    type Dependency = LocalInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Local
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LocalInstallDependency {
+    /// The path of a file or a directory relative to the manifest.
+    Simple(PathBuf),
+    Detailed(LocalInstallDependencyDetail),
+}
+
+impl ManifestDependency for LocalInstallDependency {
+    fn validate(&self) -> Result<()> {
+        self.detail().validate()
+    }
+
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        Self::Detailed(self.detail().fix(isobin_manifest_dir))
+    }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        Self::Detailed(self.detail().rebase(from_dir, to_dir))
+    }
+}
+
+impl LocalInstallDependency {
+    /// The simple form is expanded because the resolved path is kept in the detail.
+    pub fn detail(&self) -> LocalInstallDependencyDetail {
+        match self {
+            Self::Simple(path) => LocalInstallDependencyDetail {
+                path: path.clone(),
+                ..Default::default()
+            },
+            Self::Detailed(dependency) => dependency.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalLinkMode {
+    /// Links the files, so that edits take effect without reinstallation.
+    #[default]
+    Symlink,
+    /// Copies the files as they are at the installation.
+    Copy,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, new, Deserialize, Getters)]
+pub struct LocalInstallDependencyDetail {
+    /// A file, which is exposed by the dependency name, or a directory of executables.
+    path: PathBuf,
+    #[serde(skip)]
+    absolute_path: Option<PathBuf>,
+    /// The executables to expose from a directory, which defaults to all of them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<LocalLinkMode>,
+}
+
+impl LocalInstallDependencyDetail {
+    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        self.absolute_path = Some(isobin_manifest_dir.join(&self.path));
+        self
+    }
+
+    pub fn rebase(mut self, from_dir: &Path, to_dir: &Path) -> Self {
+        self.path = rebase_path(&self.path, from_dir, to_dir);
+        self.absolute_path = self.absolute_path.map(|_| to_dir.join(&self.path));
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.path.as_os_str().is_empty() {
+            Err(anyhow!("local dependency path should not be empty."))
+        } else if self
+            .bins
+            .iter()
+            .flatten()
+            .any(|bin| bin.is_empty() || bin.contains(['/', '\\']))
+        {
+            Err(anyhow!(
+                "local dependency bins should be file names in the directory."
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#""scripts/hello.sh""#, true)]
+    #[case(r#""""#, false)]
+    #[case(r#"{"path":"tools","bins":["hello"],"link":"copy"}"#, true)]
+    #[case(r#"{"path":"tools","bins":["sub/hello"]}"#, false)]
+    #[case(r#"{"path":"tools","link":"hardlink"}"#, false)]
+    fn validate_works(#[case] dependency: &str, #[case] expected: bool) {
+        let dependency = serde_json::from_str::<LocalInstallDependency>(dependency);
+        pretty_assertions::assert_eq!(
+            expected,
+            dependency.is_ok_and(|dependency| dependency.validate().is_ok())
+        );
+    }
+
+    #[rstest]
+    fn fix_works() {
+        let dependency = LocalInstallDependency::Simple("scripts/hello.sh".into());
+        pretty_assertions::assert_eq!(
+            Some(PathBuf::from("/project/scripts/hello.sh")),
+            dependency
+                .fix(Path::new("/project"))
+                .detail()
+                .absolute_path()
+                .clone()
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct LocalProvider;
+
+#[async_trait]
+impl Provider for LocalProvider {
+    type Manifest = LocalManifest;
+    type InstallerFactory = LocalInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Local
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> LocalInstallerFactory {
+        LocalInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &LocalManifest,
+        name: &str,
+        dependency: &LocalInstallDependency,
+        mode: TargetMode,
+    ) -> LocalTargetDependency {
+        LocalTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
@@ -1,16 +1,46 @@
 #[allow(unused_imports)]
 use super::*;
 use serde_derive::{Deserialize, Serialize};
-use strum_macros::{Display, IntoStaticStr};
+use strum_macros::{Display, EnumString};
 
+pub mod binary;
 pub mod cargo;
+pub mod custom;
+pub mod go;
 mod installer;
+pub mod local;
+pub mod npm;
+pub mod python;
+mod registry;
 
 pub use installer::*;
+pub use registry::*;
 
-#[derive(PartialEq, Debug, Clone, IntoStaticStr, Display, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Display, EnumString, Deserialize, Serialize)]
 pub enum ProviderKind {
     #[serde(rename = "cargo")]
     #[strum(serialize = "cargo")]
     Cargo,
+    #[serde(rename = "npm")]
+    #[strum(serialize = "npm")]
+    Npm,
+    #[serde(rename = "python")]
+    #[strum(serialize = "python")]
+    Python,
+    #[serde(rename = "go")]
+    #[strum(serialize = "go")]
+    Go,
+    #[serde(rename = "binary")]
+    #[strum(serialize = "binary")]
+    Binary,
+    #[serde(rename = "local")]
+    #[strum(serialize = "local")]
+    Local,
+    #[serde(rename = "custom")]
+    #[strum(serialize = "custom")]
+    Custom,
+    /// A provider which a library user registered by its name.
+    #[serde(untagged)]
+    #[strum(default)]
+    Other(String),
 }
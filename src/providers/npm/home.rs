@@ -0,0 +1,20 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[derive(Getters, Clone)]
+pub struct NpmWorkspace {
+    npm_home_dir: PathBuf,
+}
+
+impl NpmWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let npm_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        Self { npm_home_dir }
+    }
+
+    pub fn prefix_dir(&self, name: &str) -> PathBuf {
+        self.npm_home_dir.join(name)
+    }
+}
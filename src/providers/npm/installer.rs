@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde_derive::Deserialize;
+use tokio::{fs, process::Command};
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::{
+        command_ext::{run_commnad, RunCommandError},
+        fs_ext::{make_relative_symlink, remove_file_if_exists},
+        serde_ext::Json,
+    },
+};
+
+use super::*;
+
+const PACKAGE_JSON_FILE_NAME: &str = "package.json";
+const NODE_MODULES_DIR_NAME: &str = "node_modules";
+
+pub struct NpmInstallerFactory {
+    npm_workspace: NpmWorkspace,
+    workspace: Workspace,
+}
+
+impl NpmInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            npm_workspace: NpmWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for NpmInstallerFactory {
+    type InstallTarget = NpmTargetDependency;
+    type CoreInstaller = NpmCoreInstaller;
+    type BinPathInstaller = NpmBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        Ok(NpmCoreInstaller::new(self.npm_workspace.clone()))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(NpmBinPathInstaller::new(
+            self.npm_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+#[derive(new, Clone)]
+pub struct NpmCoreInstaller {
+    npm_workspace: NpmWorkspace,
+}
+
+impl NpmCoreInstaller {
+    fn dependency_to_args(name: &str, dependency: &NpmInstallDependencyDetail) -> Vec<String> {
+        let mut args: Vec<String> = vec![];
+        if let Some(registry) = dependency.registry() {
+            args.extend_from_slice(&["--registry".into(), registry.into()]);
+        }
+        if let Some(absolute_path) = dependency.absolute_path() {
+            // A local package is copied rather than linked, so that the workspace stays isolated.
+            args.extend_from_slice(&[
+                "--install-links".into(),
+                absolute_path.to_string_lossy().into(),
+            ]);
+        } else if let Some(version) = dependency.version() {
+            args.push(format!("{name}@{version}"));
+        } else {
+            args.push(name.into());
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl providers::CoreInstaller for NpmCoreInstaller {
+    type InstallTarget = NpmTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        let prefix_dir = self.npm_workspace.prefix_dir(target.name());
+        // The prefix only holds the target package, so a reinstallation starts from scratch.
+        if prefix_dir.exists() {
+            fs::remove_dir_all(&prefix_dir).await?;
+        }
+        fs::create_dir_all(&prefix_dir).await?;
+        let mut command = Command::new(PROVIDER_NAME);
+        let mut args: Vec<String> = vec![
+            "install".into(),
+            "--prefix".into(),
+            prefix_dir.to_string_lossy().into(),
+            "--no-audit".into(),
+            "--no-fund".into(),
+            "--loglevel".into(),
+            "error".into(),
+        ];
+        args.extend_from_slice(&Self::dependency_to_args(
+            target.name(),
+            &target.install_dependency().detail(),
+        ));
+        command.args(args);
+        run_commnad(command)
+            .await
+            .map_err(|err| match err.downcast::<RunCommandError>() {
+                Ok(err) => InstallServiceError::new_install(
+                    ProviderKind::Npm,
+                    target.name().into(),
+                    err.stderr().into(),
+                    err.into(),
+                )
+                .into(),
+                Err(err) => InstallServiceError::new_install(
+                    ProviderKind::Npm,
+                    target.name().into(),
+                    err.to_string(),
+                    err,
+                )
+                .into(),
+            })
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let prefix_dir = self.npm_workspace.prefix_dir(target.name());
+        if prefix_dir.exists() {
+            fs::remove_dir_all(&prefix_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct NpmTargetDependency {
+    name: String,
+    install_dependency: NpmInstallDependency,
+    mode: TargetMode,
+}
+
+impl providers::TargetDependency for NpmTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Npm
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        let dependency = self.install_dependency().detail();
+        if let Some(path) = dependency.path() {
+            path.to_string_lossy().to_string()
+        } else {
+            dependency.version().clone().unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct PackageJson {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    bin: Option<PackageBin>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PackageBin {
+    Single(String),
+    Multiple(BTreeMap<String, String>),
+}
+
+impl PackageJson {
+    /// Returns the bin names and their paths relative to the package dir.
+    fn bins(&self) -> Vec<(String, String)> {
+        match &self.bin {
+            Some(PackageBin::Single(path)) => self
+                .name
+                .as_ref()
+                .map(|name| {
+                    // A scoped package exposes its single bin without the scope.
+                    let bin_name = name.rsplit('/').next().unwrap_or(name);
+                    vec![(bin_name.to_string(), path.clone())]
+                })
+                .unwrap_or_default(),
+            Some(PackageBin::Multiple(bins)) => bins
+                .iter()
+                .map(|(name, path)| (name.clone(), path.clone()))
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NpmBinPathInstaller {
+    npm_workspace: NpmWorkspace,
+    workspace: Workspace,
+}
+
+impl NpmBinPathInstaller {
+    fn new(npm_workspace: NpmWorkspace, workspace: Workspace) -> Self {
+        Self {
+            npm_workspace,
+            workspace,
+        }
+    }
+
+    /// Returns the bins of the installed package with their absolute paths.
+    async fn bin_entries(&self, target: &NpmTargetDependency) -> Result<Vec<(String, PathBuf)>> {
+        let prefix_dir = self.npm_workspace.prefix_dir(target.name());
+        let prefix_package_json_path = prefix_dir.join(PACKAGE_JSON_FILE_NAME);
+        if !prefix_package_json_path.exists() {
+            return Ok(vec![]);
+        }
+        let prefix_package_json: PackageJson =
+            Json::parse_from_file(prefix_package_json_path).await?;
+        // The prefix has only the target package, whose name may differ from the dependency name when installed from a path.
+        let Some(package_name) = prefix_package_json.dependencies.keys().next() else {
+            return Ok(vec![]);
+        };
+        let package_dir = prefix_dir.join(NODE_MODULES_DIR_NAME).join(package_name);
+        let package_json: PackageJson =
+            Json::parse_from_file(package_dir.join(PACKAGE_JSON_FILE_NAME)).await?;
+        let bins = target.install_dependency().detail().bins().clone();
+        Ok(package_json
+            .bins()
+            .into_iter()
+            .filter(|(name, _)| bins.as_ref().is_none_or(|bins| bins.contains(name)))
+            .map(|(name, path)| (name, package_dir.join(path)))
+            .collect())
+    }
+
+    fn bin_link_path(&self, bin_name: &str) -> PathBuf {
+        self.workspace.bin_dir().join(bin_name)
+    }
+}
+
+#[async_trait]
+impl BinPathInstaller for NpmBinPathInstaller {
+    type InstallTarget = NpmTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        Ok(self
+            .bin_entries(target)
+            .await?
+            .into_iter()
+            .map(|(bin_name, _)| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(target.provider_kind(), target.name().to_string(), bin_name),
+                )
+            })
+            .collect())
+    }
+
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, bin_path) in self.bin_entries(target).await?.iter() {
+            // A relative link keeps working after the workspace is moved into place.
+            make_relative_symlink(bin_path, self.bin_link_path(bin_name)).await?;
+        }
+        Ok(())
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, _) in self.bin_entries(target).await?.iter() {
+            remove_file_if_exists(self.bin_link_path(bin_name)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#"{"name":"prettier","bin":"./bin/prettier.cjs"}"#, vec![("prettier", "./bin/prettier.cjs")])]
+    #[case(r#"{"name":"@scope/tool","bin":"cli.js"}"#, vec![("tool", "cli.js")])]
+    #[case(
+        r#"{"name":"markdownlint-cli","bin":{"markdownlint":"markdownlint.js","mdl":"mdl.js"}}"#,
+        vec![("markdownlint", "markdownlint.js"), ("mdl", "mdl.js")]
+    )]
+    #[case(r#"{"name":"lib"}"#, vec![])]
+    fn package_json_bins_works(#[case] package_json: &str, #[case] expected: Vec<(&str, &str)>) {
+        let package_json: PackageJson = serde_json::from_str(package_json).unwrap();
+        pretty_assertions::assert_eq!(
+            expected
+                .into_iter()
+                .map(|(name, path)| (name.to_string(), path.to_string()))
+                .collect::<Vec<_>>(),
+            package_json.bins()
+        );
+    }
+
+    #[rstest]
+    #[case(r#""^3.0.0""#, vec!["prettier@^3.0.0"])]
+    #[case(
+        r#"{"version":"3.0.0","registry":"http://localhost:4873"}"#,
+        vec!["--registry", "http://localhost:4873", "prettier@3.0.0"]
+    )]
+    fn dependency_to_args_works(#[case] dependency: &str, #[case] expected: Vec<&str>) {
+        let dependency: NpmInstallDependency = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            NpmCoreInstaller::dependency_to_args("prettier", &dependency.detail())
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    utils::fs_ext::rebase_path,
+    Result,
+};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct NpmManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, NpmInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, NpmInstallDependency>,
+}
+
+impl Manifest for NpmManifest {
+    type Dependency = NpmInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Npm
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NpmInstallDependency {
+    Simple(String),
+    Detailed(NpmInstallDependencyDetail),
+}
+
+impl ManifestDependency for NpmInstallDependency {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Simple(version) if version.is_empty() => {
+                Err(anyhow!("npm dependency version should not be empty."))
+            }
+            Self::Simple(_) => Ok(()),
+            Self::Detailed(dependency) => dependency.validate(),
+        }
+    }
+
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.fix(isobin_manifest_dir)),
+        }
+    }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.rebase(from_dir, to_dir)),
+        }
+    }
+}
+
+impl NpmInstallDependency {
+    pub fn detail(&self) -> NpmInstallDependencyDetail {
+        match self {
+            Self::Simple(version) => NpmInstallDependencyDetail {
+                version: Some(version.clone()),
+                ..Default::default()
+            },
+            Self::Detailed(dependency) => dependency.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, new, Deserialize, Getters)]
+pub struct NpmInstallDependencyDetail {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry: Option<String>,
+    /// A local package directory or tarball.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    absolute_path: Option<PathBuf>,
+}
+
+impl NpmInstallDependencyDetail {
+    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            self.absolute_path = Some(isobin_manifest_dir.join(path));
+        }
+        self
+    }
+
+    pub fn rebase(mut self, from_dir: &Path, to_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            let path = rebase_path(path, from_dir, to_dir);
+            self.absolute_path = self.absolute_path.map(|_| to_dir.join(&path));
+            self.path = Some(path);
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match (&self.version, &self.path) {
+            (None, None) => Err(anyhow!("npm dependency should have version or path.")),
+            (Some(_), Some(_)) => Err(anyhow!("npm dependency cannot have both version and path.")),
+            _ => Ok(()),
+        }
+    }
+}
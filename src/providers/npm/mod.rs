@@ -0,0 +1,13 @@
+mod home;
+mod installer;
+mod manifest;
+mod provider;
+
+#[allow(unused_imports)]
+use super::*;
+use home::*;
+pub use installer::*;
+pub use manifest::*;
+pub use provider::*;
+
+pub const PROVIDER_NAME: &str = "npm";
@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct NpmProvider;
+
+#[async_trait]
+impl Provider for NpmProvider {
+    type Manifest = NpmManifest;
+    type InstallerFactory = NpmInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Npm
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> NpmInstallerFactory {
+        NpmInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &NpmManifest,
+        name: &str,
+        dependency: &NpmInstallDependency,
+        mode: TargetMode,
+    ) -> NpmTargetDependency {
+        NpmTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::paths::workspace::Workspace;
+use std::path::PathBuf;
+
+use super::*;
+
+#[cfg(not(windows))]
+const VENV_BIN_DIR_NAME: &str = "bin";
+#[cfg(windows)]
+const VENV_BIN_DIR_NAME: &str = "Scripts";
+#[cfg(not(windows))]
+const VENV_PYTHON_FILE_NAME: &str = "python";
+#[cfg(windows)]
+const VENV_PYTHON_FILE_NAME: &str = "python.exe";
+
+#[derive(Getters, Clone)]
+pub struct PythonWorkspace {
+    python_home_dir: PathBuf,
+}
+
+impl PythonWorkspace {
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let python_home_dir = workspace.home_dir().join(PROVIDER_NAME);
+        Self { python_home_dir }
+    }
+
+    pub fn venv_dir(&self, name: &str) -> PathBuf {
+        self.python_home_dir.join(name)
+    }
+
+    pub fn venv_bin_dir(&self, name: &str) -> PathBuf {
+        self.venv_dir(name).join(VENV_BIN_DIR_NAME)
+    }
+
+    pub fn venv_python_path(&self, name: &str) -> PathBuf {
+        self.venv_bin_dir(name).join(VENV_PYTHON_FILE_NAME)
+    }
+}
@@ -0,0 +1,487 @@
+use std::path::{Path, PathBuf};
+
+use tokio::{fs, process::Command};
+
+use crate::{
+    bin_map::BinDependency,
+    install::InstallServiceError,
+    paths::workspace::Workspace,
+    utils::{
+        command_ext::{run_commnad, RunCommandError},
+        fs_ext::{make_relative_symlink, relative_path, remove_file_if_exists},
+    },
+};
+
+use super::*;
+
+const UV_COMMAND_NAME: &str = "uv";
+#[cfg(not(windows))]
+const DEFAULT_PYTHON: &str = "python3";
+#[cfg(windows)]
+const DEFAULT_PYTHON: &str = "python";
+const DIST_INFO_EXT: &str = ".dist-info";
+const RECORD_FILE_NAME: &str = "RECORD";
+const DIRECT_URL_FILE_NAME: &str = "direct_url.json";
+
+pub struct PythonInstallerFactory {
+    python_workspace: PythonWorkspace,
+    workspace: Workspace,
+}
+
+impl PythonInstallerFactory {
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            python_workspace: PythonWorkspace::from_workspace(&workspace),
+            workspace,
+        }
+    }
+}
+
+#[async_trait]
+impl InstallerFactory for PythonInstallerFactory {
+    type InstallTarget = PythonTargetDependency;
+    type CoreInstaller = PythonCoreInstaller;
+    type BinPathInstaller = PythonBinPathInstaller;
+
+    async fn create_core_installer(&self) -> Result<Self::CoreInstaller> {
+        let uv_available = Command::new(UV_COMMAND_NAME)
+            .arg("--version")
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success());
+        Ok(PythonCoreInstaller::new(
+            self.python_workspace.clone(),
+            uv_available,
+        ))
+    }
+    async fn create_bin_path_installer(&self) -> Result<Self::BinPathInstaller> {
+        Ok(PythonBinPathInstaller::new(
+            self.python_workspace.clone(),
+            self.workspace.clone(),
+        ))
+    }
+}
+
+#[derive(new, Clone)]
+pub struct PythonCoreInstaller {
+    python_workspace: PythonWorkspace,
+    uv_available: bool,
+}
+
+impl PythonCoreInstaller {
+    fn dependency_to_args(name: &str, dependency: &PythonInstallDependencyDetail) -> Vec<String> {
+        let mut args: Vec<String> = vec![];
+        if let Some(index_url) = dependency.index_url() {
+            args.extend_from_slice(&["--index-url".into(), index_url.into()]);
+        }
+        if let Some(absolute_path) = dependency.absolute_path() {
+            args.push(absolute_path.to_string_lossy().into());
+        } else if let Some(version) = dependency.version() {
+            args.push(Self::requirement(name, version));
+        } else {
+            args.push(name.into());
+        }
+        args
+    }
+
+    /// A bare version is pinned, while a version specifier such as `>=0.1` is passed as is.
+    fn requirement(name: &str, version: &str) -> String {
+        if version.starts_with(['=', '<', '>', '!', '~']) {
+            format!("{name}{version}")
+        } else {
+            format!("{name}=={version}")
+        }
+    }
+
+    fn make_venv_command(&self, target: &PythonTargetDependency) -> Command {
+        let venv_dir = self.python_workspace.venv_dir(target.name());
+        let python = target.install_dependency().detail().python().clone();
+        if self.uv_available {
+            let mut command = Command::new(UV_COMMAND_NAME);
+            command.args(["venv", "--quiet"]);
+            if let Some(python) = python {
+                command.args(["--python", &python]);
+            }
+            command.arg(venv_dir);
+            command
+        } else {
+            let mut command = Command::new(python.as_deref().unwrap_or(DEFAULT_PYTHON));
+            command.args(["-m", "venv"]).arg(venv_dir);
+            command
+        }
+    }
+
+    fn make_install_command(&self, target: &PythonTargetDependency) -> Command {
+        let venv_python_path = self.python_workspace.venv_python_path(target.name());
+        let mut command = if self.uv_available {
+            let mut command = Command::new(UV_COMMAND_NAME);
+            command
+                .args(["pip", "install", "--quiet", "--python"])
+                .arg(venv_python_path);
+            command
+        } else {
+            let mut command = Command::new(venv_python_path);
+            command.args([
+                "-m",
+                "pip",
+                "install",
+                "--quiet",
+                "--disable-pip-version-check",
+                "--no-input",
+            ]);
+            command
+        };
+        command.args(Self::dependency_to_args(
+            target.name(),
+            &target.install_dependency().detail(),
+        ));
+        command
+    }
+
+    async fn run_command(&self, target: &PythonTargetDependency, command: Command) -> Result<()> {
+        run_commnad(command)
+            .await
+            .map_err(|err| match err.downcast::<RunCommandError>() {
+                Ok(err) => InstallServiceError::new_install(
+                    ProviderKind::Python,
+                    target.name().into(),
+                    err.stderr().into(),
+                    err.into(),
+                )
+                .into(),
+                Err(err) => InstallServiceError::new_install(
+                    ProviderKind::Python,
+                    target.name().into(),
+                    err.to_string(),
+                    err,
+                )
+                .into(),
+            })
+    }
+
+    /// The scripts in a venv refer to its interpreter by the absolute path,
+    /// which is gone once the workspace is moved into place.
+    async fn make_scripts_relocatable(&self, target: &PythonTargetDependency) -> Result<()> {
+        let venv_bin_dir = self.python_workspace.venv_bin_dir(target.name());
+        let venv_bin_dir_str = venv_bin_dir.to_string_lossy();
+        let mut read_dir = fs::read_dir(&venv_bin_dir).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            let Ok(script) = String::from_utf8(fs::read(entry.path()).await?) else {
+                continue;
+            };
+            if let Some(script) = relocatable_script(&script, &venv_bin_dir_str) {
+                fs::write(entry.path(), script).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rewrites the shebang of a script which runs the interpreter in `venv_bin_dir`,
+/// so that the interpreter is found next to the script itself.
+fn relocatable_script(script: &str, venv_bin_dir: &str) -> Option<String> {
+    let venv_bin_dir = format!("{venv_bin_dir}/");
+    let (first_line, rest) = script.split_once('\n')?;
+    let (interpreter, body) = if let Some(interpreter) = first_line
+        .strip_prefix("#!")
+        .and_then(|shebang| shebang.strip_prefix(venv_bin_dir.as_str()))
+    {
+        (interpreter.trim(), rest)
+    } else if first_line == "#!/bin/sh" {
+        // pip writes this form when the interpreter path is too long for a shebang.
+        let (exec_line, rest) = rest.split_once('\n')?;
+        let interpreter = exec_line
+            .strip_prefix("'''exec' ")?
+            .strip_prefix(venv_bin_dir.as_str())?
+            .split_once(' ')?
+            .0;
+        let (_, body) = rest.split_once('\n')?;
+        (interpreter, body)
+    } else {
+        return None;
+    };
+    Some(format!(
+        "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'{interpreter}' \"$0\" \"$@\"\n' '''\n{body}"
+    ))
+}
+
+#[async_trait]
+impl providers::CoreInstaller for PythonCoreInstaller {
+    type InstallTarget = PythonTargetDependency;
+    fn multi_install_mode(&self) -> providers::MultiInstallMode {
+        providers::MultiInstallMode::Parallel
+    }
+
+    async fn install(&self, target: &Self::InstallTarget) -> Result<()> {
+        let venv_dir = self.python_workspace.venv_dir(target.name());
+        // The venv only holds the target package, so a reinstallation starts from scratch.
+        if venv_dir.exists() {
+            fs::remove_dir_all(&venv_dir).await?;
+        }
+        self.run_command(target, self.make_venv_command(target))
+            .await?;
+        self.run_command(target, self.make_install_command(target))
+            .await?;
+        if cfg!(windows) {
+            Ok(())
+        } else {
+            self.make_scripts_relocatable(target).await
+        }
+    }
+
+    async fn uninstall(&self, target: &Self::InstallTarget) -> Result<()> {
+        let venv_dir = self.python_workspace.venv_dir(target.name());
+        if venv_dir.exists() {
+            fs::remove_dir_all(&venv_dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(new, Getters, Clone)]
+pub struct PythonTargetDependency {
+    name: String,
+    install_dependency: PythonInstallDependency,
+    mode: TargetMode,
+}
+
+impl providers::TargetDependency for PythonTargetDependency {
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Python
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn mode(&self) -> &TargetMode {
+        &self.mode
+    }
+    fn summary(&self) -> String {
+        let dependency = self.install_dependency().detail();
+        if let Some(path) = dependency.path() {
+            path.to_string_lossy().to_string()
+        } else {
+            dependency.version().clone().unwrap_or_default()
+        }
+    }
+}
+
+/// Normalizes a distribution name as PEP 503 does.
+fn normalize_dist_name(name: &str) -> String {
+    name.split(['-', '_', '.'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Returns the file names in the `RECORD` of a dist which were installed into the venv bin dir.
+/// `bin_dir_from_site_packages` is the bin dir relative to the site-packages dir.
+fn record_scripts(record: &str, bin_dir_from_site_packages: &Path) -> Vec<String> {
+    record
+        .lines()
+        .filter_map(|line| {
+            // The hash and the size never contain commas, unlike the path.
+            let path = line.rsplitn(3, ',').nth(2)?.trim_matches('"');
+            let path = Path::new(path);
+            if path.parent() == Some(bin_dir_from_site_packages) {
+                path.file_name()
+                    .map(|file_name| file_name.to_string_lossy().to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct PythonBinPathInstaller {
+    python_workspace: PythonWorkspace,
+    workspace: Workspace,
+}
+
+impl PythonBinPathInstaller {
+    fn new(python_workspace: PythonWorkspace, workspace: Workspace) -> Self {
+        Self {
+            python_workspace,
+            workspace,
+        }
+    }
+
+    fn site_packages_dirs(&self, target: &PythonTargetDependency) -> Result<Vec<PathBuf>> {
+        let venv_dir = self.python_workspace.venv_dir(target.name());
+        let pattern = if cfg!(windows) {
+            venv_dir.join("Lib").join("site-packages")
+        } else {
+            venv_dir.join("lib").join("python*").join("site-packages")
+        };
+        let mut site_packages_dirs = vec![];
+        for site_packages_dir in glob::glob(&pattern.to_string_lossy())? {
+            site_packages_dirs.push(site_packages_dir?);
+        }
+        Ok(site_packages_dirs)
+    }
+
+    /// Returns the dist-info dir of the target package.
+    /// A package installed from a path is found by its `direct_url.json`, since its name may differ from the dependency name.
+    async fn target_dist_info_dir(
+        &self,
+        target: &PythonTargetDependency,
+        site_packages_dir: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let from_path = target.install_dependency().detail().path().is_some();
+        let name = normalize_dist_name(target.name());
+        let mut read_dir = fs::read_dir(site_packages_dir).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            let Some((dist_name, _version)) = dir_name
+                .strip_suffix(DIST_INFO_EXT)
+                .and_then(|dir_name| dir_name.rsplit_once('-'))
+            else {
+                continue;
+            };
+            let is_target = if from_path {
+                entry.path().join(DIRECT_URL_FILE_NAME).exists()
+            } else {
+                normalize_dist_name(dist_name) == name
+            };
+            if is_target {
+                return Ok(Some(entry.path()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the scripts of the installed package with their absolute paths.
+    async fn bin_entries(&self, target: &PythonTargetDependency) -> Result<Vec<(String, PathBuf)>> {
+        let venv_bin_dir = self.python_workspace.venv_bin_dir(target.name());
+        let bins = target.install_dependency().detail().bins().clone();
+        let mut bin_entries = vec![];
+        for site_packages_dir in self.site_packages_dirs(target)? {
+            let Some(dist_info_dir) = self
+                .target_dist_info_dir(target, &site_packages_dir)
+                .await?
+            else {
+                continue;
+            };
+            let record = fs::read_to_string(dist_info_dir.join(RECORD_FILE_NAME)).await?;
+            bin_entries.extend(
+                record_scripts(&record, &relative_path(&site_packages_dir, &venv_bin_dir))
+                    .into_iter()
+                    .filter(|name| bins.as_ref().is_none_or(|bins| bins.contains(name)))
+                    .map(|name| {
+                        let path = venv_bin_dir.join(&name);
+                        (name, path)
+                    })
+                    .filter(|(_, path)| path.exists()),
+            );
+        }
+        Ok(bin_entries)
+    }
+
+    fn bin_link_path(&self, bin_name: &str) -> PathBuf {
+        self.workspace.bin_dir().join(bin_name)
+    }
+}
+
+#[async_trait]
+impl BinPathInstaller for PythonBinPathInstaller {
+    type InstallTarget = PythonTargetDependency;
+
+    async fn bin_paths(&self, target: &Self::InstallTarget) -> Result<Vec<TargetBinDependency>> {
+        Ok(self
+            .bin_entries(target)
+            .await?
+            .into_iter()
+            .map(|(bin_name, _)| {
+                TargetBinDependency::new(
+                    target.mode().clone(),
+                    BinDependency::new(target.provider_kind(), target.name().to_string(), bin_name),
+                )
+            })
+            .collect())
+    }
+
+    async fn install_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, bin_path) in self.bin_entries(target).await?.iter() {
+            // A relative link keeps working after the workspace is moved into place.
+            make_relative_symlink(bin_path, self.bin_link_path(bin_name)).await?;
+        }
+        Ok(())
+    }
+
+    async fn uninstall_bin_path(&self, target: &Self::InstallTarget) -> Result<()> {
+        for (bin_name, _) in self.bin_entries(target).await?.iter() {
+            remove_file_if_exists(self.bin_link_path(bin_name)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#""0.1.6""#, vec!["ruff==0.1.6"])]
+    #[case(r#"">=0.1,<0.2""#, vec!["ruff>=0.1,<0.2"])]
+    #[case(
+        r#"{"version":"0.1.6","index_url":"http://localhost:8080/simple"}"#,
+        vec!["--index-url", "http://localhost:8080/simple", "ruff==0.1.6"]
+    )]
+    fn dependency_to_args_works(#[case] dependency: &str, #[case] expected: Vec<&str>) {
+        let dependency: PythonInstallDependency = serde_json::from_str(dependency).unwrap();
+        pretty_assertions::assert_eq!(
+            expected,
+            PythonCoreInstaller::dependency_to_args("ruff", &dependency.detail())
+        );
+    }
+
+    #[rstest]
+    #[case("ruff", "ruff")]
+    #[case("pre_commit", "pre-commit")]
+    #[case("PyYAML", "pyyaml")]
+    #[case("zope.interface", "zope-interface")]
+    fn normalize_dist_name_works(#[case] name: &str, #[case] expected: &str) {
+        pretty_assertions::assert_eq!(expected, normalize_dist_name(name));
+    }
+
+    #[rstest]
+    #[case(
+        r#"../../../bin/ruff,sha256=abc,123
+ruff-0.1.6.dist-info/RECORD,,
+ruff/__init__.py,sha256=def,0"#,
+        vec!["ruff"]
+    )]
+    #[case(
+        r#"../../../bin/pre-commit,sha256=abc,250
+"pre_commit/a,b.py",sha256=def,10
+pre_commit/__main__.py,sha256=ghi,20"#,
+        vec!["pre-commit"]
+    )]
+    fn record_scripts_works(#[case] record: &str, #[case] expected: Vec<&str>) {
+        pretty_assertions::assert_eq!(expected, record_scripts(record, Path::new("../../../bin")));
+    }
+
+    #[rstest]
+    #[case(
+        "#!/w/python/ruff/bin/python\n# -*- coding: utf-8 -*-\nimport sys\n",
+        Some("#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'python' \"$0\" \"$@\"\n' '''\n# -*- coding: utf-8 -*-\nimport sys\n")
+    )]
+    #[case(
+        "#!/bin/sh\n'''exec' /w/python/ruff/bin/python3 \"$0\" \"$@\"\n' '''\nimport sys\n",
+        Some("#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'python3' \"$0\" \"$@\"\n' '''\nimport sys\n")
+    )]
+    #[case("#!/usr/bin/env python\nimport sys\n", None)]
+    #[case("#!/bin/sh\necho ok\n", None)]
+    fn relocatable_script_works(#[case] script: &str, #[case] expected: Option<&str>) {
+        pretty_assertions::assert_eq!(
+            expected.map(|s| s.to_string()),
+            relocatable_script(script, "/w/python/ruff/bin")
+        );
+    }
+}
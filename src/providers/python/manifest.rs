@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{Manifest, ManifestDependency},
+    providers::ProviderKind,
+    utils::fs_ext::rebase_path,
+    Result,
+};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, new, Default, Getters)]
+pub struct PythonManifest {
+    #[serde(
+        serialize_with = "toml::ser::tables_last",
+        skip_serializing_if = "HashMap::<String, PythonInstallDependency>::is_empty"
+    )]
+    dependencies: HashMap<String, PythonInstallDependency>,
+}
+
+impl Manifest for PythonManifest {
+    type Dependency = PythonInstallDependency;
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Python
+    }
+    fn dependencies(&self) -> &HashMap<String, Self::Dependency> {
+        &self.dependencies
+    }
+    fn make_from_new_dependencies(&self, dependencies: HashMap<String, Self::Dependency>) -> Self {
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PythonInstallDependency {
+    Simple(String),
+    Detailed(PythonInstallDependencyDetail),
+}
+
+impl ManifestDependency for PythonInstallDependency {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Simple(version) if version.is_empty() => {
+                Err(anyhow!("python dependency version should not be empty."))
+            }
+            Self::Simple(_) => Ok(()),
+            Self::Detailed(dependency) => dependency.validate(),
+        }
+    }
+
+    fn fix(self, isobin_manifest_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.fix(isobin_manifest_dir)),
+        }
+    }
+
+    fn rebase(self, from_dir: &Path, to_dir: &Path) -> Self {
+        match self {
+            Self::Simple(_) => self,
+            Self::Detailed(dependency) => Self::Detailed(dependency.rebase(from_dir, to_dir)),
+        }
+    }
+}
+
+impl PythonInstallDependency {
+    pub fn detail(&self) -> PythonInstallDependencyDetail {
+        match self {
+            Self::Simple(version) => PythonInstallDependencyDetail {
+                version: Some(version.clone()),
+                ..Default::default()
+            },
+            Self::Detailed(dependency) => dependency.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, new, Deserialize, Getters)]
+pub struct PythonInstallDependencyDetail {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_url: Option<String>,
+    /// The interpreter which creates the venv, such as `python3.11`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    python: Option<String>,
+    /// A local project directory, sdist or wheel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    absolute_path: Option<PathBuf>,
+}
+
+impl PythonInstallDependencyDetail {
+    pub fn fix(mut self, isobin_manifest_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            self.absolute_path = Some(isobin_manifest_dir.join(path));
+        }
+        self
+    }
+
+    pub fn rebase(mut self, from_dir: &Path, to_dir: &Path) -> Self {
+        if let Some(path) = &self.path {
+            let path = rebase_path(path, from_dir, to_dir);
+            self.absolute_path = self.absolute_path.map(|_| to_dir.join(&path));
+            self.path = Some(path);
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match (&self.version, &self.path) {
+            (None, None) => Err(anyhow!("python dependency should have version or path.")),
+            (Some(_), Some(_)) => Err(anyhow!(
+                "python dependency cannot have both version and path."
+            )),
+            _ => Ok(()),
+        }
+    }
+}
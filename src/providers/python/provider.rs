@@ -0,0 +1,27 @@
+use crate::paths::workspace::Workspace;
+
+use super::*;
+
+pub struct PythonProvider;
+
+#[async_trait]
+impl Provider for PythonProvider {
+    type Manifest = PythonManifest;
+    type InstallerFactory = PythonInstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Python
+    }
+    fn make_installer_factory(&self, workspace: Workspace, _quiet: bool) -> PythonInstallerFactory {
+        PythonInstallerFactory::new(workspace)
+    }
+    fn make_target(
+        &self,
+        _manifest: &PythonManifest,
+        name: &str,
+        dependency: &PythonInstallDependency,
+        mode: TargetMode,
+    ) -> PythonTargetDependency {
+        PythonTargetDependency::new(name.into(), dependency.clone(), mode)
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    path::Path,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    install::{InstallRunner, InstallRunnerProvider},
+    list::{ListItem, ListService},
+    manifest::{Manifest, ProviderManifest},
+    paths::workspace::Workspace,
+    IsobinManifestWarning,
+};
+
+use super::*;
+
+pub type ProviderDependency<P> = <<P as Provider>::Manifest as Manifest>::Dependency;
+pub type ProviderTarget<P> = <<P as Provider>::InstallerFactory as InstallerFactory>::InstallTarget;
+
+/// Supplies the manifest section, the diff logic and the installers of a provider, so that
+/// isobin installs its dependencies without knowing the provider.
+#[async_trait]
+pub trait Provider: 'static + Send + Sync {
+    /// The section of the manifest under the provider name.
+    type Manifest: ProviderManifest;
+    type InstallerFactory: InstallerFactory;
+
+    fn provider_kind(&self) -> ProviderKind;
+
+    fn validate(&self, manifest: &Self::Manifest) -> Result<()> {
+        manifest.validate()
+    }
+
+    fn warnings(&self, _manifest: &Self::Manifest) -> Vec<IsobinManifestWarning> {
+        vec![]
+    }
+
+    /// Resolves the settings which are relative to the manifest dir.
+    fn fix(&self, manifest: Self::Manifest, isobin_manifest_dir: &Path) -> Self::Manifest {
+        manifest.fix(isobin_manifest_dir)
+    }
+
+    /// Makes the paths relative to `from_dir` relative to `to_dir`, for the manifests which are
+    /// included from another dir, so they resolve the same after reloaded from the cache.
+    fn rebase(&self, manifest: Self::Manifest, from_dir: &Path, to_dir: &Path) -> Self::Manifest {
+        manifest.rebase(from_dir, to_dir)
+    }
+
+    /// Returns the dependencies of `base` which are not installed as `old` describes.
+    /// By default an entry is installed again when it differs from the installed one.
+    async fn get_need_install_dependency_manifest(
+        &self,
+        base: &Self::Manifest,
+        old: &Self::Manifest,
+        _workspace: &Workspace,
+    ) -> Result<Self::Manifest> {
+        Ok(Manifest::get_need_install_dependency_manifest(base, old))
+    }
+
+    /// Returns the dependencies of `old` which `base` does not have anymore.
+    async fn get_need_uninstall_dependency_manifest(
+        &self,
+        base: &Self::Manifest,
+        old: &Self::Manifest,
+    ) -> Result<Self::Manifest> {
+        Ok(Manifest::get_need_uninstall_dependency_manifest(base, old))
+    }
+
+    async fn ditect_difference(
+        &self,
+        manifest: &Self::Manifest,
+        other: &Self::Manifest,
+        name: &str,
+        _workspace: &Workspace,
+    ) -> Result<bool> {
+        Ok(Manifest::ditect_difference(manifest, other, name))
+    }
+
+    /// `quiet` suppresses the messages which the installers print besides the progress.
+    fn make_installer_factory(&self, workspace: Workspace, quiet: bool) -> Self::InstallerFactory;
+
+    fn make_target(
+        &self,
+        manifest: &Self::Manifest,
+        name: &str,
+        dependency: &ProviderDependency<Self>,
+        mode: TargetMode,
+    ) -> ProviderTarget<Self>;
+
+    /// Makes the target which `list` summarizes a dependency with.
+    async fn make_list_target(
+        &self,
+        manifest: &Self::Manifest,
+        name: &str,
+        dependency: &ProviderDependency<Self>,
+        _workspace: &Workspace,
+    ) -> Result<ProviderTarget<Self>> {
+        Ok(self.make_target(manifest, name, dependency, TargetMode::AlreadyInstalled))
+    }
+}
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum ProviderRegistryError {
+    #[error("{provider} provider is already registered")]
+    AlreadyRegistered { provider: ProviderKind },
+}
+
+static PROVIDER_REGISTRY: LazyLock<RwLock<ProviderRegistry>> =
+    LazyLock::new(|| RwLock::new(ProviderRegistry::default()));
+
+/// Registers a provider, whose section is read from the manifests loaded afterwards.
+pub fn register_provider(provider: impl Provider) -> Result<()> {
+    PROVIDER_REGISTRY
+        .write()
+        .expect("the provider registry is not poisoned")
+        .register(provider)
+}
+
+/// The providers which are registered at the moment, for the manifests loaded without a registry.
+pub(crate) fn registered_provider_registry() -> ProviderRegistry {
+    PROVIDER_REGISTRY
+        .read()
+        .expect("the provider registry is not poisoned")
+        .clone()
+}
+
+#[derive(Clone)]
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn AnyProvider>>,
+}
+
+impl fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.providers
+                    .iter()
+                    .map(|provider| provider.provider_kind()),
+            )
+            .finish()
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self {
+            providers: vec![
+                Arc::new(cargo::CargoProvider),
+                Arc::new(npm::NpmProvider),
+                Arc::new(python::PythonProvider),
+                Arc::new(go::GoProvider),
+                Arc::new(binary::BinaryProvider),
+                Arc::new(local::LocalProvider),
+                Arc::new(custom::CustomProvider),
+            ],
+        }
+    }
+}
+
+impl ProviderRegistry {
+    pub fn register(&mut self, provider: impl Provider) -> Result<()> {
+        let provider_kind = provider.provider_kind();
+        if self
+            .providers
+            .iter()
+            .any(|registered| registered.provider_kind() == provider_kind)
+        {
+            Err(ProviderRegistryError::new_already_registered(provider_kind).into())
+        } else {
+            self.providers.push(Arc::new(provider));
+            Ok(())
+        }
+    }
+
+    /// The providers in the order of the registration.
+    pub(crate) fn providers(&self) -> &[Arc<dyn AnyProvider>] {
+        &self.providers
+    }
+
+    pub(crate) fn find(&self, provider_kind: &ProviderKind) -> Option<&Arc<dyn AnyProvider>> {
+        self.providers
+            .iter()
+            .find(|provider| &provider.provider_kind() == provider_kind)
+    }
+}
+
+/// A provider whose manifest section is handled without knowing its type.
+#[async_trait]
+pub(crate) trait AnyProvider: 'static + Send + Sync {
+    fn provider_kind(&self) -> ProviderKind;
+    fn default_manifest(&self) -> Box<dyn AnyManifest>;
+    fn deserialize_manifest<'de>(
+        &self,
+        deserializer: &mut dyn erased_serde::Deserializer<'de>,
+    ) -> std::result::Result<Box<dyn AnyManifest>, erased_serde::Error>;
+    fn validate(&self, manifest: &dyn AnyManifest) -> Result<()>;
+    fn warnings(&self, manifest: &dyn AnyManifest) -> Vec<IsobinManifestWarning>;
+    fn fix(&self, manifest: &dyn AnyManifest, isobin_manifest_dir: &Path) -> Box<dyn AnyManifest>;
+    fn rebase(
+        &self,
+        manifest: &dyn AnyManifest,
+        from_dir: &Path,
+        to_dir: &Path,
+    ) -> Box<dyn AnyManifest>;
+    async fn get_need_install_dependency_manifest(
+        &self,
+        base: &dyn AnyManifest,
+        old: &dyn AnyManifest,
+        workspace: &Workspace,
+    ) -> Result<Box<dyn AnyManifest>>;
+    async fn get_need_uninstall_dependency_manifest(
+        &self,
+        base: &dyn AnyManifest,
+        old: &dyn AnyManifest,
+    ) -> Result<Box<dyn AnyManifest>>;
+    async fn ditect_difference(
+        &self,
+        manifest: &dyn AnyManifest,
+        other: &dyn AnyManifest,
+        name: &str,
+        workspace: &Workspace,
+    ) -> Result<bool>;
+    async fn make_runner(
+        &self,
+        install_runner_provider: &InstallRunnerProvider,
+        workspace: &Workspace,
+        specified_manifest: &dyn AnyManifest,
+        install_target_manifest: &dyn AnyManifest,
+        uninstall_target_manifest: &dyn AnyManifest,
+    ) -> Result<Arc<Mutex<dyn InstallRunner>>>;
+    async fn make_list_items(
+        &self,
+        manifest: &dyn AnyManifest,
+        cache_manifest: &dyn AnyManifest,
+        workspace: &Workspace,
+        bins: &mut HashMap<(String, String), Vec<String>>,
+    ) -> Result<Vec<ListItem>>;
+}
+
+fn downcast<M: ProviderManifest>(manifest: &dyn AnyManifest) -> &M {
+    manifest
+        .as_any()
+        .downcast_ref()
+        .expect("a section is combined only with the sections of the same provider")
+}
+
+#[async_trait]
+impl<P: Provider> AnyProvider for P {
+    fn provider_kind(&self) -> ProviderKind {
+        Provider::provider_kind(self)
+    }
+    fn default_manifest(&self) -> Box<dyn AnyManifest> {
+        Box::<P::Manifest>::default()
+    }
+    fn deserialize_manifest<'de>(
+        &self,
+        deserializer: &mut dyn erased_serde::Deserializer<'de>,
+    ) -> std::result::Result<Box<dyn AnyManifest>, erased_serde::Error> {
+        Ok(Box::new(erased_serde::deserialize::<P::Manifest>(
+            deserializer,
+        )?))
+    }
+    fn validate(&self, manifest: &dyn AnyManifest) -> Result<()> {
+        Provider::validate(self, downcast(manifest))
+    }
+    fn warnings(&self, manifest: &dyn AnyManifest) -> Vec<IsobinManifestWarning> {
+        Provider::warnings(self, downcast(manifest))
+    }
+    fn fix(&self, manifest: &dyn AnyManifest, isobin_manifest_dir: &Path) -> Box<dyn AnyManifest> {
+        Box::new(Provider::fix(
+            self,
+            downcast::<P::Manifest>(manifest).clone(),
+            isobin_manifest_dir,
+        ))
+    }
+    fn rebase(
+        &self,
+        manifest: &dyn AnyManifest,
+        from_dir: &Path,
+        to_dir: &Path,
+    ) -> Box<dyn AnyManifest> {
+        Box::new(Provider::rebase(
+            self,
+            downcast::<P::Manifest>(manifest).clone(),
+            from_dir,
+            to_dir,
+        ))
+    }
+    async fn get_need_install_dependency_manifest(
+        &self,
+        base: &dyn AnyManifest,
+        old: &dyn AnyManifest,
+        workspace: &Workspace,
+    ) -> Result<Box<dyn AnyManifest>> {
+        Ok(Box::new(
+            Provider::get_need_install_dependency_manifest(
+                self,
+                downcast(base),
+                downcast(old),
+                workspace,
+            )
+            .await?,
+        ))
+    }
+    async fn get_need_uninstall_dependency_manifest(
+        &self,
+        base: &dyn AnyManifest,
+        old: &dyn AnyManifest,
+    ) -> Result<Box<dyn AnyManifest>> {
+        Ok(Box::new(
+            Provider::get_need_uninstall_dependency_manifest(self, downcast(base), downcast(old))
+                .await?,
+        ))
+    }
+    async fn ditect_difference(
+        &self,
+        manifest: &dyn AnyManifest,
+        other: &dyn AnyManifest,
+        name: &str,
+        workspace: &Workspace,
+    ) -> Result<bool> {
+        Provider::ditect_difference(self, downcast(manifest), downcast(other), name, workspace)
+            .await
+    }
+    async fn make_runner(
+        &self,
+        install_runner_provider: &InstallRunnerProvider,
+        workspace: &Workspace,
+        specified_manifest: &dyn AnyManifest,
+        install_target_manifest: &dyn AnyManifest,
+        uninstall_target_manifest: &dyn AnyManifest,
+    ) -> Result<Arc<Mutex<dyn InstallRunner>>> {
+        install_runner_provider
+            .make_provider_runner(
+                self,
+                workspace,
+                downcast(specified_manifest),
+                downcast(install_target_manifest),
+                downcast(uninstall_target_manifest),
+            )
+            .await
+    }
+    async fn make_list_items(
+        &self,
+        manifest: &dyn AnyManifest,
+        cache_manifest: &dyn AnyManifest,
+        workspace: &Workspace,
+        bins: &mut HashMap<(String, String), Vec<String>>,
+    ) -> Result<Vec<ListItem>> {
+        ListService::make_provider_list_items(
+            self,
+            downcast(manifest),
+            downcast(cache_manifest),
+            workspace,
+            bins,
+        )
+        .await
+    }
+}
+
+/// A manifest section which is handled without knowing its type.
+pub(crate) trait AnyManifest: 'static + Send + Sync + fmt::Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize;
+    fn clone_box(&self) -> Box<dyn AnyManifest>;
+    fn eq_dyn(&self, other: &dyn AnyManifest) -> bool;
+    fn is_empty(&self) -> bool;
+    fn names(&self) -> Vec<String>;
+    fn make_empty(&self) -> Box<dyn AnyManifest>;
+    fn filter_target(&self, name: &str) -> Result<Box<dyn AnyManifest>>;
+    fn merge(&self, new_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest>;
+    fn inherit(&self, included_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest>;
+    fn remove_targets(&self, remove_target_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest>;
+}
+
+impl<M: ProviderManifest> AnyManifest for M {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize {
+        self
+    }
+    fn clone_box(&self) -> Box<dyn AnyManifest> {
+        Box::new(self.clone())
+    }
+    fn eq_dyn(&self, other: &dyn AnyManifest) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<M>()
+            .is_some_and(|other| self == other)
+    }
+    fn is_empty(&self) -> bool {
+        Manifest::is_empty(self)
+    }
+    fn names(&self) -> Vec<String> {
+        let mut names = self.dependencies().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+    fn make_empty(&self) -> Box<dyn AnyManifest> {
+        Box::new(Manifest::make_empty(self))
+    }
+    fn filter_target(&self, name: &str) -> Result<Box<dyn AnyManifest>> {
+        Ok(Box::new(Manifest::filter_target(self, name)?))
+    }
+    fn merge(&self, new_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest> {
+        Box::new(Manifest::merge(self, downcast(new_manifest)))
+    }
+    fn inherit(&self, included_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest> {
+        Box::new(Manifest::inherit(self, downcast(included_manifest)))
+    }
+    fn remove_targets(&self, remove_target_manifest: &dyn AnyManifest) -> Box<dyn AnyManifest> {
+        Box::new(Manifest::remove_targets(
+            self,
+            downcast(remove_target_manifest),
+        ))
+    }
+}
+
+impl Clone for Box<dyn AnyManifest> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for Box<dyn AnyManifest> {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_dyn(other.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::IsobinManifest;
+    use npm::{NpmInstallDependency, NpmInstallerFactory, NpmManifest, NpmTargetDependency};
+    use rstest::*;
+
+    /// A provider outside of the builtins, which reuses the npm section for the test.
+    struct ExampleProvider;
+
+    #[async_trait]
+    impl Provider for ExampleProvider {
+        type Manifest = NpmManifest;
+        type InstallerFactory = NpmInstallerFactory;
+
+        fn provider_kind(&self) -> ProviderKind {
+            ProviderKind::Other("example".into())
+        }
+        fn make_installer_factory(
+            &self,
+            workspace: Workspace,
+            _quiet: bool,
+        ) -> NpmInstallerFactory {
+            NpmInstallerFactory::new(workspace)
+        }
+        fn make_target(
+            &self,
+            _manifest: &NpmManifest,
+            name: &str,
+            dependency: &NpmInstallDependency,
+            mode: TargetMode,
+        ) -> NpmTargetDependency {
+            NpmTargetDependency::new(name.into(), dependency.clone(), mode)
+        }
+    }
+
+    #[rstest]
+    fn register_builtin_provider_fails() {
+        let mut registry = ProviderRegistry::default();
+        assert!(registry.register(cargo::CargoProvider).is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn registered_provider_section_works() {
+        let mut provider_registry = ProviderRegistry::default();
+        provider_registry.register(ExampleProvider).unwrap();
+        assert!(provider_registry.register(ExampleProvider).is_err());
+        let dir = std::env::temp_dir().join(format!("isobin_registry_{}", nanoid::nanoid!()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("isobin.toml");
+        tokio::fs::write(&path, "[example.dependencies]\nprettier = \"3.0.0\"\n")
+            .await
+            .unwrap();
+        let isobin_manifest =
+            IsobinManifest::load_from_file_with_registry(&path, &provider_registry)
+                .await
+                .unwrap();
+        pretty_assertions::assert_eq!(
+            vec![(
+                ProviderKind::Other("example".into()),
+                "prettier".to_string()
+            )],
+            isobin_manifest.dependency_names()
+        );
+        pretty_assertions::assert_eq!(
+            "[example.dependencies]\nprettier = \"3.0.0\"\n",
+            toml::to_string(&isobin_manifest).unwrap()
+        );
+        // The manifests loaded without the registry do not know the provider.
+        let isobin_manifest = IsobinManifest::load_from_file(&path).await.unwrap();
+        assert!(isobin_manifest.dependency_names().is_empty());
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        assert!(
+            SpecifiedTarget::parse_with_registry("example/prettier", &provider_registry).is_ok()
+        );
+        assert!("example/prettier".parse::<SpecifiedTarget>().is_err());
+    }
+}
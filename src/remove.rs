@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+
+use crate::{
+    flex_eprintln,
+    manifest::{DependencyLocation, IsobinManifest, IsobinManifestCache, IsobinManifestError},
+    manifest_editor::{IsobinManifestEditor, IsobinManifestEditorError},
+    paths::{
+        isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+        workspace::WorkspaceProvider,
+    },
+    utils::{
+        fs_ext::{self, copy_dir},
+        io_ext,
+    },
+    InstallService, Result, SpecifiedTarget,
+};
+
+#[derive(Default, new)]
+pub struct RemoveService {
+    install_service: InstallService,
+    workspace_provider: WorkspaceProvider,
+}
+
+impl RemoveService {
+    pub async fn remove(&self, remove_service_option: RemoveServiceOption) -> Result<()> {
+        let remove_service_option = remove_service_option.fix().await?;
+        let isobin_manifest =
+            IsobinManifest::load_from_file(remove_service_option.isobin_manifest_path()).await?;
+        let remove_target_isobin_manifest =
+            isobin_manifest.filter_target(remove_service_option.targets())?;
+        let mut isobin_manifest_editor =
+            IsobinManifestEditor::load_from_file(remove_service_option.isobin_manifest_path())
+                .await?;
+        for (provider_kind, name) in remove_target_isobin_manifest.dependency_names().iter() {
+            let locations = IsobinManifest::locate_dependency(
+                remove_service_option.isobin_manifest_path(),
+                provider_kind,
+                name,
+            )
+            .await?;
+            // Removing the entry of the manifest file would leave the included one in effect.
+            if let Some(DependencyLocation::Include { path }) = locations
+                .iter()
+                .find(|location| matches!(location, DependencyLocation::Include { .. }))
+            {
+                Err(IsobinManifestError::new_defined_in_include(
+                    name.clone(),
+                    io_ext::path_to_string(path),
+                ))?;
+            }
+            // The entry is removed from every platform table too, not only the one of the host.
+            for location in locations.iter() {
+                if let DependencyLocation::Section { platform } = location {
+                    if !isobin_manifest_editor.remove_dependency(
+                        platform.as_deref(),
+                        provider_kind,
+                        name,
+                    )? {
+                        Err(IsobinManifestEditorError::new_not_found_dependency(
+                            io_ext::path_to_string(remove_service_option.isobin_manifest_path()),
+                            name.clone(),
+                        ))?;
+                    }
+                }
+            }
+        }
+
+        let isobin_manifest_dir =
+            isobin_manifest_dir(remove_service_option.isobin_manifest_path())?;
+        let workspace = self
+            .workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(isobin_manifest_dir)
+            .await?;
+        let isobin_manifest_cache =
+            IsobinManifestCache::lenient_load_cache_from_dir(workspace.base_dir()).await;
+        let uninstall_target_isobin_manifest =
+            IsobinManifest::get_need_uninstall_dependency_manifest(
+                &isobin_manifest_cache.remove_targets(&remove_target_isobin_manifest),
+                &isobin_manifest_cache,
+            )
+            .await?;
+        if uninstall_target_isobin_manifest.is_empty() {
+            flex_eprintln!(
+                remove_service_option.quiet,
+                "The remove targets are not installed."
+            );
+            return isobin_manifest_editor.save().await;
+        }
+
+        let tmp_workspace = workspace.make_tmp_workspace();
+        fs_ext::create_dir_if_not_exists(tmp_workspace.base_dir()).await?;
+        copy_dir(
+            workspace.base_dir().clone(),
+            tmp_workspace.base_dir().clone(),
+        )
+        .await?;
+        let save_isobin_manifest =
+            isobin_manifest_cache.remove_targets(&uninstall_target_isobin_manifest);
+
+        // The manifest keeps the entries until the uninstall in the tmp workspace has replaced
+        // the workspace, so a failed uninstall leaves both of them as they were.
+        self.install_service
+            .run_install(
+                &workspace,
+                &tmp_workspace,
+                &save_isobin_manifest,
+                &IsobinManifest::default(),
+                &IsobinManifest::default(),
+                &uninstall_target_isobin_manifest,
+                remove_service_option.quiet,
+            )
+            .await?;
+        isobin_manifest_editor.save().await
+    }
+}
+
+#[derive(Getters)]
+pub struct RemoveServiceOptionBase<P> {
+    quiet: bool,
+    targets: Vec<SpecifiedTarget>,
+    isobin_manifest_path: P,
+}
+
+pub type RemoveServiceOption = RemoveServiceOptionBase<Option<PathBuf>>;
+type FixedRemoveServiceOption = RemoveServiceOptionBase<PathBuf>;
+
+impl RemoveServiceOption {
+    async fn fix(self) -> Result<FixedRemoveServiceOption> {
+        let isobin_manifest_path =
+            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+        Ok(FixedRemoveServiceOption {
+            quiet: self.quiet,
+            targets: self.targets,
+            isobin_manifest_path,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct RemoveServiceOptionBuilder {
+    quiet: bool,
+    targets: Vec<SpecifiedTarget>,
+    isobin_manifest_path: Option<PathBuf>,
+}
+
+impl RemoveServiceOptionBuilder {
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+    pub fn targets(mut self, targets: Vec<SpecifiedTarget>) -> Self {
+        self.targets = targets;
+        self
+    }
+    pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
+        self.isobin_manifest_path = Some(isobin_manifest_path);
+        self
+    }
+    pub fn build(self) -> RemoveServiceOption {
+        RemoveServiceOption {
+            quiet: self.quiet,
+            targets: self.targets,
+            isobin_manifest_path: self.isobin_manifest_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{install::InstallServiceOptionBuilder, paths::project::Project, InstallMode};
+    use std::path::Path;
+    use tokio::fs;
+
+    const ISOBIN_MANIFEST: &str = r#"# The tools of the project.
+[local.dependencies]
+fmt = "scripts/fmt.sh" # Formats the code.
+lint = "scripts/lint.sh"
+test = "scripts/test.sh"
+"#;
+
+    /// Installs the local tools of the manifest into a workspace under `dir`.
+    async fn prepare(dir: &Path, isobin_manifest: &str) -> (WorkspaceProvider, PathBuf) {
+        let project_dir = dir.join("project");
+        for name in ["fmt", "lint", "test"] {
+            let path = project_dir.join(format!("scripts/{name}.sh"));
+            fs_ext::smart_create_file(&path).await.unwrap();
+            fs::write(&path, "#!/bin/sh\n").await.unwrap();
+            fs_ext::set_executable(&path).await.unwrap();
+        }
+        let isobin_manifest_path = project_dir.join("isobin.toml");
+        fs::write(&isobin_manifest_path, isobin_manifest)
+            .await
+            .unwrap();
+        let workspace_provider = WorkspaceProvider::new(Project::new(
+            dir.join("config"),
+            dir.join("cache"),
+            dir.join("data"),
+        ));
+        InstallService::new(workspace_provider.clone())
+            .install(
+                InstallServiceOptionBuilder::default()
+                    .quiet(true)
+                    .mode(InstallMode::All)
+                    .isobin_manifest_path(isobin_manifest_path.clone())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        (workspace_provider, isobin_manifest_path)
+    }
+
+    async fn installed_names(
+        workspace_provider: &WorkspaceProvider,
+        isobin_manifest_path: &Path,
+    ) -> Vec<String> {
+        let workspace = workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(
+                fs::canonicalize(isobin_manifest_path.parent().unwrap())
+                    .await
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        IsobinManifestCache::lenient_load_cache_from_dir(workspace.base_dir())
+            .await
+            .dependency_names()
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    fn remove_service_option(targets: &[&str], isobin_manifest_path: &Path) -> RemoveServiceOption {
+        RemoveServiceOptionBuilder::default()
+            .quiet(true)
+            .targets(
+                targets
+                    .iter()
+                    .map(|target| target.parse().unwrap())
+                    .collect(),
+            )
+            .isobin_manifest_path(isobin_manifest_path.into())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn remove_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_remove_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(&dir, ISOBIN_MANIFEST).await;
+        pretty_assertions::assert_eq!(
+            vec!["fmt", "lint", "test"],
+            installed_names(&workspace_provider, &isobin_manifest_path).await
+        );
+
+        RemoveService::new(InstallService::default(), workspace_provider.clone())
+            .remove(remove_service_option(
+                &["fmt", "local/test"],
+                &isobin_manifest_path,
+            ))
+            .await
+            .unwrap();
+        let actual = fs::read_to_string(&isobin_manifest_path).await.unwrap();
+        let installed = installed_names(&workspace_provider, &isobin_manifest_path).await;
+        fs::remove_dir_all(&dir).await.unwrap();
+        pretty_assertions::assert_eq!(
+            "# The tools of the project.\n[local.dependencies]\nlint = \"scripts/lint.sh\"\n",
+            actual
+        );
+        pretty_assertions::assert_eq!(vec!["lint"], installed);
+    }
+
+    #[tokio::test]
+    async fn remove_platform_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_remove_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(
+            &dir,
+            r#"[local.dependencies]
+fmt = "scripts/fmt.sh"
+lint = "scripts/lint.sh"
+
+[target.'cfg(unix)'.local.dependencies]
+fmt = "scripts/fmt.sh"
+test = "scripts/test.sh"
+
+[target.'cfg(windows)'.local.dependencies]
+fmt = "scripts/fmt.sh"
+"#,
+        )
+        .await;
+
+        RemoveService::new(InstallService::default(), workspace_provider.clone())
+            .remove(remove_service_option(
+                &["fmt", "test"],
+                &isobin_manifest_path,
+            ))
+            .await
+            .unwrap();
+        let actual = fs::read_to_string(&isobin_manifest_path).await.unwrap();
+        let installed = installed_names(&workspace_provider, &isobin_manifest_path).await;
+        fs::remove_dir_all(&dir).await.unwrap();
+        pretty_assertions::assert_eq!(
+            r#"[local.dependencies]
+lint = "scripts/lint.sh"
+
+[target.'cfg(unix)'.local.dependencies]
+
+[target.'cfg(windows)'.local.dependencies]
+"#,
+            actual
+        );
+        pretty_assertions::assert_eq!(vec!["lint"], installed);
+    }
+
+    #[tokio::test]
+    async fn remove_included_name_fails() {
+        let dir = std::env::temp_dir().join(format!("isobin_remove_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(&dir, ISOBIN_MANIFEST).await;
+        let isobin_manifest = format!("include = [\"shared.toml\"]\n{ISOBIN_MANIFEST}");
+        fs::write(&isobin_manifest_path, &isobin_manifest)
+            .await
+            .unwrap();
+        fs::write(
+            dir.join("project/shared.toml"),
+            "[local.dependencies]\nshared = \"scripts/lint.sh\"\n",
+        )
+        .await
+        .unwrap();
+
+        let result = RemoveService::new(InstallService::default(), workspace_provider.clone())
+            .remove(remove_service_option(
+                &["fmt", "shared"],
+                &isobin_manifest_path,
+            ))
+            .await;
+        let actual = fs::read_to_string(&isobin_manifest_path).await.unwrap();
+        let installed = installed_names(&workspace_provider, &isobin_manifest_path).await;
+        fs::remove_dir_all(&dir).await.unwrap();
+        assert!(matches!(
+            result.unwrap_err().downcast_ref(),
+            Some(IsobinManifestError::DefinedInInclude { name, path })
+                if name == "shared" && path.ends_with("shared.toml")
+        ));
+        pretty_assertions::assert_eq!(isobin_manifest, actual);
+        pretty_assertions::assert_eq!(vec!["fmt", "lint", "test"], installed);
+    }
+
+    #[tokio::test]
+    async fn remove_unknown_name_fails() {
+        let dir = std::env::temp_dir().join(format!("isobin_remove_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(&dir, ISOBIN_MANIFEST).await;
+
+        let result = RemoveService::new(InstallService::default(), workspace_provider.clone())
+            .remove(remove_service_option(
+                &["fmt", "unknown"],
+                &isobin_manifest_path,
+            ))
+            .await;
+        let actual = fs::read_to_string(&isobin_manifest_path).await.unwrap();
+        let installed = installed_names(&workspace_provider, &isobin_manifest_path).await;
+        fs::remove_dir_all(&dir).await.unwrap();
+        assert!(result.is_err());
+        pretty_assertions::assert_eq!(ISOBIN_MANIFEST, actual);
+        pretty_assertions::assert_eq!(vec!["fmt", "lint", "test"], installed);
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{ExitStatus, Stdio},
 };
 
@@ -8,22 +8,50 @@ use tokio::process::Command;
 use crate::{
     bin_map::BinMap,
     flex_eprintln,
+    install::{InstallService, InstallServiceOptionBuilder},
     manifest::{IsobinManifest, IsobinManifestCache},
     paths::{
-        isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+        isobin_manifest::{
+            isobin_manifest_dir, isobin_manifest_path_canonicalize, search_isobin_manifest_path,
+            IsobinManifestPathError,
+        },
         workspace::{Workspace, WorkspaceProvider},
     },
     InstallMode, Result, SpecifiedTarget,
 };
 
-#[derive(Default)]
+#[derive(Default, new)]
 pub struct RunService {
     workspace_provider: WorkspaceProvider,
 }
 
 impl RunService {
     pub async fn run(&self, run_service_option: RunServiceOption) -> Result<()> {
-        let run_service_option = run_service_option.fix().await?;
+        let global_isobin_manifest_path = self.workspace_provider.global_isobin_manifest_path();
+        let run_service_option = run_service_option
+            .fix(&std::env::current_dir()?, &global_isobin_manifest_path)
+            .await?;
+        match self.run_in_manifest(run_service_option.clone()).await {
+            Err(err)
+                if matches!(
+                    err.downcast_ref(),
+                    Some(RunServiceError::NotFoundBinDependency { .. })
+                ) =>
+            {
+                if let Some(global_run_service_option) = run_service_option
+                    .to_global(&global_isobin_manifest_path)
+                    .await
+                {
+                    self.run_in_manifest(global_run_service_option).await
+                } else {
+                    Err(err)
+                }
+            }
+            result => result,
+        }
+    }
+
+    async fn run_in_manifest(&self, run_service_option: FixedRunServiceOption) -> Result<()> {
         let isobin_manifest_dir = isobin_manifest_dir(run_service_option.isobin_manifest_path())?;
         let isobin_manifest =
             IsobinManifest::load_from_file(run_service_option.isobin_manifest_path()).await?;
@@ -35,7 +63,9 @@ impl RunService {
         if let Some(bin_dependency) = bin_map.bin_dependencies().get(run_service_option.bin()) {
             if isobin_manifest.exists_name(bin_dependency.name()) {
                 let isobin_cache =
-                    IsobinManifestCache::lenient_load_cache_from_dir(workspace.base_dir()).await;
+                    IsobinManifestCache::lenient_load_cache_from_dir(workspace.base_dir())
+                        .await
+                        .fix(isobin_manifest_dir);
                 if isobin_manifest
                     .ditect_difference(
                         &isobin_cache,
@@ -87,16 +117,17 @@ impl RunService {
             run_service_option.quiet,
             "ditected difference from current manifest"
         );
-        crate::install(
-            crate::InstallServiceOptionBuilder::default()
-                .isobin_manifest_path(run_service_option.isobin_manifest_path().into())
-                .mode(InstallMode::SpecificInstallTargetsOnly {
-                    specified_install_targets: vec![specified_target],
-                })
-                .quiet(*run_service_option.quiet())
-                .build(),
-        )
-        .await?;
+        InstallService::new(self.workspace_provider.clone())
+            .install(
+                InstallServiceOptionBuilder::default()
+                    .isobin_manifest_path(run_service_option.isobin_manifest_path().into())
+                    .mode(InstallMode::SpecificInstallTargetsOnly {
+                        specified_install_targets: vec![specified_target],
+                    })
+                    .quiet(*run_service_option.quiet())
+                    .build(),
+            )
+            .await?;
         self.run_command(workspace, run_service_option).await
     }
 
@@ -138,7 +169,7 @@ pub enum RunServiceError {
     RunFailed { status: ExitStatus },
 }
 
-#[derive(Getters)]
+#[derive(Getters, Clone)]
 pub struct RunServiceOptionBase<P> {
     quiet: bool,
     bin: String,
@@ -150,10 +181,45 @@ pub type RunServiceOption = RunServiceOptionBase<Option<PathBuf>>;
 
 pub type FixedRunServiceOption = RunServiceOptionBase<PathBuf>;
 
+impl FixedRunServiceOption {
+    /// Returns the option for the global manifest unless it is already the one in use.
+    async fn to_global(&self, global_isobin_manifest_path: &Path) -> Option<Self> {
+        let global_isobin_manifest_path =
+            isobin_manifest_path_canonicalize(Some(global_isobin_manifest_path.into()))
+                .await
+                .ok()?;
+        (global_isobin_manifest_path != self.isobin_manifest_path).then(|| Self {
+            isobin_manifest_path: global_isobin_manifest_path,
+            ..self.clone()
+        })
+    }
+}
+
 impl RunServiceOption {
-    pub async fn fix(self) -> Result<FixedRunServiceOption> {
+    /// Searches the manifest from `current_dir` unless the path is given.
+    pub async fn fix(
+        self,
+        current_dir: &Path,
+        global_isobin_manifest_path: &Path,
+    ) -> Result<FixedRunServiceOption> {
+        let isobin_manifest_path = match self.isobin_manifest_path {
+            Some(isobin_manifest_path) => isobin_manifest_path,
+            None => match search_isobin_manifest_path(current_dir).await {
+                // Outside of any project, the global manifest is used as it is.
+                Err(err)
+                    if global_isobin_manifest_path.exists()
+                        && matches!(
+                            err.downcast_ref(),
+                            Some(IsobinManifestPathError::NotFoundIsobinManifest)
+                        ) =>
+                {
+                    global_isobin_manifest_path.into()
+                }
+                result => result?,
+            },
+        };
         let isobin_manifest_path =
-            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+            isobin_manifest_path_canonicalize(Some(isobin_manifest_path)).await?;
         Ok(FixedRunServiceOption {
             quiet: self.quiet,
             bin: self.bin,
@@ -197,3 +263,119 @@ impl RunServiceOptionBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{paths::project::Project, utils::fs_ext};
+    use tokio::fs;
+
+    /// Installs a global tool which touches the file given as the argument, and makes a project
+    /// manifest which does not have the tool.
+    async fn prepare(dir: &Path) -> (WorkspaceProvider, PathBuf) {
+        let workspace_provider = WorkspaceProvider::new(Project::new(
+            dir.join("config"),
+            dir.join("cache"),
+            dir.join("data"),
+        ));
+        let global_isobin_manifest_path = workspace_provider.global_isobin_manifest_path();
+        let script_path = dir.join("config/scripts/touch.sh");
+        fs_ext::smart_create_file(&script_path).await.unwrap();
+        fs::write(&script_path, "#!/bin/sh\ntouch \"$1\"\n")
+            .await
+            .unwrap();
+        fs_ext::set_executable(&script_path).await.unwrap();
+        fs::write(
+            &global_isobin_manifest_path,
+            "[local.dependencies]\ntouch = \"scripts/touch.sh\"\n",
+        )
+        .await
+        .unwrap();
+        InstallService::new(workspace_provider.clone())
+            .install(
+                InstallServiceOptionBuilder::default()
+                    .quiet(true)
+                    .mode(InstallMode::All)
+                    .isobin_manifest_path(global_isobin_manifest_path)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let isobin_manifest_path = dir.join("project/isobin.toml");
+        fs_ext::smart_create_file(&isobin_manifest_path)
+            .await
+            .unwrap();
+        fs::write(
+            &isobin_manifest_path,
+            "[local.dependencies]\nfmt = \"scripts/fmt.sh\"\n",
+        )
+        .await
+        .unwrap();
+        (workspace_provider, isobin_manifest_path)
+    }
+
+    fn run_service_option(
+        bin: &str,
+        args: Vec<String>,
+        isobin_manifest_path: &Path,
+    ) -> RunServiceOption {
+        RunServiceOptionBuilder::default()
+            .quiet(true)
+            .bin(bin.into())
+            .args(args)
+            .isobin_manifest_path(isobin_manifest_path.into())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn run_falls_back_to_global_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_run_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(&dir).await;
+        let touched_path = dir.join("touched");
+
+        let result = RunService::new(workspace_provider)
+            .run(run_service_option(
+                "touch",
+                vec![touched_path.to_string_lossy().into()],
+                &isobin_manifest_path,
+            ))
+            .await;
+        let touched = touched_path.exists();
+        fs::remove_dir_all(&dir).await.unwrap();
+        result.unwrap();
+        assert!(touched);
+    }
+
+    #[tokio::test]
+    async fn run_not_found_bin_fails() {
+        let dir = std::env::temp_dir().join(format!("isobin_run_{}", nanoid::nanoid!()));
+        let (workspace_provider, isobin_manifest_path) = prepare(&dir).await;
+
+        let result = RunService::new(workspace_provider)
+            .run(run_service_option("unknown", vec![], &isobin_manifest_path))
+            .await;
+        fs::remove_dir_all(&dir).await.unwrap();
+        assert!(matches!(
+            result.unwrap_err().downcast_ref(),
+            Some(RunServiceError::NotFoundBinDependency { bin }) if bin == "unknown"
+        ));
+    }
+
+    #[tokio::test]
+    async fn fix_without_project_manifest_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_run_{}", nanoid::nanoid!()));
+        let (workspace_provider, _) = prepare(&dir).await;
+        let global_isobin_manifest_path = workspace_provider.global_isobin_manifest_path();
+
+        let actual = RunServiceOptionBuilder::default()
+            .bin("touch".into())
+            .build()
+            .fix(&dir.join("cache"), &global_isobin_manifest_path)
+            .await;
+        let expected = fs::canonicalize(&global_isobin_manifest_path)
+            .await
+            .unwrap();
+        fs::remove_dir_all(&dir).await.unwrap();
+        pretty_assertions::assert_eq!(&expected, actual.unwrap().isobin_manifest_path());
+    }
+}
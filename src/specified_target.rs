@@ -1,7 +1,87 @@
-use crate::providers::ProviderKind;
+use std::str::FromStr;
 
-#[derive(Getters, new, PartialEq)]
+use crate::providers::{registered_provider_registry, ProviderKind, ProviderRegistry};
+
+#[derive(Getters, new, PartialEq, Clone, Debug)]
 pub struct SpecifiedTarget {
     provider_kind: Option<ProviderKind>,
     name: String,
 }
+
+impl FromStr for SpecifiedTarget {
+    type Err = SpecifiedTargetError;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse_with_registry(s, &registered_provider_registry())
+    }
+}
+
+impl SpecifiedTarget {
+    /// Parses the target whose provider is one of the registry.
+    pub(crate) fn parse_with_registry(
+        s: &str,
+        provider_registry: &ProviderRegistry,
+    ) -> std::result::Result<Self, SpecifiedTargetError> {
+        match s.split_once('/') {
+            Some((provider, name)) => {
+                let provider_kind = ProviderKind::from_str(provider)
+                    .ok()
+                    .filter(|provider_kind| provider_registry.find(provider_kind).is_some())
+                    .ok_or_else(|| {
+                        SpecifiedTargetError::new_unknown_provider(provider.into(), s.into())
+                    })?;
+                if name.is_empty() {
+                    Err(SpecifiedTargetError::new_empty_name(s.into()))
+                } else {
+                    Ok(Self::new(Some(provider_kind), name.into()))
+                }
+            }
+            None => {
+                if s.is_empty() {
+                    Err(SpecifiedTargetError::new_empty_name(s.into()))
+                } else {
+                    Ok(Self::new(None, s.into()))
+                }
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, new, PartialEq)]
+pub enum SpecifiedTargetError {
+    #[error("Unknown provider {provider} in {target}")]
+    UnknownProvider { provider: String, target: String },
+    #[error("The target name is empty in \"{target}\"")]
+    EmptyName { target: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("cargo-make", SpecifiedTarget::new(None, "cargo-make".into()))]
+    #[case(
+        "cargo/cargo-make",
+        SpecifiedTarget::new(Some(ProviderKind::Cargo), "cargo-make".into())
+    )]
+    fn specified_target_from_str_works(#[case] s: &str, #[case] expected: SpecifiedTarget) {
+        let actual = SpecifiedTarget::from_str(s).unwrap();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case(
+        "npx/cargo-make",
+        SpecifiedTargetError::new_unknown_provider("npx".into(), "npx/cargo-make".into())
+    )]
+    #[case("cargo/", SpecifiedTargetError::new_empty_name("cargo/".into()))]
+    #[case("", SpecifiedTargetError::new_empty_name("".into()))]
+    fn specified_target_from_str_error_works(
+        #[case] s: &str,
+        #[case] expected: SpecifiedTargetError,
+    ) {
+        let actual = SpecifiedTarget::from_str(s).unwrap_err();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
 use crate::{
+    fronts,
+    lock::IsobinLock,
     manifest::{IsobinManifest, IsobinManifestCache},
     paths::{
         isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
@@ -22,6 +24,11 @@ impl SyncService {
 
         let isobin_manifest =
             IsobinManifest::load_from_file(sync_service_option.isobin_manifest_path()).await?;
+        if !sync_service_option.quiet {
+            for warning in isobin_manifest.warnings().iter() {
+                fronts::print_warning(warning);
+            }
+        }
         let isobin_manifest_dir = isobin_manifest_dir(sync_service_option.isobin_manifest_path())?;
 
         let workspace = self
@@ -41,10 +48,26 @@ impl SyncService {
         let isobin_manifest_cache = if sync_service_option.force {
             IsobinManifest::default()
         } else {
-            IsobinManifestCache::lenient_load_cache_from_dir(tmp_workspace.base_dir()).await
+            IsobinManifestCache::lenient_load_cache_from_dir(tmp_workspace.base_dir())
+                .await
+                .fix(isobin_manifest_dir)
         };
 
-        let specified_isobin_manifest = isobin_manifest;
+        let isobin_lock = IsobinLock::load_from_dir(isobin_manifest_dir).await?;
+        let unexpanded_isobin_manifest =
+            IsobinManifest::load_unexpanded_from_file(sync_service_option.isobin_manifest_path())
+                .await?;
+        if sync_service_option.locked {
+            isobin_lock.check_locked(
+                &isobin_manifest,
+                &unexpanded_isobin_manifest,
+                isobin_manifest_dir,
+            )?;
+        }
+        let specified_isobin_manifest = isobin_manifest.filter_groups(
+            sync_service_option.groups(),
+            sync_service_option.without_groups(),
+        )?;
         let install_target_isobin_manifest = IsobinManifest::get_need_install_dependency_manifest(
             &specified_isobin_manifest,
             &isobin_manifest_cache,
@@ -52,9 +75,10 @@ impl SyncService {
         )
         .await?;
 
+        // The tools in the unselected groups are kept, since they are still in the manifest.
         let uninstall_target_isobin_manifest =
             IsobinManifest::get_need_uninstall_dependency_manifest(
-                &specified_isobin_manifest,
+                &isobin_manifest,
                 &isobin_manifest_cache,
             )
             .await?;
@@ -68,12 +92,24 @@ impl SyncService {
                 &workspace,
                 &tmp_workspace,
                 &save_isobin_manifest,
-                &specified_isobin_manifest,
-                &install_target_isobin_manifest,
+                &isobin_lock.pin(&specified_isobin_manifest, &unexpanded_isobin_manifest),
+                &isobin_lock.pin(&install_target_isobin_manifest, &unexpanded_isobin_manifest),
                 &uninstall_target_isobin_manifest,
                 sync_service_option.quiet,
             )
-            .await
+            .await?;
+        if !sync_service_option.locked {
+            isobin_lock
+                .update_and_save(
+                    &isobin_manifest,
+                    &unexpanded_isobin_manifest,
+                    &specified_isobin_manifest,
+                    &workspace,
+                    isobin_manifest_dir,
+                )
+                .await?;
+        }
+        Ok(())
     }
 }
 
@@ -81,6 +117,9 @@ impl SyncService {
 pub struct SyncServiceOptionBase<P> {
     quiet: bool,
     force: bool,
+    locked: bool,
+    groups: Vec<String>,
+    without_groups: Vec<String>,
     isobin_manifest_path: P,
 }
 pub type SyncServiceOption = SyncServiceOptionBase<Option<PathBuf>>;
@@ -94,6 +133,9 @@ impl SyncServiceOption {
         Ok(FixedSyncServiceOption {
             quiet: self.quiet,
             force: self.force,
+            locked: self.locked,
+            groups: self.groups,
+            without_groups: self.without_groups,
             isobin_manifest_path,
         })
     }
@@ -103,6 +145,9 @@ impl SyncServiceOption {
 pub struct SyncServiceOptionBuilder {
     quiet: bool,
     force: bool,
+    locked: bool,
+    groups: Vec<String>,
+    without_groups: Vec<String>,
     isobin_manifest_path: Option<PathBuf>,
 }
 
@@ -115,6 +160,18 @@ impl SyncServiceOptionBuilder {
         self.force = force;
         self
     }
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+    pub fn groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = groups;
+        self
+    }
+    pub fn without_groups(mut self, without_groups: Vec<String>) -> Self {
+        self.without_groups = without_groups;
+        self
+    }
     pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
         self.isobin_manifest_path = Some(isobin_manifest_path);
         self
@@ -123,6 +180,9 @@ impl SyncServiceOptionBuilder {
         SyncServiceOption {
             quiet: self.quiet,
             force: self.force,
+            locked: self.locked,
+            groups: self.groups,
+            without_groups: self.without_groups,
             isobin_manifest_path: self.isobin_manifest_path,
         }
     }
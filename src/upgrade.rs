@@ -0,0 +1,398 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use cargo::util::PartialVersion;
+
+use crate::{
+    flex_eprintln,
+    manifest::{DependencyLocation, IsobinManifest, IsobinManifestError},
+    manifest_editor::IsobinManifestEditor,
+    outdated::{OutdatedItem, OutdatedService},
+    paths::{
+        isobin_manifest::{isobin_manifest_dir, isobin_manifest_path_canonicalize},
+        workspace::WorkspaceProvider,
+    },
+    providers::{cargo::CargoInstallDependency, ProviderKind},
+    utils::io_ext,
+    InstallMode, Result, SpecifiedTarget,
+};
+
+#[derive(Default)]
+pub struct UpgradeService {
+    workspace_provider: WorkspaceProvider,
+}
+
+impl UpgradeService {
+    pub async fn upgrade(
+        &self,
+        upgrade_service_option: UpgradeServiceOption,
+    ) -> Result<Vec<UpgradeItem>> {
+        let upgrade_service_option = upgrade_service_option.fix().await?;
+        let isobin_manifest =
+            IsobinManifest::load_from_file(upgrade_service_option.isobin_manifest_path()).await?;
+        let target_isobin_manifest = if upgrade_service_option.targets().is_empty() {
+            isobin_manifest
+        } else {
+            isobin_manifest.filter_target(upgrade_service_option.targets())?
+        };
+        let isobin_manifest_dir =
+            isobin_manifest_dir(upgrade_service_option.isobin_manifest_path())?;
+        let workspace = self
+            .workspace_provider
+            .base_unique_workspace_dir_from_isobin_manifest_dir(isobin_manifest_dir)
+            .await?;
+        let upgrade_items =
+            OutdatedService::make_outdated_items(&target_isobin_manifest, &workspace)
+                .await?
+                .iter()
+                .filter_map(|outdated_item| {
+                    UpgradeItem::from_outdated_item(outdated_item, upgrade_service_option.latest)
+                })
+                .collect::<Vec<_>>();
+        if upgrade_items.is_empty() {
+            flex_eprintln!(
+                upgrade_service_option.quiet,
+                "The upgrade targets are up to date."
+            );
+            return Ok(upgrade_items);
+        }
+        if upgrade_service_option.dry_run {
+            return Ok(upgrade_items);
+        }
+
+        Self::edit_isobin_manifest(
+            upgrade_service_option.isobin_manifest_path(),
+            &target_isobin_manifest,
+            &upgrade_items,
+        )
+        .await?;
+
+        crate::install(
+            crate::InstallServiceOptionBuilder::default()
+                .isobin_manifest_path(upgrade_service_option.isobin_manifest_path().into())
+                .mode(InstallMode::SpecificInstallTargetsOnly {
+                    specified_install_targets: upgrade_items
+                        .iter()
+                        .map(|upgrade_item| {
+                            SpecifiedTarget::new(
+                                Some(upgrade_item.provider().clone()),
+                                upgrade_item.name().clone(),
+                            )
+                        })
+                        .collect(),
+                })
+                .quiet(upgrade_service_option.quiet)
+                .build(),
+        )
+        .await?;
+        Ok(upgrade_items)
+    }
+
+    /// Pins the versions of the upgrade items in the sections which they are taken from.
+    async fn edit_isobin_manifest(
+        isobin_manifest_path: &Path,
+        target_isobin_manifest: &IsobinManifest,
+        upgrade_items: &[UpgradeItem],
+    ) -> Result<()> {
+        let mut isobin_manifest_editor =
+            IsobinManifestEditor::load_from_file(isobin_manifest_path).await?;
+        // The entries are taken as written, so the expanded environment variables are not saved.
+        let unfixed_isobin_manifest =
+            IsobinManifest::load_unfixed_from_file(isobin_manifest_path).await?;
+        for upgrade_item in upgrade_items.iter() {
+            let locations = IsobinManifest::locate_dependency(
+                isobin_manifest_path,
+                upgrade_item.provider(),
+                upgrade_item.name(),
+            )
+            .await?;
+            let platform = match DependencyLocation::find_applied(&locations) {
+                Some(DependencyLocation::Section { platform }) => platform.as_deref(),
+                Some(DependencyLocation::Include { path }) => {
+                    Err(IsobinManifestError::new_defined_in_include(
+                        upgrade_item.name().clone(),
+                        io_ext::path_to_string(path),
+                    ))?
+                }
+                None => None,
+            };
+            // The entry is pinned as it is written, so only the version of it changes.
+            let dependency = unfixed_isobin_manifest
+                .platform_manifest(platform)
+                .and_then(|isobin_manifest| {
+                    isobin_manifest
+                        .cargo()
+                        .dependencies()
+                        .get(upgrade_item.name())
+                })
+                .ok_or_else(|| {
+                    IsobinManifestError::new_not_found_dependency(
+                        Some(upgrade_item.provider().clone()),
+                        upgrade_item.name().clone(),
+                    )
+                })?;
+            let version = PartialVersion::from_str(upgrade_item.to())?;
+            let dependency = match dependency {
+                CargoInstallDependency::Simple(_) => CargoInstallDependency::Simple(version),
+                CargoInstallDependency::Detailed(dependency) => {
+                    CargoInstallDependency::Detailed(dependency.clone().pin_version(version))
+                }
+            };
+            isobin_manifest_editor.upsert_dependency(
+                platform,
+                upgrade_item.provider(),
+                upgrade_item.name(),
+                &target_isobin_manifest.make_dependency_entry(
+                    upgrade_item.provider(),
+                    upgrade_item.name(),
+                    &dependency,
+                )?,
+            )?;
+        }
+        isobin_manifest_editor.save().await
+    }
+}
+
+#[derive(Getters, new, Debug, PartialEq)]
+pub struct UpgradeItem {
+    provider: ProviderKind,
+    name: String,
+    from: String,
+    to: String,
+}
+
+impl UpgradeItem {
+    fn from_outdated_item(outdated_item: &OutdatedItem, latest: bool) -> Option<Self> {
+        let to = if latest {
+            outdated_item.latest()
+        } else {
+            outdated_item.compatible()
+        };
+        to.as_ref()
+            .filter(|to| *to != outdated_item.requirement())
+            .map(|to| {
+                Self::new(
+                    outdated_item.provider().clone(),
+                    outdated_item.name().clone(),
+                    outdated_item.requirement().clone(),
+                    to.clone(),
+                )
+            })
+    }
+}
+
+#[derive(Getters)]
+pub struct UpgradeServiceOptionBase<P> {
+    quiet: bool,
+    latest: bool,
+    dry_run: bool,
+    targets: Vec<SpecifiedTarget>,
+    isobin_manifest_path: P,
+}
+
+pub type UpgradeServiceOption = UpgradeServiceOptionBase<Option<PathBuf>>;
+type FixedUpgradeServiceOption = UpgradeServiceOptionBase<PathBuf>;
+
+impl UpgradeServiceOption {
+    async fn fix(self) -> Result<FixedUpgradeServiceOption> {
+        let isobin_manifest_path =
+            isobin_manifest_path_canonicalize(self.isobin_manifest_path).await?;
+        Ok(FixedUpgradeServiceOption {
+            quiet: self.quiet,
+            latest: self.latest,
+            dry_run: self.dry_run,
+            targets: self.targets,
+            isobin_manifest_path,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct UpgradeServiceOptionBuilder {
+    quiet: bool,
+    latest: bool,
+    dry_run: bool,
+    targets: Vec<SpecifiedTarget>,
+    isobin_manifest_path: Option<PathBuf>,
+}
+
+impl UpgradeServiceOptionBuilder {
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+    pub fn latest(mut self, latest: bool) -> Self {
+        self.latest = latest;
+        self
+    }
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+    pub fn targets(mut self, targets: Vec<SpecifiedTarget>) -> Self {
+        self.targets = targets;
+        self
+    }
+    pub fn isobin_manifest_path(mut self, isobin_manifest_path: PathBuf) -> Self {
+        self.isobin_manifest_path = Some(isobin_manifest_path);
+        self
+    }
+    pub fn build(self) -> UpgradeServiceOption {
+        UpgradeServiceOption {
+            quiet: self.quiet,
+            latest: self.latest,
+            dry_run: self.dry_run,
+            targets: self.targets,
+            isobin_manifest_path: self.isobin_manifest_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn outdated_item(requirement: &str, compatible: &str, latest: &str) -> OutdatedItem {
+        OutdatedItem::new(
+            ProviderKind::Cargo,
+            "cargo-make".into(),
+            requirement.into(),
+            None,
+            Some(compatible.into()),
+            Some(latest.into()),
+        )
+    }
+
+    #[rstest]
+    #[case(outdated_item("0.36", "0.36.13", "0.37.24"), false, Some("0.36.13"))]
+    #[case(outdated_item("0.36", "0.36.13", "0.37.24"), true, Some("0.37.24"))]
+    #[case(outdated_item("0.37.24", "0.37.24", "0.37.24"), false, None)]
+    #[case(outdated_item("0.37.24", "0.37.24", "0.37.24"), true, None)]
+    fn from_outdated_item_works(
+        #[case] outdated_item: OutdatedItem,
+        #[case] latest: bool,
+        #[case] expected_to: Option<&str>,
+    ) {
+        let expected = expected_to.map(|to| {
+            UpgradeItem::new(
+                ProviderKind::Cargo,
+                "cargo-make".into(),
+                outdated_item.requirement().clone(),
+                to.into(),
+            )
+        });
+        pretty_assertions::assert_eq!(
+            expected,
+            UpgradeItem::from_outdated_item(&outdated_item, latest)
+        );
+    }
+
+    #[tokio::test]
+    async fn edit_isobin_manifest_platform_works() {
+        let dir = std::env::temp_dir().join(format!("isobin_upgrade_{}", nanoid::nanoid!()));
+        let isobin_manifest_path = dir.join("isobin.toml");
+        crate::utils::fs_ext::smart_create_file(&isobin_manifest_path)
+            .await
+            .unwrap();
+        tokio::fs::write(
+            &isobin_manifest_path,
+            r#"[cargo.dependencies]
+cargo-make = "0.36"
+comrak = "0.20"
+
+[target.'cfg(unix)'.cargo.dependencies]
+comrak = { version = "0.20", features = ["syntect"] } # Only for unix.
+typos-cli = "1.16"
+
+[target.'cfg(windows)'.cargo.dependencies]
+typos-cli = "1.16"
+"#,
+        )
+        .await
+        .unwrap();
+        let target_isobin_manifest = IsobinManifest::load_from_file(&isobin_manifest_path)
+            .await
+            .unwrap();
+        let upgrade_items = [
+            ("cargo-make", "0.36", "0.37"),
+            ("comrak", "0.20", "0.21"),
+            ("typos-cli", "1.16", "1.17"),
+        ]
+        .map(|(name, from, to)| {
+            UpgradeItem::new(ProviderKind::Cargo, name.into(), from.into(), to.into())
+        });
+
+        let result = UpgradeService::edit_isobin_manifest(
+            &isobin_manifest_path,
+            &target_isobin_manifest,
+            &upgrade_items,
+        )
+        .await;
+        let actual = tokio::fs::read_to_string(&isobin_manifest_path)
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        result.unwrap();
+        pretty_assertions::assert_eq!(
+            r#"[cargo.dependencies]
+cargo-make = "0.37"
+comrak = "0.20"
+
+[target.'cfg(unix)'.cargo.dependencies]
+comrak = { version = "0.21", features = ["syntect"] } # Only for unix.
+typos-cli = "1.17"
+
+[target.'cfg(windows)'.cargo.dependencies]
+typos-cli = "1.16"
+"#,
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn edit_isobin_manifest_included_fails() {
+        let dir = std::env::temp_dir().join(format!("isobin_upgrade_{}", nanoid::nanoid!()));
+        let isobin_manifest_path = dir.join("isobin.toml");
+        let isobin_manifest =
+            "include = [\"shared.toml\"]\n\n[cargo.dependencies]\ncargo-make = \"0.36\"\n";
+        crate::utils::fs_ext::smart_create_file(&isobin_manifest_path)
+            .await
+            .unwrap();
+        tokio::fs::write(&isobin_manifest_path, isobin_manifest)
+            .await
+            .unwrap();
+        tokio::fs::write(
+            dir.join("shared.toml"),
+            "[cargo.dependencies]\ncomrak = \"0.20\"\n",
+        )
+        .await
+        .unwrap();
+        let target_isobin_manifest = IsobinManifest::load_from_file(&isobin_manifest_path)
+            .await
+            .unwrap();
+        let upgrade_items =
+            [("cargo-make", "0.36", "0.37"), ("comrak", "0.20", "0.21")].map(|(name, from, to)| {
+                UpgradeItem::new(ProviderKind::Cargo, name.into(), from.into(), to.into())
+            });
+
+        let result = UpgradeService::edit_isobin_manifest(
+            &isobin_manifest_path,
+            &target_isobin_manifest,
+            &upgrade_items,
+        )
+        .await;
+        let actual = tokio::fs::read_to_string(&isobin_manifest_path)
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        assert!(matches!(
+            result.unwrap_err().downcast_ref(),
+            Some(IsobinManifestError::DefinedInInclude { name, path })
+                if name == "comrak" && path.ends_with("shared.toml")
+        ));
+        pretty_assertions::assert_eq!(isobin_manifest, actual);
+    }
+}
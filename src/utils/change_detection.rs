@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::Result;
+
+use super::{
+    file_hash::{FileHashCacheMap, FILE_HASH_CACHE_MAP_FILE_NAME},
+    file_modified::{FileModifiedCacheMap, FILE_MODIFIED_CACHE_MAP_FILE_NAME},
+};
+
+/// How the changes of local sources are detected.
+/// `Modified` compares the sizes and the modified times, `Hash` compares the contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeDetection {
+    #[default]
+    Modified,
+    Hash,
+}
+
+impl ChangeDetection {
+    pub fn cache_file_name(&self) -> &'static str {
+        match self {
+            Self::Modified => FILE_MODIFIED_CACHE_MAP_FILE_NAME,
+            Self::Hash => FILE_HASH_CACHE_MAP_FILE_NAME,
+        }
+    }
+
+    pub async fn save_snapshot(&self, files: Vec<PathBuf>, cache_path: &Path) -> Result<()> {
+        match self {
+            Self::Modified => {
+                FileModifiedCacheMap::from_files(files)
+                    .await?
+                    .save_to_file(cache_path)
+                    .await
+            }
+            Self::Hash => {
+                FileHashCacheMap::from_files(files)
+                    .await?
+                    .save_to_file(cache_path)
+                    .await
+            }
+        }
+    }
+
+    /// Returns true when the files differ from the snapshot or the snapshot does not exist.
+    pub async fn has_changes(&self, files: Vec<PathBuf>, cache_path: &Path) -> Result<bool> {
+        match self {
+            Self::Modified => match FileModifiedCacheMap::lenient_load_from_file(cache_path).await?
+            {
+                Some(old) => Ok(FileModifiedCacheMap::from_files(files)
+                    .await?
+                    .has_diff(&old)),
+                None => Ok(true),
+            },
+            Self::Hash => match FileHashCacheMap::lenient_load_from_file(cache_path).await? {
+                Some(old) => Ok(FileHashCacheMap::from_files(files).await?.has_diff(&old)),
+                None => Ok(true),
+            },
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::anyhow;
+use tokio::fs;
+
+use crate::Result;
+
+#[derive(thiserror::Error, Debug, new)]
+pub enum DownloadError {
+    #[error("failed to download {url}: {error}")]
+    Fetch {
+        url: String,
+        #[source]
+        error: reqwest::Error,
+    },
+
+    #[error("not found {url}")]
+    NotFound { url: String },
+}
+
+/// Downloads the content of an http(s) or `file://` URL.
+pub async fn download(url: &str) -> Result<Vec<u8>> {
+    lenient_download(url)
+        .await?
+        .ok_or_else(|| DownloadError::new_not_found(url.into()).into())
+}
+
+/// Returns `None` when the content is not found.
+pub async fn lenient_download(url: &str) -> Result<Option<Vec<u8>>> {
+    let parsed_url = reqwest::Url::parse(url)?;
+    if parsed_url.scheme() == "file" {
+        let path = parsed_url
+            .to_file_path()
+            .map_err(|_| anyhow!("{url} is not a file path."))?;
+        return if path.is_file() {
+            Ok(Some(fs::read(path).await?))
+        } else {
+            Ok(None)
+        };
+    }
+    let response = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "isobin")
+        .send()
+        .await
+        .map_err(|err| DownloadError::new_fetch(url.into(), err))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(
+        response
+            .error_for_status()
+            .map_err(|err| DownloadError::new_fetch(url.into(), err))?
+            .bytes()
+            .await
+            .map_err(|err| DownloadError::new_fetch(url.into(), err))?
+            .to_vec(),
+    ))
+}
@@ -0,0 +1,87 @@
+/// Expands `${VAR}` and `${VAR:-default}` with the environment variables.
+/// The names of the undefined variables without defaults are returned as the error.
+pub fn expand_env_vars(value: &str) -> Result<String, Vec<String>> {
+    expand_vars(value, |name| std::env::var(name).ok())
+}
+
+fn expand_vars(
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, Vec<String>> {
+    let mut expanded = String::new();
+    let mut undefined_names = vec![];
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        // A reference which is not closed or does not have a valid name is left as it is.
+        let Some((name, default)) = reference
+            .find('}')
+            .map(|end| &reference[..end])
+            .map(|body| match body.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (body, None),
+            })
+            .filter(|(name, _)| is_var_name(name))
+        else {
+            expanded.push_str("${");
+            rest = reference;
+            continue;
+        };
+        match lookup(name).filter(|var| !(var.is_empty() && default.is_some())) {
+            Some(var) => expanded.push_str(&var),
+            None => match default {
+                Some(default) => expanded.push_str(default),
+                None => undefined_names.push(name.to_string()),
+            },
+        }
+        let reference_len = name.len() + default.map_or(0, |default| default.len() + 2) + 1;
+        rest = &reference[reference_len..];
+    }
+    expanded.push_str(rest);
+    if undefined_names.is_empty() {
+        Ok(expanded)
+    } else {
+        Err(undefined_names)
+    }
+}
+
+fn is_var_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    #[rstest]
+    #[case("https://example.com/index", Ok("https://example.com/index"))]
+    #[case("${MIRROR}/index", Ok("https://mirror.example.com/index"))]
+    #[case("${HOME}/src/${MISSING:-tool}", Ok("/home/user/src/tool"))]
+    #[case("${EMPTY:-default}", Ok("default"))]
+    #[case("${EMPTY}", Ok(""))]
+    #[case("${MISSING}/${HOME}/${OTHER}", Err(vec!["MISSING", "OTHER"]))]
+    #[case("${1INVALID} ${UNCLOSED", Ok("${1INVALID} ${UNCLOSED"))]
+    #[case("$HOME", Ok("$HOME"))]
+    fn expand_vars_works(#[case] value: &str, #[case] expected: Result<&str, Vec<&str>>) {
+        let vars: HashMap<&str, &str> = [
+            ("MIRROR", "https://mirror.example.com"),
+            ("HOME", "/home/user"),
+            ("EMPTY", ""),
+        ]
+        .into_iter()
+        .collect();
+        let actual = expand_vars(value, |name| vars.get(name).map(|var| var.to_string()));
+        pretty_assertions::assert_eq!(
+            expected
+                .map(String::from)
+                .map_err(|names| names.into_iter().map(String::from).collect::<Vec<_>>()),
+            actual
+        );
+    }
+}
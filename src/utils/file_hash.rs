@@ -0,0 +1,50 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::Result;
+
+use super::{join_future::join_all, serde_ext::Json};
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct FileHashCacheMap {
+    files: HashMap<PathBuf, String>,
+}
+
+pub const FILE_HASH_CACHE_MAP_FILE_NAME: &str = "file_hash_cache.v1.json";
+
+impl FileHashCacheMap {
+    pub async fn from_files(files: Vec<PathBuf>) -> Result<Self> {
+        let hashes = join_all(files.into_iter().map(get_file_hash)).await?;
+        Ok(Self {
+            files: hashes.into_iter().collect(),
+        })
+    }
+
+    /// Returns `None` when the cache has not been written yet.
+    pub async fn lenient_load_from_file(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        if path.as_ref().exists() {
+            Ok(Some(Json::parse_from_file(path).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        Json::save_to_file(self, path).await
+    }
+
+    /// Returns true when the content of a file was changed, or a file was added or deleted.
+    pub fn has_diff(&self, old: &Self) -> bool {
+        self.files != old.files
+    }
+}
+
+async fn get_file_hash(path: PathBuf) -> Result<(PathBuf, String)> {
+    let content = tokio::fs::read(&path).await?;
+    Ok((path, format!("{:x}", Sha256::digest(content))))
+}
@@ -8,9 +8,9 @@ use serde_derive::{Deserialize, Serialize};
 
 use crate::Result;
 
-use super::{fs_ext, join_future::join_all};
+use super::{join_future::join_all, serde_ext::Json};
 
-#[derive(Deserialize, Serialize, PartialEq)]
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
 pub struct FileModifiedCache {
     size: u64,
     modifieded_at: Option<SystemTime>,
@@ -21,13 +21,44 @@ pub struct FileModifiedCacheSet {
     cache: FileModifiedCache,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Default, Debug)]
 pub struct FileModifiedCacheMap {
     files: HashMap<PathBuf, FileModifiedCache>,
 }
 
+impl FileModifiedCacheMap {
+    pub async fn from_files(files: Vec<PathBuf>) -> Result<Self> {
+        let cache_sets = join_all(files.into_iter().map(get_file_modified_cache)).await?;
+        Ok(Self {
+            files: cache_sets
+                .into_iter()
+                .map(|cache_set| (cache_set.path, cache_set.cache))
+                .collect(),
+        })
+    }
+
+    /// Returns `None` when the cache has not been written yet.
+    pub async fn lenient_load_from_file(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        if path.as_ref().exists() {
+            Ok(Some(Json::parse_from_file(path).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        Json::save_to_file(self, path).await
+    }
+
+    /// Returns true when a file was modified, added or deleted.
+    pub fn has_diff(&self, old: &Self) -> bool {
+        self.files != old.files
+    }
+}
+
 pub const FILE_MODIFIED_CACHE_MAP_FILE_NAME: &str = "file_modifid_cache.v1.json";
 
+#[derive(Clone)]
 pub struct FileDiffHelpers {
     pub target_exts: Vec<String>,
     pub target_file_names: Vec<String>,
@@ -35,11 +66,32 @@ pub struct FileDiffHelpers {
     pub exclude_dir_names: Vec<String>,
 }
 
-pub async fn has_file_diff_in_dir(
+async fn get_file_modified_cache(path: PathBuf) -> Result<FileModifiedCacheSet> {
+    let file = tokio::fs::File::open(&path).await?;
+    let metadata = file.metadata().await?;
+    let modifieded_at = metadata.modified().ok();
+    let size = metadata.len();
+    Ok(FileModifiedCacheSet {
+        path,
+        cache: FileModifiedCache {
+            size,
+            modifieded_at,
+        },
+    })
+}
+
+/// Enumerates the target files in the dir.
+/// The files ignored by `.gitignore` are skipped in addition to the excluded names.
+pub async fn enumurate_target_files(
     dir: impl AsRef<Path>,
-    helpers: FileDiffHelpers,
-    modified_cache_map: FileModifiedCacheMap,
-) -> Result<bool> {
+    helpers: &FileDiffHelpers,
+) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref().to_path_buf();
+    let helpers = helpers.clone();
+    tokio::task::spawn_blocking(move || enumurate_target_files_blocking(&dir, helpers)).await?
+}
+
+fn enumurate_target_files_blocking(dir: &Path, helpers: FileDiffHelpers) -> Result<Vec<PathBuf>> {
     let target_ext_map = helpers.target_exts.into_iter().collect::<HashSet<_>>();
     let target_file_name_map = helpers
         .target_file_names
@@ -53,91 +105,121 @@ pub async fn has_file_diff_in_dir(
         .exclude_dir_names
         .into_iter()
         .collect::<HashSet<_>>();
-    let target_files = enumurate_target_files(
-        dir.as_ref(),
-        &target_ext_map,
-        &target_file_name_map,
-        &exclude_file_name_map,
-        &exclude_dir_name_map,
-    )
-    .await?;
-    let cache_sets = join_all(target_files.into_iter().map(get_file_modified_cache)).await?;
-    for cache in cache_sets.iter() {
-        if let Some(old_cache) = modified_cache_map.files.get(&cache.path) {
-            if &cache.cache != old_cache {
-                return Ok(true);
+    let walk = ignore::WalkBuilder::new(dir)
+        .hidden(false)
+        .ignore(false)
+        .git_global(false)
+        .git_exclude(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            let file_name = entry.file_name().to_string_lossy();
+            if entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+            {
+                !exclude_dir_name_map.contains(file_name.as_ref())
+            } else {
+                !exclude_file_name_map.contains(file_name.as_ref())
             }
+        })
+        .build();
+    let mut paths = vec![];
+    for entry in walk {
+        let entry = entry?;
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let path = entry.into_path();
+        let is_target_file_name = path.file_name().is_some_and(|file_name| {
+            target_file_name_map.contains(file_name.to_string_lossy().as_ref())
+        });
+        let is_target_ext = path
+            .extension()
+            .is_some_and(|ext| target_ext_map.contains(ext.to_string_lossy().as_ref()));
+        if is_target_file_name || is_target_ext {
+            paths.push(path)
         }
     }
-    Ok(false)
+    Ok(paths)
 }
 
-async fn get_file_modified_cache(path: PathBuf) -> Result<FileModifiedCacheSet> {
-    let file = tokio::fs::File::open(&path).await?;
-    let metadata = file.metadata().await?;
-    let modifieded_at = if let Ok(modifieded_at) = metadata.modified() {
-        Some(modifieded_at)
-    } else {
-        None
-    };
-    let size = metadata.len();
-    Ok(FileModifiedCacheSet {
-        path,
-        cache: FileModifiedCache {
-            size,
-            modifieded_at,
-        },
-    })
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
 
-#[async_recursion::async_recursion]
-pub async fn enumurate_target_files(
-    dir: impl AsRef<Path> + std::marker::Send + std::marker::Sync + 'async_recursion,
-    target_ext_map: &HashSet<String>,
-    target_file_name_map: &HashSet<String>,
-    exclude_file_name_map: &HashSet<String>,
-    exclude_dir_name_map: &HashSet<String>,
-) -> Result<Vec<PathBuf>> {
-    let mut read_dir = fs_ext::read_dir(dir).await?;
-    let mut paths = vec![];
-    while let Some(entry) = read_dir.next_entry().await? {
-        let path = entry.path();
-        if path.is_dir() {
-            if let Some(file_name) = path.file_name() {
-                let file_name = file_name.to_string_lossy();
-                if exclude_dir_name_map.get(file_name.as_ref()).is_some() {
-                    continue;
-                }
-            }
-            paths.extend_from_slice(
-                &enumurate_target_files(
-                    path,
-                    target_ext_map,
-                    target_file_name_map,
-                    exclude_file_name_map,
-                    exclude_dir_name_map,
-                )
-                .await?,
-            );
-        } else {
-            if let Some(file_name) = path.file_name() {
-                let file_name = file_name.to_string_lossy();
-                if exclude_file_name_map.get(file_name.as_ref()).is_some() {
-                    continue;
-                }
-            }
-            if let Some(ext) = path.extension() {
-                let ext = ext.to_string_lossy();
-                if target_ext_map.get(ext.as_ref()).is_some() {
-                    paths.push(path)
-                }
-            } else if let Some(file_name) = path.file_name() {
-                let file_name = file_name.to_string_lossy();
-                if target_file_name_map.get(file_name.as_ref()).is_some() {
-                    paths.push(path)
-                }
-            }
+    fn cache_map(files: &[(&str, u64)]) -> FileModifiedCacheMap {
+        FileModifiedCacheMap {
+            files: files
+                .iter()
+                .map(|(path, size)| {
+                    (
+                        PathBuf::from(path),
+                        FileModifiedCache {
+                            size: *size,
+                            modifieded_at: None,
+                        },
+                    )
+                })
+                .collect(),
         }
     }
-    Ok(paths)
+
+    #[rstest]
+    #[case(cache_map(&[("a.rs", 1)]), cache_map(&[("a.rs", 1)]), false)]
+    #[case(cache_map(&[("a.rs", 2)]), cache_map(&[("a.rs", 1)]), true)]
+    #[case(cache_map(&[("a.rs", 1), ("b.rs", 1)]), cache_map(&[("a.rs", 1)]), true)]
+    #[case(cache_map(&[("a.rs", 1)]), cache_map(&[("a.rs", 1), ("b.rs", 1)]), true)]
+    fn has_diff_works(
+        #[case] current: FileModifiedCacheMap,
+        #[case] old: FileModifiedCacheMap,
+        #[case] expected: bool,
+    ) {
+        pretty_assertions::assert_eq!(expected, current.has_diff(&old));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn enumurate_target_files_works() {
+        let dir = std::env::temp_dir().join(nanoid::nanoid!());
+        for (path, content) in [
+            ("Cargo.toml", ""),
+            ("README.md", ""),
+            (".gitignore", "/src/generated.rs\n"),
+            ("src/main.rs", ""),
+            ("src/generated.rs", ""),
+            ("target/debug/build.rs", ""),
+        ] {
+            let path = dir.join(path);
+            tokio::fs::create_dir_all(path.parent().unwrap())
+                .await
+                .unwrap();
+            tokio::fs::write(path, content).await.unwrap();
+        }
+        let actual = enumurate_target_files(
+            &dir,
+            &FileDiffHelpers {
+                target_exts: vec!["rs".into()],
+                target_file_names: vec!["Cargo.toml".into()],
+                exclude_file_names: vec![],
+                exclude_dir_names: vec!["target".into()],
+            },
+        )
+        .await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        let mut actual = actual
+            .unwrap()
+            .into_iter()
+            .map(|path| path.strip_prefix(&dir).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        actual.sort();
+        let expected = ["Cargo.toml", "src/main.rs"]
+            .iter()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
 }
@@ -1,6 +1,6 @@
 use super::*;
 use anyhow::anyhow;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use tokio::{
     fs,
     fs::{copy, File, ReadDir},
@@ -52,11 +52,7 @@ pub async fn make_hard_links_in_dir(
         let executable_files = enumerate_executable_files(from_dir).await?;
         for executable_file in executable_files.iter() {
             let file_name = executable_file.file_name().unwrap().to_str().unwrap();
-            let to_file_path = to_dir.join(file_name);
-            if to_file_path.exists() {
-                fs::remove_file(&to_file_path).await?;
-            }
-            fs::hard_link(executable_file, to_file_path).await?;
+            make_hard_link(executable_file, to_dir.join(file_name)).await?;
         }
         Ok(())
     } else {
@@ -64,6 +60,99 @@ pub async fn make_hard_links_in_dir(
     }
 }
 
+/// Makes a hard link at `link` which replaces the existing file.
+pub async fn make_hard_link(original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+    let link = link.as_ref();
+    if let Some(link_dir) = link.parent() {
+        create_dir_if_not_exists(link_dir).await?;
+    }
+    if link.exists() {
+        fs::remove_file(link).await?;
+    }
+    Ok(fs::hard_link(original, link).await?)
+}
+
+#[cfg(unix)]
+pub async fn set_executable(path: impl AsRef<Path>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path.as_ref()).await?.permissions();
+    permissions.set_mode(permissions.mode() | 0o111);
+    Ok(fs::set_permissions(path, permissions).await?)
+}
+
+#[cfg(windows)]
+pub async fn set_executable(_path: impl AsRef<Path>) -> Result<()> {
+    Ok(())
+}
+
+/// Makes a symlink at `link` which points to `original` by the path relative to the link.
+pub async fn make_relative_symlink(
+    original: impl AsRef<Path>,
+    link: impl AsRef<Path>,
+) -> Result<()> {
+    let link = link.as_ref();
+    let link_dir = link
+        .parent()
+        .ok_or_else(|| anyhow!("{} has no parent dir.", link.display()))?;
+    create_dir_if_not_exists(link_dir).await?;
+    remove_file_if_exists(link).await?;
+    symlink(relative_path(link_dir, original.as_ref()), link).await
+}
+
+/// Makes a symlink at `link` which points to `original` by the path as given.
+pub async fn make_symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+    let link = link.as_ref();
+    if let Some(link_dir) = link.parent() {
+        create_dir_if_not_exists(link_dir).await?;
+    }
+    remove_file_if_exists(link).await?;
+    symlink(original, link).await
+}
+
+pub async fn remove_file_if_exists(path: impl AsRef<Path>) -> Result<()> {
+    // symlink_metadata also finds the links whose originals were removed.
+    if fs::symlink_metadata(path.as_ref()).await.is_ok() {
+        fs::remove_file(path).await?;
+    }
+    Ok(())
+}
+
+/// Returns the path to `to` relative to `from_dir`. Both paths should be absolute.
+pub fn relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+    let from_components = from_dir.components().collect::<Vec<_>>();
+    let to_components = to.components().collect::<Vec<_>>();
+    let common_len = from_components
+        .iter()
+        .zip(to_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    from_components[common_len..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(to_components[common_len..].iter().copied())
+        .collect()
+}
+
+/// Returns `path`, which is relative to `from_dir`, as relative to `to_dir`.
+/// Absolute paths are kept as they are.
+pub fn rebase_path(path: &Path, from_dir: &Path, to_dir: &Path) -> PathBuf {
+    if path.is_absolute() || from_dir == to_dir {
+        path.to_path_buf()
+    } else {
+        relative_path(to_dir, &from_dir.join(path))
+    }
+}
+
+#[cfg(unix)]
+async fn symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+    Ok(fs::symlink(original, link).await?)
+}
+
+#[cfg(windows)]
+async fn symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+    Ok(fs::symlink_file(original, link).await?)
+}
+
 #[allow(dead_code)]
 pub async fn clean_dir(dir: impl AsRef<Path>) -> Result<()> {
     let dir = dir.as_ref();
@@ -94,7 +183,13 @@ pub async fn copy_dir(
     let mut rd = read_dir(from_dir.as_ref()).await?;
     while let Some(entry) = rd.next_entry().await? {
         let path = entry.path();
-        if path.is_dir() {
+        if entry.file_type().await?.is_symlink() {
+            let to_path = to_dir
+                .as_ref()
+                .join(path.file_name().unwrap().to_string_lossy().as_ref());
+            remove_file_if_exists(&to_path).await?;
+            symlink(fs::read_link(&path).await?, to_path).await?;
+        } else if path.is_dir() {
             let new_to_dir = to_dir
                 .as_ref()
                 .join(path.file_name().unwrap().to_string_lossy().as_ref());
@@ -111,3 +206,41 @@ pub async fn copy_dir(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        "/w/home/bin",
+        "/w/home/npm/a/node_modules/a/cli.js",
+        "../npm/a/node_modules/a/cli.js"
+    )]
+    #[case("/w/home/bin", "/w/home/bin/a", "a")]
+    #[case("/a/b", "/c", "../../c")]
+    fn relative_path_works(#[case] from_dir: &str, #[case] to: &str, #[case] expected: &str) {
+        pretty_assertions::assert_eq!(
+            PathBuf::from(expected),
+            relative_path(Path::new(from_dir), Path::new(to))
+        );
+    }
+
+    #[rstest]
+    #[case("tools/a", "/w/shared", "/w", "shared/tools/a")]
+    #[case("tools/a", "/w/shared", "/w/root", "../shared/tools/a")]
+    #[case("tools/a", "/w", "/w", "tools/a")]
+    #[case("/opt/a", "/w/shared", "/w", "/opt/a")]
+    fn rebase_path_works(
+        #[case] path: &str,
+        #[case] from_dir: &str,
+        #[case] to_dir: &str,
+        #[case] expected: &str,
+    ) {
+        pretty_assertions::assert_eq!(
+            PathBuf::from(expected),
+            rebase_path(Path::new(path), Path::new(from_dir), Path::new(to_dir))
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use super::*;
+pub mod change_detection;
 pub mod command_ext;
+pub mod download;
+pub mod env_var;
+pub mod file_hash;
 pub mod file_modified;
 pub mod fs_ext;
 pub mod io_ext;
 pub mod join_future;
+pub mod platform;
 pub mod serde_ext;
@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use cargo_platform::{Cfg, CfgExpr, Platform};
+
+use crate::Result;
+
+const CFG_NAMES: [&str; 2] = ["unix", "windows"];
+const CFG_KEYS: [&str; 6] = [
+    "target_os",
+    "target_family",
+    "target_arch",
+    "target_pointer_width",
+    "target_endian",
+    "target_env",
+];
+
+/// A `cfg(...)` expression which the key of a `target` table in the manifest holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetPlatform {
+    cfg: CfgExpr,
+}
+
+impl TargetPlatform {
+    pub fn matches_host(&self) -> bool {
+        self.cfg.matches(&host_cfgs())
+    }
+}
+
+impl FromStr for TargetPlatform {
+    type Err = anyhow::Error;
+
+    // Target names such as `x86_64-unknown-linux-gnu` are not known without rustc, so only the
+    // cfg expressions are allowed, whose names are checked for the typos.
+    fn from_str(platform: &str) -> Result<Self> {
+        let Platform::Cfg(cfg) = Platform::from_str(platform)? else {
+            return Err(anyhow!(
+                "{platform} should be a cfg expression such as cfg(unix)."
+            ));
+        };
+        let mut unknown_cfgs = vec![];
+        collect_unknown_cfgs(&cfg, &mut unknown_cfgs);
+        match unknown_cfgs.first() {
+            Some(unknown_cfg) => Err(anyhow!("{unknown_cfg} is not a known cfg in {platform}.")),
+            None => Ok(Self { cfg }),
+        }
+    }
+}
+
+fn collect_unknown_cfgs(cfg: &CfgExpr, unknown_cfgs: &mut Vec<String>) {
+    match cfg {
+        CfgExpr::Not(cfg) => collect_unknown_cfgs(cfg, unknown_cfgs),
+        CfgExpr::All(cfgs) | CfgExpr::Any(cfgs) => cfgs
+            .iter()
+            .for_each(|cfg| collect_unknown_cfgs(cfg, unknown_cfgs)),
+        CfgExpr::Value(Cfg::Name(name)) if !CFG_NAMES.contains(&name.as_str()) => {
+            unknown_cfgs.push(name.clone())
+        }
+        CfgExpr::Value(Cfg::KeyPair(key, _)) if !CFG_KEYS.contains(&key.as_str()) => {
+            unknown_cfgs.push(key.clone())
+        }
+        CfgExpr::Value(_) => {}
+    }
+}
+
+fn host_cfgs() -> Vec<Cfg> {
+    let target_env = if cfg!(target_env = "gnu") {
+        "gnu"
+    } else if cfg!(target_env = "musl") {
+        "musl"
+    } else if cfg!(target_env = "msvc") {
+        "msvc"
+    } else {
+        ""
+    };
+    let key_pairs = [
+        ("target_os", std::env::consts::OS),
+        ("target_family", std::env::consts::FAMILY),
+        ("target_arch", std::env::consts::ARCH),
+        (
+            "target_pointer_width",
+            if cfg!(target_pointer_width = "64") {
+                "64"
+            } else {
+                "32"
+            },
+        ),
+        (
+            "target_endian",
+            if cfg!(target_endian = "little") {
+                "little"
+            } else {
+                "big"
+            },
+        ),
+        ("target_env", target_env),
+    ];
+    std::iter::once(std::env::consts::FAMILY)
+        .filter(|family| CFG_NAMES.contains(family))
+        .map(|family| Cfg::Name(family.into()))
+        .chain(
+            key_pairs
+                .into_iter()
+                .map(|(key, value)| Cfg::KeyPair(key.into(), value.into())),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("cfg(unix)", true)]
+    #[case("cfg(all(target_os = \"linux\", target_arch = \"aarch64\"))", true)]
+    #[case("cfg(not(windows))", true)]
+    #[case("cfg(unixx)", false)]
+    #[case("cfg(target_oss = \"linux\")", false)]
+    #[case("x86_64-unknown-linux-gnu", false)]
+    #[case("cfg(unix", false)]
+    fn target_platform_from_str_works(#[case] platform: &str, #[case] expected: bool) {
+        pretty_assertions::assert_eq!(expected, TargetPlatform::from_str(platform).is_ok());
+    }
+
+    #[rstest]
+    fn target_platform_matches_host_works() {
+        let host = TargetPlatform::from_str(&format!(
+            "cfg(all(target_os = \"{}\", target_arch = \"{}\"))",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ))
+        .unwrap();
+        assert!(host.matches_host());
+        let other = TargetPlatform::from_str("cfg(target_os = \"none\")").unwrap();
+        assert!(!other.matches_host());
+    }
+}
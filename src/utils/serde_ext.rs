@@ -24,6 +24,18 @@ impl Json {
         Self::deserialize_str(&s, path)
     }
 
+    /// Parses the file with the seed, which carries what the value is deserialized with.
+    pub async fn parse_from_file_with_seed<T>(
+        seed: impl for<'de> serde::de::DeserializeSeed<'de, Value = T>,
+        path: impl AsRef<Path>,
+    ) -> Result<T> {
+        let s = read_string_for_deserialize(path.as_ref()).await?;
+        let mut deserializer = serde_json::Deserializer::from_str(&s);
+        seed.deserialize(&mut deserializer)
+            .and_then(|value| deserializer.end().map(|_| value))
+            .map_err(|e| convert_deserialize_json_error(e, path, &s).into())
+    }
+
     pub async fn parse_or_default_if_not_found<T: serde::de::DeserializeOwned + Default>(
         path: impl AsRef<Path>,
     ) -> Result<T> {
@@ -61,6 +73,15 @@ impl Yaml {
         Self::deserialize_str(&s, path)
     }
 
+    pub async fn parse_from_file_with_seed<T>(
+        seed: impl for<'de> serde::de::DeserializeSeed<'de, Value = T>,
+        path: impl AsRef<Path>,
+    ) -> Result<T> {
+        let s = read_string_for_deserialize(path.as_ref()).await?;
+        seed.deserialize(serde_yaml::Deserializer::from_str(&s))
+            .map_err(|e| convert_deserialize_yaml_error(e, path, &s).into())
+    }
+
     #[allow(dead_code)]
     pub async fn parse_or_default_if_not_found<T: serde::de::DeserializeOwned + Default>(
         path: impl AsRef<Path>,
@@ -98,6 +119,15 @@ impl Toml {
         Self::deserialize_str(&s, path)
     }
 
+    pub async fn parse_from_file_with_seed<T>(
+        seed: impl for<'de> serde::de::DeserializeSeed<'de, Value = T>,
+        path: impl AsRef<Path>,
+    ) -> Result<T> {
+        let s = read_string_for_deserialize(path.as_ref()).await?;
+        seed.deserialize(&mut toml::Deserializer::new(&s))
+            .map_err(|e| convert_deserialize_toml_error(e, path, &s).into())
+    }
+
     #[allow(dead_code)]
     pub async fn parse_or_default_if_not_found<T: serde::de::DeserializeOwned + Default>(
         path: impl AsRef<Path>,